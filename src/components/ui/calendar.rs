@@ -1,38 +1,51 @@
-use super::utils::merge_class;
-use crate::time::{Duration, NaiveDate};
+use super::utils::{merge_class, use_today};
+use crate::i18n::use_i18n;
+use crate::time::{DateStyle, Duration, Locale, NaiveDate, Weekday};
 use dioxus::prelude::*;
-const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+use std::cell::RefCell;
+use std::rc::Rc;
 
-fn first_day_of_month(date: NaiveDate) -> NaiveDate {
-    date.with_day(1).unwrap_or(date)
+/// The first of the 42 days shown for `month`.
+fn grid_start(month: NaiveDate, first_day_of_week: Weekday) -> NaiveDate {
+    let month = month.first_day_of_month();
+    month - Duration::days(month.weekday().num_days_from(first_day_of_week) as i64)
 }
 
-fn next_month(date: NaiveDate) -> NaiveDate {
-    let year = if date.month() == 12 {
-        date.year() + 1
-    } else {
-        date.year()
-    };
-    let month = if date.month() == 12 {
-        1
-    } else {
-        date.month() + 1
-    };
-    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date)
+/// Where a key moves the keyboard focus from `day`: arrows by a day or a week, PageUp / PageDown by a month (a
+/// year with Shift), Home / End to the bounds of the week. `None` for keys the grid leaves alone.
+fn key_target(
+    day: NaiveDate,
+    key: &Key,
+    shift: bool,
+    first_day_of_week: Weekday,
+) -> Option<NaiveDate> {
+    let months = if shift { 12 } else { 1 };
+    let into_week = day.weekday().num_days_from(first_day_of_week) as i64;
+    match key {
+        Key::ArrowLeft => Some(day - Duration::days(1)),
+        Key::ArrowRight => Some(day + Duration::days(1)),
+        Key::ArrowUp => Some(day - Duration::days(7)),
+        Key::ArrowDown => Some(day + Duration::days(7)),
+        Key::PageUp => Some(day.add_months(-months)),
+        Key::PageDown => Some(day.add_months(months)),
+        Key::Home => Some(day - Duration::days(into_week)),
+        Key::End => Some(day + Duration::days(6 - into_week)),
+        _ => None,
+    }
 }
 
-fn previous_month(date: NaiveDate) -> NaiveDate {
-    let year = if date.month() == 1 {
-        date.year() - 1
-    } else {
-        date.year()
-    };
-    let month = if date.month() == 1 {
-        12
-    } else {
-        date.month() - 1
-    };
-    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date)
+/// `target` when `available`, else the nearest available day beyond it, else the nearest one back towards
+/// `from`, which keeps the focus at `min` / `max` instead of stopping short of them. Looks a year ahead at most.
+fn nearest_available(
+    from: NaiveDate,
+    target: NaiveDate,
+    available: impl Fn(NaiveDate) -> bool,
+) -> Option<NaiveDate> {
+    let step = Duration::days(if target < from { -1 } else { 1 });
+    let beyond = (0..366).map(|offset| target + Duration::days(step.num_days() * offset));
+    let back = (1..(target - from).num_days().abs())
+        .map(|offset| target - Duration::days(step.num_days() * offset));
+    beyond.chain(back).find(|day| available(*day))
 }
 
 #[component]
@@ -41,24 +54,96 @@ pub fn Calendar(
     #[props(optional)] selected: Option<NaiveDate>,
     #[props(default)] show_outside_days: bool,
     #[props(optional)] on_select: Option<EventHandler<NaiveDate>>,
+    /// Days before `min` or after `max` cannot be picked.
+    #[props(optional)]
+    min: Option<NaiveDate>,
+    #[props(optional)] max: Option<NaiveDate>,
+    /// Days for which this returns `true` cannot be picked either, e.g. weekends.
+    #[props(optional)]
+    disabled_date: Option<Callback<NaiveDate, bool>>,
+    /// Focus the selected day (or today) when mounted, so the grid can be used from the keyboard right away.
+    #[props(default)]
+    autofocus: bool,
+    /// Month title and weekday names, the app's language by default.
+    #[props(optional)]
+    locale: Option<Locale>,
+    /// Defaults to the locale's, Monday for `zh-CN`.
+    #[props(optional)]
+    first_day_of_week: Option<Weekday>,
     #[props(into, default)] class: Option<String>,
 ) -> Element {
-    let starting_month = first_day_of_month(initial_month);
-    let month = use_signal(move || starting_month);
-    let selection = use_signal(move || selected);
+    let today = use_today();
+    let i18n = use_i18n();
+    let locale = locale.unwrap_or(i18n.locale());
+    let first_day_of_week = first_day_of_week.unwrap_or(locale.first_day_of_week());
+    let starting_month = initial_month.first_day_of_month();
+    let mut month = use_signal(move || starting_month);
+    let mut selection = use_signal(move || selected);
+    let mut focused = use_signal(move || {
+        selected
+            .filter(|day| day.first_day_of_month() == starting_month)
+            .unwrap_or(today)
+    });
+    let mut focus_pending = use_signal(move || autofocus);
+    let day_refs = use_hook(|| Rc::new(RefCell::new(vec![None::<Rc<MountedData>>; 42])));
     let mut month_signal = month.clone();
     let on_select_handler = on_select.clone();
 
+    // Follow the caller when it changes the selection itself, e.g. after a date is typed.
+    use_effect(use_reactive((&selected,), move |(selected,)| {
+        if *selection.peek() != selected {
+            selection.set(selected);
+            if let Some(day) = selected {
+                month.set(day.first_day_of_month());
+                focused.set(day);
+            }
+        }
+    }));
+
+    let available = move |day: NaiveDate| {
+        !(min.is_some_and(|min| day < min)
+            || max.is_some_and(|max| day > max)
+            || disabled_date.is_some_and(|disabled| disabled.call(day)))
+    };
+
     let active_month = month();
-    let month_label = active_month.format("%B %Y").to_string();
-    let start_weekday = active_month.weekday().num_days_from_monday() as i64;
-    let mut first_visible = active_month - Duration::days(start_weekday);
+    let month_label = active_month
+        .format_localized(locale.pattern(DateStyle::YearMonth), locale)
+        .to_string();
+    let mut first_visible = grid_start(active_month, first_day_of_week);
     let mut days = Vec::with_capacity(42);
     for _ in 0..42 {
         days.push(first_visible);
         first_visible = first_visible + Duration::days(1);
     }
 
+    // The one day reachable with Tab: the focused day while it is in view, else the first day that can be picked.
+    let in_view = |day: &NaiveDate| day.first_day_of_month() == active_month && available(*day);
+    let tab_stop = Some(focused())
+        .filter(in_view)
+        .or_else(|| days.iter().copied().find(in_view));
+
+    // Keyboard moves land after the grid re-renders for the new month.
+    {
+        let day_refs = day_refs.clone();
+        use_effect(move || {
+            if !focus_pending() {
+                return;
+            }
+            let day = focused();
+            let index = (day - grid_start(month(), first_day_of_week)).num_days();
+            let mounted = usize::try_from(index)
+                .ok()
+                .and_then(|index| day_refs.borrow().get(index).cloned().flatten());
+            if let Some(mounted) = mounted {
+                focus_pending.set(false);
+                spawn(async move {
+                    _ = mounted.set_focus(true).await;
+                });
+            }
+        });
+    }
+
     let current_selection = selection();
 
     rsx! {
@@ -69,9 +154,9 @@ pub fn Calendar(
                 button {
                     class: "ui-calendar-nav",
                     r#type: "button",
-                    "aria-label": "Go to previous month",
+                    "aria-label": i18n.t("calendar.previous_month"),
                     onclick: move |_| {
-                        month_signal.set(previous_month(active_month));
+                        month_signal.set(active_month.add_months(-1));
                     },
                     "‹"
                 }
@@ -79,43 +164,75 @@ pub fn Calendar(
                 button {
                     class: "ui-calendar-nav",
                     r#type: "button",
-                    "aria-label": "Go to next month",
+                    "aria-label": i18n.t("calendar.next_month"),
                     onclick: move |_| {
-                        month_signal.set(next_month(active_month));
+                        month_signal.set(active_month.add_months(1));
                     },
                     "›"
                 }
             }
             div {
                 class: "ui-calendar-weekdays",
-                for label in WEEKDAY_LABELS {
-                    span { class: "ui-calendar-weekday", "{label}" }
+                for weekday in Weekday::week_from(first_day_of_week) {
+                    span { class: "ui-calendar-weekday", {locale.short_weekday_name(weekday)} }
                 }
             }
             div {
                 class: "ui-calendar-grid",
-                for day in days {
+                onkeydown: move |evt: KeyboardEvent| {
+                    let Some(from) = tab_stop else {
+                        return;
+                    };
+                    let Some(target) = key_target(from, &evt.key(), evt.modifiers().shift(), first_day_of_week) else {
+                        return;
+                    };
+                    evt.prevent_default();
+                    if let Some(next) = nearest_available(from, target, available) {
+                        focused.set(next);
+                        month.set(next.first_day_of_month());
+                        focus_pending.set(true);
+                    }
+                },
+                for (index, day) in days.into_iter().enumerate() {
                     {
                         let is_current_month = day.month() == active_month.month();
                         let is_selected = current_selection
                             .as_ref()
                             .map(|selected| *selected == day)
                             .unwrap_or(false);
-                        let is_disabled = !show_outside_days && !is_current_month;
+                        let is_disabled = (!show_outside_days && !is_current_month) || !available(day);
+                        let is_tab_stop = tab_stop == Some(day);
                         let day_display = day.day();
                         let mut selection_signal = selection.clone();
                         let handler = on_select_handler.clone();
+                        let day_refs = day_refs.clone();
 
                         rsx! {
                             button {
                                 class: "ui-calendar-day",
                                 r#type: "button",
+                                tabindex: if is_tab_stop { "0" } else { "-1" },
                                 "data-state": if is_selected { "selected" } else { "idle" },
                                 "data-outside": if is_current_month { "false" } else { "true" },
+                                "data-today": if day == today { "true" } else { "false" },
                                 disabled: is_disabled,
+                                onmounted: move |event: MountedEvent| {
+                                    let mounted = event.data();
+                                    if let Some(slot) = day_refs.borrow_mut().get_mut(index) {
+                                        *slot = Some(mounted.clone());
+                                    }
+                                    if is_tab_stop && *focus_pending.peek() {
+                                        focus_pending.set(false);
+                                        spawn(async move {
+                                            _ = mounted.set_focus(true).await;
+                                        });
+                                    }
+                                },
+                                onfocus: move |_| focused.set(day),
                                 onclick: move |_| {
                                     if !is_disabled {
                                         selection_signal.set(Some(day));
+                                        focused.set(day);
                                         if let Some(callback) = handler.clone() {
                                             callback.call(day);
                                         }
@@ -130,3 +247,70 @@ pub fn Calendar(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn keys_move_by_days_weeks_and_months() {
+        // 2024-06-12 is a Wednesday.
+        let day = date(6, 12);
+        let monday = Weekday::Monday;
+        assert_eq!(
+            key_target(day, &Key::ArrowLeft, false, monday),
+            Some(date(6, 11))
+        );
+        assert_eq!(
+            key_target(day, &Key::ArrowDown, false, monday),
+            Some(date(6, 19))
+        );
+        assert_eq!(
+            key_target(day, &Key::PageUp, false, monday),
+            Some(date(5, 12))
+        );
+        assert_eq!(
+            key_target(day, &Key::PageDown, true, monday),
+            NaiveDate::from_ymd_opt(2025, 6, 12)
+        );
+        assert_eq!(
+            key_target(day, &Key::Home, false, monday),
+            Some(date(6, 10))
+        );
+        assert_eq!(key_target(day, &Key::End, false, monday), Some(date(6, 16)));
+        assert_eq!(
+            key_target(day, &Key::Home, false, Weekday::Sunday),
+            Some(date(6, 9))
+        );
+        assert_eq!(key_target(day, &Key::Enter, false, monday), None);
+        assert_eq!(grid_start(date(6, 12), monday), date(5, 27));
+    }
+
+    #[test]
+    fn moves_skip_unavailable_days_and_stop_at_bounds() {
+        let weekday = |day: NaiveDate| day.weekday().num_days_from_monday() < 5;
+        // Friday 2024-06-14 → the weekend is skipped.
+        assert_eq!(
+            nearest_available(date(6, 14), date(6, 15), weekday),
+            Some(date(6, 17))
+        );
+        assert_eq!(
+            nearest_available(date(6, 17), date(6, 16), weekday),
+            Some(date(6, 14))
+        );
+        // A month ahead is past `max`, so the focus stops on `max`.
+        let max = date(6, 20);
+        assert_eq!(
+            nearest_available(date(6, 18), date(7, 18), |day| day <= max),
+            Some(max)
+        );
+        assert_eq!(
+            nearest_available(date(6, 20), date(6, 21), |day| day <= max),
+            None
+        );
+    }
+}
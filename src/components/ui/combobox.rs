@@ -1,5 +1,6 @@
 use super::input::Input;
 use super::utils::merge_class;
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 #[derive(Clone, PartialEq)]
 pub struct ComboboxOption {
@@ -36,7 +37,9 @@ pub fn Combobox(
 ) -> Element {
     let classes = merge_class("ui-combobox", class);
     let trigger_id = id.unwrap_or_default();
-    let search_placeholder_text = search_placeholder.unwrap_or_else(|| "Search...".to_string());
+    let i18n = use_i18n();
+    let search_placeholder_text =
+        search_placeholder.unwrap_or_else(|| i18n.t("combobox.search_placeholder"));
     let mut open = use_signal(|| false);
     let mut current_selection = use_signal(move || selected.clone());
     let mut query = use_signal(|| String::new());
@@ -135,6 +138,7 @@ fn ComboboxContent(
     current_value: Option<String>,
     on_select: EventHandler<String>,
 ) -> Element {
+    let i18n = use_i18n();
     rsx! {
         div {
             class: "ui-combobox-content",
@@ -151,7 +155,7 @@ fn ComboboxContent(
             if filtered_options.is_empty() {
                 div {
                     class: "ui-combobox-empty",
-                    "No results found"
+                    {i18n.t("combobox.empty")}
                 }
             } else {
                 ul {
@@ -1,4 +1,5 @@
 use super::input::Input;
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 
 #[derive(Clone, PartialEq)]
@@ -34,8 +35,10 @@ impl CommandItem {
 pub fn CommandPalette(
     #[props(into)] items: Vec<CommandItem>,
     #[props(optional)] on_select: Option<EventHandler<String>>,
-    #[props(into, default = "Search commands".to_string())] placeholder: String,
+    #[props(into, default)] placeholder: Option<String>,
 ) -> Element {
+    let i18n = use_i18n();
+    let placeholder = placeholder.unwrap_or_else(|| i18n.t("command.search_placeholder"));
     let mut query = use_signal(|| String::new());
 
     let mut filtered = items.clone();
@@ -101,7 +104,7 @@ pub fn CommandPalette(
             div {
                 class: "ui-command-list",
                 if command_nodes.is_empty() {
-                    span { style: "padding: 0.6rem 0.9rem; color: hsl(var(--muted-foreground));", "No results" }
+                    span { style: "padding: 0.6rem 0.9rem; color: hsl(var(--muted-foreground));", {i18n.t("command.empty")} }
                 } else {
                     {command_nodes.into_iter()}
                 }
@@ -0,0 +1,286 @@
+use super::calendar::Calendar;
+use super::form_field::{FormMessage, FormMessageVariant};
+use super::input::Input;
+use super::popover::{Popover, PopoverHandle};
+use super::utils::{merge_class, use_today};
+use crate::i18n::{use_i18n, I18n};
+use crate::time::{Locale, NaiveDate, ParseError, Weekday};
+use dioxus::prelude::*;
+
+/// Why typed text was not accepted as a date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DateInputError {
+    Invalid(ParseError),
+    BeforeMin(NaiveDate),
+    AfterMax(NaiveDate),
+    Unavailable(NaiveDate),
+}
+
+impl DateInputError {
+    fn message(self, i18n: I18n, pattern: &str, today: NaiveDate) -> String {
+        let shown = |date: NaiveDate| date.format(pattern).to_string();
+        match self {
+            DateInputError::Invalid(err) => i18n.t_with(
+                "date_picker.invalid",
+                &[
+                    ("position", &(err.position() + 1)),
+                    ("example", &shown(today)),
+                ],
+            ),
+            DateInputError::BeforeMin(min) => {
+                i18n.t_with("date_picker.before_min", &[("date", &shown(min))])
+            }
+            DateInputError::AfterMax(max) => {
+                i18n.t_with("date_picker.after_max", &[("date", &shown(max))])
+            }
+            DateInputError::Unavailable(date) => {
+                i18n.t_with("date_picker.unavailable", &[("date", &shown(date))])
+            }
+        }
+    }
+}
+
+/// Reads typed text laid out as `pattern`; blank text clears the date.
+fn read_date(
+    text: &str,
+    pattern: &str,
+    min: Option<NaiveDate>,
+    max: Option<NaiveDate>,
+    disabled: impl Fn(NaiveDate) -> bool,
+) -> Result<Option<NaiveDate>, DateInputError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let date = NaiveDate::parse_from_str(text, pattern).map_err(DateInputError::Invalid)?;
+    match (min, max) {
+        (Some(min), _) if date < min => Err(DateInputError::BeforeMin(min)),
+        (_, Some(max)) if date > max => Err(DateInputError::AfterMax(max)),
+        _ if disabled(date) => Err(DateInputError::Unavailable(date)),
+        _ => Ok(Some(date)),
+    }
+}
+
+/// A date field: the date can be typed as `pattern` or picked from a `Calendar` in a popover, whose grid can be
+/// walked with the arrow keys, PageUp / PageDown and Home / End. Typed text is checked when the field loses
+/// focus or Enter is pressed; text that is not a date, or a date that cannot be picked, keeps the value as it was
+/// and shows why below the field.
+#[component]
+pub fn DatePicker(
+    mut value: Signal<Option<NaiveDate>>,
+    #[props(optional)] on_change: Option<EventHandler<Option<NaiveDate>>>,
+    /// How the date is shown and typed, with the specifiers of `NaiveDate::format`. Month names are English.
+    #[props(into, default = "%Y-%m-%d".to_string())]
+    pattern: String,
+    #[props(optional)] min: Option<NaiveDate>,
+    #[props(optional)] max: Option<NaiveDate>,
+    /// Days for which this returns `true` cannot be picked or typed, e.g. weekends.
+    #[props(optional)]
+    disabled_date: Option<Callback<NaiveDate, bool>>,
+    /// Month titles and weekday names, the app's language by default.
+    #[props(optional)]
+    locale: Option<Locale>,
+    /// Defaults to the locale's, Monday for `zh-CN`.
+    #[props(optional)]
+    first_day_of_week: Option<Weekday>,
+    #[props(into, default)] placeholder: Option<String>,
+    #[props(into, default)] id: Option<String>,
+    #[props(into, default)] name: Option<String>,
+    #[props(default)] disabled: bool,
+    #[props(into, default)] class: Option<String>,
+) -> Element {
+    let i18n = use_i18n();
+    let today = use_today();
+    let locale = locale.unwrap_or(i18n.locale());
+    let shown = {
+        let pattern = pattern.clone();
+        move |date: Option<NaiveDate>| {
+            date.map(|date| date.format(&pattern).to_string())
+                .unwrap_or_default()
+        }
+    };
+    let mut text = use_signal({
+        let shown = shown.clone();
+        move || shown(value())
+    });
+    let mut error = use_signal(|| None::<DateInputError>);
+
+    // Show the value again whenever it changes, whether picked, typed or set by the caller.
+    {
+        let shown = shown.clone();
+        use_effect(move || {
+            text.set(shown(value()));
+            error.set(None);
+        });
+    }
+
+    let commit = move |next: Option<NaiveDate>| {
+        value.set(next);
+        if let Some(handler) = on_change {
+            handler.call(next);
+        }
+    };
+
+    let read_typed = {
+        let pattern = pattern.clone();
+        let mut commit = commit;
+        move |typed: String| {
+            let disabled = |date| disabled_date.is_some_and(|disabled| disabled.call(date));
+            match read_date(&typed, &pattern, min, max, disabled) {
+                Ok(next) if next == value() => {
+                    text.set(shown(next));
+                    error.set(None);
+                }
+                Ok(next) => commit(next),
+                Err(err) => error.set(Some(err)),
+            }
+        }
+    };
+
+    let placeholder = placeholder.unwrap_or_else(|| {
+        i18n.t_with(
+            "date_picker.placeholder",
+            &[("example", &today.format(&pattern))],
+        )
+    });
+    let message = error().map(|err| err.message(i18n, &pattern, today));
+    let invalid = message.is_some();
+
+    rsx! {
+        div {
+            class: merge_class("ui-date-picker", class),
+            "data-invalid": if invalid { "true" } else { "false" },
+            div {
+                class: "ui-date-picker-field",
+                Input {
+                    id,
+                    name,
+                    value: text(),
+                    placeholder,
+                    autocomplete: "off",
+                    disabled,
+                    on_input: move |event: FormEvent| text.set(event.value()),
+                    on_change: move |event: FormEvent| {
+                        let mut read_typed = read_typed.clone();
+                        read_typed(event.value())
+                    },
+                }
+                if !disabled {
+                    Popover {
+                        placement: "bottom".to_string(),
+                        trigger: rsx! {
+                            button {
+                                class: "ui-date-picker-trigger",
+                                r#type: "button",
+                                "aria-label": i18n.t("date_picker.open"),
+                                "📅"
+                            }
+                        },
+                        content: rsx! {
+                            DatePickerPanel {
+                                value: value(),
+                                on_pick: commit,
+                                min,
+                                max,
+                                disabled_date,
+                                locale,
+                                first_day_of_week,
+                            }
+                        },
+                    }
+                }
+            }
+            if let Some(message) = message {
+                FormMessage { variant: FormMessageVariant::Error, "{message}" }
+            }
+        }
+    }
+}
+
+/// The popover content of [`DatePicker`], a child of the `Popover` so it can close it.
+#[component]
+fn DatePickerPanel(
+    value: Option<NaiveDate>,
+    on_pick: EventHandler<Option<NaiveDate>>,
+    min: Option<NaiveDate>,
+    max: Option<NaiveDate>,
+    disabled_date: Option<Callback<NaiveDate, bool>>,
+    locale: Locale,
+    first_day_of_week: Option<Weekday>,
+) -> Element {
+    let today = use_today();
+    let popover = try_use_context::<PopoverHandle>();
+    let initial_month = value
+        .or_else(|| min.filter(|min| *min > today))
+        .or_else(|| max.filter(|max| *max < today))
+        .unwrap_or(today);
+    let close = move || {
+        if let Some(mut handle) = popover {
+            handle.state.set(false);
+        }
+    };
+
+    rsx! {
+        div {
+            class: "ui-date-picker-panel",
+            onkeydown: move |evt: KeyboardEvent| {
+                if evt.key() == Key::Escape {
+                    close();
+                }
+            },
+            Calendar {
+                initial_month,
+                selected: value,
+                min,
+                max,
+                disabled_date,
+                autofocus: true,
+                locale,
+                first_day_of_week: first_day_of_week.unwrap_or(locale.first_day_of_week()),
+                on_select: move |day: NaiveDate| {
+                    on_pick.call(Some(day));
+                    close();
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_dates_are_read_and_checked() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        let (min, max) = (Some(date(3)), Some(date(28)));
+        let weekend = |day: NaiveDate| day.weekday().num_days_from_monday() >= 5;
+        let read = |text: &str| read_date(text, "%Y/%m/%d", min, max, weekend);
+
+        assert_eq!(read(" 2024/6/12 "), Ok(Some(date(12))));
+        assert_eq!(read(""), Ok(None));
+        assert!(matches!(
+            read("2024-06-12"),
+            Err(DateInputError::Invalid(_))
+        ));
+        assert_eq!(read("2024/06/01"), Err(DateInputError::BeforeMin(date(3))));
+        assert_eq!(read("2024/06/30"), Err(DateInputError::AfterMax(date(28))));
+        assert_eq!(
+            read("2024/06/15"),
+            Err(DateInputError::Unavailable(date(15)))
+        );
+
+        let i18n = I18n::new(Locale::EnUs);
+        assert_eq!(
+            DateInputError::BeforeMin(date(3)).message(i18n, "%Y/%m/%d", date(12)),
+            "The date cannot be before 2024/06/03"
+        );
+        let Err(invalid) = read("2024-06-12") else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            invalid.message(i18n, "%Y/%m/%d", date(12)),
+            "Check character 5 and enter the date like 2024/06/12"
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use super::button::{Button, ButtonSize, ButtonVariant};
-use super::utils::merge_class;
+use super::checkbox::Checkbox;
+use super::utils::{merge_class, use_today};
 use crate::{
     components::ui::PopoverHandle,
-    time::{Duration, NaiveDate},
+    i18n::{use_i18n, I18n},
+    time::{DateStyle, Duration, Locale, NaiveDate, Weekday},
 };
 use dioxus::prelude::*;
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::SystemTime;
+use std::rc::Rc;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DateRange {
@@ -26,77 +27,198 @@ impl DateRange {
     pub fn contains(&self, date: &NaiveDate) -> bool {
         *date >= self.start && *date <= self.end
     }
+
+    /// Days in the range, both ends included.
+    pub fn len_days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+
+    /// The range just as long that ends the day before this one starts, e.g. June 1–30 → May 2–31.
+    pub fn previous_period(&self) -> DateRange {
+        let end = self.start - Duration::days(1);
+        DateRange::new(end - Duration::days(self.len_days() - 1), end)
+    }
+
+    /// The part of the range between `min` and `max`; `None` when it lies entirely outside them.
+    pub fn clamp(&self, min: Option<NaiveDate>, max: Option<NaiveDate>) -> Option<DateRange> {
+        let start = min.map_or(self.start, |min| self.start.max(min));
+        let end = max.map_or(self.end, |max| self.end.min(max));
+        (start <= end).then_some(DateRange { start, end })
+    }
+}
+
+/// A confirmed range together with the period it is compared against, see [`DateRangePicker`]'s `compare`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateRangeComparison {
+    pub primary: DateRange,
+    pub previous: DateRange,
+}
+
+impl DateRangeComparison {
+    pub fn with_previous_period(primary: DateRange) -> Self {
+        Self {
+            primary,
+            previous: primary.previous_period(),
+        }
+    }
 }
 
-const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+type PresetFn = dyn Fn(NaiveDate, Weekday) -> DateRange;
 
-fn first_day_of_month(date: NaiveDate) -> NaiveDate {
-    date.with_day(1).unwrap_or(date)
+/// A shortcut offered above the calendars: a label and the range it picks, worked out from today (in the
+/// business time zone) and the first day of the week. Presets compare by identity, like table renderers.
+pub struct DateRangePreset {
+    label: String,
+    range: Rc<PresetFn>,
 }
 
-fn add_months(date: NaiveDate, offset: i32) -> NaiveDate {
-    let mut year = date.year();
-    let mut month = date.month() as i32 + offset;
-    while month > 12 {
-        month -= 12;
-        year += 1;
+impl DateRangePreset {
+    pub fn new(
+        label: impl Into<String>,
+        range: impl Fn(NaiveDate, Weekday) -> DateRange + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            range: Rc::new(range),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn range(&self, today: NaiveDate, first_day_of_week: Weekday) -> DateRange {
+        (self.range)(today, first_day_of_week)
+    }
+
+    /// The last `days` days, today included.
+    pub fn last_days(days: u32, i18n: I18n) -> Self {
+        let days = days.max(1);
+        Self::new(
+            i18n.t_count("date_range.last_days", days as usize, &[]),
+            move |today, _| DateRange::new(today - Duration::days(days as i64 - 1), today),
+        )
+    }
+
+    pub fn this_week(i18n: I18n) -> Self {
+        Self::new(i18n.t("date_range.this_week"), quick_range_week)
     }
-    while month < 1 {
-        month += 12;
-        year -= 1;
+
+    pub fn this_month(i18n: I18n) -> Self {
+        Self::new(i18n.t("date_range.this_month"), |today, _| {
+            quick_range_month(today)
+        })
+    }
+
+    pub fn previous_month(i18n: I18n) -> Self {
+        Self::new(i18n.t("date_range.previous_month"), |today, _| {
+            quick_range_month(today.first_day_of_month().add_months(-1))
+        })
+    }
+
+    /// From the first day of this quarter to today.
+    pub fn quarter_to_date(i18n: I18n) -> Self {
+        Self::new(i18n.t("date_range.quarter_to_date"), |today, _| {
+            DateRange::new(today.first_day_of_quarter(), today)
+        })
+    }
+
+    /// From January 1 to today.
+    pub fn year_to_date(i18n: I18n) -> Self {
+        Self::new(i18n.t("date_range.year_to_date"), |today, _| {
+            let january_first =
+                NaiveDate::from_ymd_opt(today.year(), 1, 1).expect("every year has a January 1");
+            DateRange::new(january_first, today)
+        })
+    }
+
+    /// This week and this month, offered when a picker is given no presets.
+    pub fn defaults(i18n: I18n) -> Vec<Self> {
+        vec![Self::this_week(i18n), Self::this_month(i18n)]
     }
-    NaiveDate::from_ymd_opt(year, month as u32, 1).unwrap_or(date)
 }
 
-fn last_day_of_month(date: NaiveDate) -> NaiveDate {
-    let first = first_day_of_month(date);
-    let next_month = add_months(first, 1);
-    next_month - Duration::days(1)
+impl Clone for DateRangePreset {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            range: self.range.clone(),
+        }
+    }
 }
 
-fn quick_range_week(today: NaiveDate) -> DateRange {
-    let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
-    let end = start + Duration::days(6);
-    DateRange::new(start, end)
+impl PartialEq for DateRangePreset {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && Rc::ptr_eq(&self.range, &other.range)
+    }
 }
 
-fn quick_range_month(today: NaiveDate) -> DateRange {
-    let start = first_day_of_month(today);
-    let end = last_day_of_month(today);
-    DateRange::new(start, end)
+/// What a [`DateRangePicker`] lets through: ends between `min` and `max` that `disabled_date` does not rule out,
+/// at most `max_span` days apart counting both ends.
+#[derive(Clone, Copy, PartialEq)]
+struct RangeLimits {
+    min: Option<NaiveDate>,
+    max: Option<NaiveDate>,
+    max_span: Option<u32>,
+    disabled_date: Option<Callback<NaiveDate, bool>>,
 }
 
-#[cfg(target_arch = "wasm32")]
-fn today_date() -> NaiveDate {
-    use js_sys::Date;
+impl RangeLimits {
+    fn day_allowed(self, day: NaiveDate) -> bool {
+        !(self.min.is_some_and(|min| day < min)
+            || self.max.is_some_and(|max| day > max)
+            || self
+                .disabled_date
+                .is_some_and(|disabled| disabled.call(day)))
+    }
 
-    let date = Date::new_0();
-    let year = date.get_full_year() as i32;
-    let month = (date.get_month() + 1) as u32;
-    let day = date.get_date() as u32;
-    NaiveDate::from_ymd_opt(year, month, day)
-        .unwrap_or_else(|| NaiveDate::from_ymd_opt(1970, 1, 1).expect("unix epoch"))
+    fn allows(self, range: DateRange) -> bool {
+        self.day_allowed(range.start)
+            && self.day_allowed(range.end)
+            && self
+                .max_span
+                .is_none_or(|span| range.len_days() <= span as i64)
+    }
+
+    /// A preset's range cut down to `min` / `max`, `None` when what is left cannot be picked.
+    fn fit(self, range: DateRange) -> Option<DateRange> {
+        range
+            .clamp(self.min, self.max)
+            .filter(|range| self.allows(*range))
+    }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn today_date() -> NaiveDate {
-    NaiveDate::from_system_time(SystemTime::now())
-        .or_else(|| NaiveDate::from_ymd_opt(1970, 1, 1))
-        .expect("system time within supported range")
+fn quick_range_week(today: NaiveDate, first_day_of_week: Weekday) -> DateRange {
+    let start = today - Duration::days(today.weekday().num_days_from(first_day_of_week) as i64);
+    let end = start + Duration::days(6);
+    DateRange::new(start, end)
 }
 
-fn describe_range(range: DateRange) -> String {
+fn quick_range_month(today: NaiveDate) -> DateRange {
+    let start = today.first_day_of_month();
+    let end = today.last_day_of_month();
+    DateRange::new(start, end)
+}
+
+fn describe_range(range: DateRange, locale: Locale) -> String {
+    let long = |date: NaiveDate| {
+        date.format_localized(locale.pattern(DateStyle::Long), locale)
+            .to_string()
+    };
     if range.start == range.end {
-        range.start.format("%b %d %Y").to_string()
+        long(range.start)
+    } else if range.start.year() == range.end.year() {
+        let start_label = range
+            .start
+            .format_localized(locale.pattern(DateStyle::MonthDay), locale);
+        format!("{start_label} → {}", long(range.end))
     } else {
-        let start_label = range.start.format("%b %d");
-        let end_label = range.end.format("%b %d %Y");
-        format!("{start_label} → {end_label}")
+        format!("{} → {}", long(range.start), long(range.end))
     }
 }
 
-fn days_for_month(month_start: NaiveDate) -> Vec<NaiveDate> {
-    let start_offset = month_start.weekday().num_days_from_monday() as i64;
+fn days_for_month(month_start: NaiveDate, first_day_of_week: Weekday) -> Vec<NaiveDate> {
+    let start_offset = month_start.weekday().num_days_from(first_day_of_week) as i64;
     let mut cursor = month_start - Duration::days(start_offset);
     let mut days = Vec::with_capacity(42);
     for _ in 0..42 {
@@ -121,21 +243,58 @@ pub fn DateRangePicker(
     mut value: Signal<Option<DateRange>>,
     #[props(optional)] on_change: Option<EventHandler<Option<DateRange>>>,
     #[props(optional)] initial_month: Option<NaiveDate>,
+    /// Month titles, weekday names and the range summary, the app's language by default.
+    #[props(optional)]
+    locale: Option<Locale>,
+    /// Defaults to the locale's, Monday for `zh-CN`.
+    #[props(optional)]
+    first_day_of_week: Option<Weekday>,
+    /// Shortcuts above the calendars, [`DateRangePreset::defaults`] when not given.
+    #[props(optional)]
+    presets: Option<Vec<DateRangePreset>>,
+    /// Days before `min` or after `max` cannot be picked; presets are cut down to them.
+    #[props(optional)]
+    min: Option<NaiveDate>,
+    #[props(optional)] max: Option<NaiveDate>,
+    /// The most days a range may span, both ends included.
+    #[props(optional)]
+    max_span: Option<u32>,
+    /// Days for which this returns `true` cannot start or end a range.
+    #[props(optional)]
+    disabled_date: Option<Callback<NaiveDate, bool>>,
+    /// Offers comparing with the previous period, switched on and off through this signal on confirm.
+    #[props(optional)]
+    compare: Option<Signal<bool>>,
+    /// With `compare` given, called on confirm with the range and its previous period while comparing, `None`
+    /// otherwise.
+    #[props(optional)]
+    on_compare_change: Option<EventHandler<Option<DateRangeComparison>>>,
     #[props(into, default)] class: Option<String>,
 ) -> Element {
-    let today = today_date();
+    let today = use_today();
+    let i18n = use_i18n();
+    let locale = locale.unwrap_or(i18n.locale());
+    let first_day_of_week = first_day_of_week.unwrap_or(locale.first_day_of_week());
+    let limits = RangeLimits {
+        min,
+        max,
+        max_span,
+        disabled_date,
+    };
+    let presets = presets.unwrap_or_else(|| DateRangePreset::defaults(i18n));
 
     let initial_month = value()
         .map(|range| range.start)
         .or(initial_month)
-        .map(first_day_of_month)
-        .unwrap_or_else(|| first_day_of_month(today));
+        .map(NaiveDate::first_day_of_month)
+        .unwrap_or_else(|| today.first_day_of_month());
     let month = use_signal(move || initial_month);
     let hover_date = use_signal(|| None::<NaiveDate>);
     let draft_range = use_signal({
         let initial_value = value();
         move || initial_value
     });
+    let mut compare_draft = use_signal(move || compare.is_some_and(|compare| compare()));
     let popover_handle = try_use_context::<PopoverHandle>();
     let classes = merge_class("ui-date-range", class);
     let on_change_handler = on_change.clone();
@@ -146,32 +305,54 @@ pub fn DateRangePicker(
         use_effect(move || {
             draft_signal.set(value_signal());
         });
+        use_effect(move || {
+            if let Some(compare) = compare {
+                compare_draft.set(compare());
+            }
+        });
     }
 
     let confirmed_range = value();
     let pending_range = draft_range();
     let base_month = month();
 
-    let month_label = |date: NaiveDate| date.format("%B %Y").to_string();
+    let month_label = |date: NaiveDate| {
+        date.format_localized(locale.pattern(DateStyle::YearMonth), locale)
+            .to_string()
+    };
 
+    let current = |range: DateRange| {
+        i18n.t_with(
+            "date_range.current",
+            &[("range", &describe_range(range, locale))],
+        )
+    };
     let preview_primary_text = match (pending_range, confirmed_range) {
-        (Some(pending), Some(confirmed)) if pending == confirmed => {
-            format!("当前: {}", describe_range(pending))
-        }
-        (Some(pending), _) => format!("待确认: {}", describe_range(pending)),
-        (None, Some(_)) => "待确认: 清除日期".to_string(),
-        (None, None) => "选择一个开始日期以创建范围。".to_string(),
+        (Some(pending), Some(confirmed)) if pending == confirmed => current(pending),
+        (Some(pending), _) => i18n.t_with(
+            "date_range.pending",
+            &[("range", &describe_range(pending, locale))],
+        ),
+        (None, Some(_)) => i18n.t("date_range.pending_clear"),
+        (None, None) => i18n.t("date_range.hint"),
     };
     let preview_secondary_text = match (pending_range, confirmed_range) {
-        (Some(pending), Some(confirmed)) if pending != confirmed => {
-            Some(format!("当前: {}", describe_range(confirmed)))
-        }
-        (None, Some(confirmed)) => Some(format!("当前: {}", describe_range(confirmed))),
+        (Some(pending), Some(confirmed)) if pending != confirmed => Some(current(confirmed)),
+        (None, Some(confirmed)) => Some(current(confirmed)),
         _ => None,
     };
+    let comparing = compare_draft();
+    let compare_changed = compare.is_some_and(|compare| compare() != comparing);
+    let compare_text = pending_range.filter(|_| comparing).map(|range| {
+        i18n.t_with(
+            "date_range.compare_with",
+            &[("range", &describe_range(range.previous_period(), locale))],
+        )
+    });
     let confirm_disabled = match (pending_range, confirmed_range) {
         (None, None) => true,
-        (Some(pending), Some(confirmed)) if pending == confirmed => true,
+        (Some(pending), _) if !limits.allows(pending) => true,
+        (Some(pending), Some(confirmed)) if pending == confirmed => !compare_changed,
         _ => false,
     };
 
@@ -188,7 +369,7 @@ pub fn DateRangePicker(
                         on_click: {
                             let mut month_signal = month.clone();
                             move |_| {
-                                let next = add_months(month_signal(), -12);
+                                let next = month_signal().add_months(-12);
                                 month_signal.set(next);
                             }
                         },
@@ -200,7 +381,7 @@ pub fn DateRangePicker(
                         on_click: {
                             let mut month_signal = month.clone();
                             move |_| {
-                                let next = add_months(month_signal(), -1);
+                                let next = month_signal().add_months(-1);
                                 month_signal.set(next);
                             }
                         },
@@ -210,7 +391,7 @@ pub fn DateRangePicker(
                 div {
                     class: "ui-date-range-labels",
                     span { class: "ui-date-range-title", "{month_label(base_month)}" }
-                    span { class: "ui-date-range-title", "{month_label(add_months(base_month, 1))}" }
+                    span { class: "ui-date-range-title", "{month_label(base_month.add_months(1))}" }
                 }
                 div {
                     class: "ui-date-range-nav-group",
@@ -220,7 +401,7 @@ pub fn DateRangePicker(
                         on_click: {
                             let mut month_signal = month.clone();
                             move |_| {
-                                let next = add_months(month_signal(), 1);
+                                let next = month_signal().add_months(1);
                                 month_signal.set(next);
                             }
                         },
@@ -232,7 +413,7 @@ pub fn DateRangePicker(
                         on_click: {
                             let mut month_signal = month.clone();
                             move |_| {
-                                let next = add_months(month_signal(), 12);
+                                let next = month_signal().add_months(12);
                                 month_signal.set(next);
                             }
                         },
@@ -242,47 +423,37 @@ pub fn DateRangePicker(
             }
             div {
                 class: "ui-date-range-shortcuts",
-                Button {
-                    class: "ui-date-range-shortcut",
-                    size: ButtonSize::Sm,
-                    variant: ButtonVariant::Secondary,
-                    on_click: {
-                        let mut draft_signal = draft_range.clone();
-                        let mut month_signal = month.clone();
-                        let mut hover_signal = hover_date.clone();
-                        move |_| {
-                            let selected = quick_range_week(today);
-                            draft_signal.set(Some(selected));
-                            month_signal.set(first_day_of_month(selected.start));
-                            hover_signal.set(None);
-                        }
-                    },
-                    "本周"
-                }
-                Button {
-                    class: "ui-date-range-shortcut",
-                    size: ButtonSize::Sm,
-                    variant: ButtonVariant::Secondary,
-                    on_click: {
+                for preset in presets {
+                    {
+                        let fitted = limits.fit(preset.range(today, first_day_of_week));
                         let mut draft_signal = draft_range.clone();
                         let mut month_signal = month.clone();
                         let mut hover_signal = hover_date.clone();
-                        move |_| {
-                            let selected = quick_range_month(today);
-                            draft_signal.set(Some(selected));
-                            month_signal.set(first_day_of_month(selected.start));
-                            hover_signal.set(None);
+                        rsx! {
+                            Button {
+                                class: "ui-date-range-shortcut",
+                                size: ButtonSize::Sm,
+                                variant: ButtonVariant::Secondary,
+                                disabled: fitted.is_none(),
+                                on_click: move |_| {
+                                    if let Some(selected) = fitted {
+                                        draft_signal.set(Some(selected));
+                                        month_signal.set(selected.start.first_day_of_month());
+                                        hover_signal.set(None);
+                                    }
+                                },
+                                {preset.label().to_string()}
+                            }
                         }
-                    },
-                    "本月"
+                    }
                 }
             }
             div {
                 class: "ui-date-range-calendars",
                 for offset in 0..2 {
                     {
-                        let calendar_month = add_months(base_month, offset);
-                        let days = days_for_month(calendar_month);
+                        let calendar_month = base_month.add_months(offset);
+                        let days = days_for_month(calendar_month, first_day_of_week);
                         let active_month = calendar_month.month();
                         let range_signal = draft_range.clone();
                         let hover_signal = hover_date.clone();
@@ -290,8 +461,8 @@ pub fn DateRangePicker(
                             div {
                                 class: "ui-date-range-calendar",
                                 div { class: "ui-calendar-weekdays",
-                                    for label in WEEKDAY_LABELS {
-                                        span { class: "ui-calendar-weekday", "{label}" }
+                                    for weekday in Weekday::week_from(first_day_of_week) {
+                                        span { class: "ui-calendar-weekday", {locale.short_weekday_name(weekday)} }
                                     }
                                 }
                                 div { class: "ui-calendar-grid",
@@ -302,6 +473,11 @@ pub fn DateRangePicker(
                                             let is_selected_start = preview.map(|range| range.start == day).unwrap_or(false);
                                             let is_selected_end = preview.map(|range| range.end == day).unwrap_or(false);
                                             let is_in_range = preview.map(|range| range.contains(&day)).unwrap_or(false);
+                                            // While a range is half picked, its end must also keep within `max_span`.
+                                            let is_disabled = match range_signal() {
+                                                Some(range) if range.start == range.end => !limits.allows(DateRange::new(range.start, day)),
+                                                _ => !limits.day_allowed(day),
+                                            };
                                             rsx! {
                                                 button {
                                                     class: "ui-calendar-day",
@@ -309,6 +485,8 @@ pub fn DateRangePicker(
                                                     "data-state": if is_selected_start || is_selected_end { "selected" } else { "idle" },
                                                     "data-in-range": if is_in_range { "true" } else { "false" },
                                                     "data-outside": if in_current_month { "false" } else { "true" },
+                                                    "data-today": if day == today { "true" } else { "false" },
+                                                    disabled: is_disabled,
                                                     onclick: {
                                                         let day_value = day;
                                                         let mut draft_signal = range_signal.clone();
@@ -367,9 +545,22 @@ pub fn DateRangePicker(
                     for text in preview_secondary_text.iter() {
                         span { class: "ui-date-range-preview-secondary", "{text}" }
                     }
+                    for text in compare_text.iter() {
+                        span { class: "ui-date-range-preview-secondary", "{text}" }
+                    }
                 }
                 div {
                     class: "ui-date-range-controls",
+                    if compare.is_some() {
+                        label {
+                            class: "ui-date-range-compare",
+                            Checkbox {
+                                checked: comparing,
+                                on_checked_change: move |checked| compare_draft.set(checked),
+                            }
+                            span { {i18n.t("date_range.compare")} }
+                        }
+                    }
                     Button {
                         class: "ui-date-range-clear",
                         variant: ButtonVariant::Ghost,
@@ -382,7 +573,7 @@ pub fn DateRangePicker(
                                 hover_signal.set(None);
                             }
                         },
-                        "清除"
+                        {i18n.t("date_range.clear")}
                     }
                     Button {
                         class: "ui-date-range-confirm",
@@ -403,16 +594,110 @@ pub fn DateRangePicker(
                                 if let Some(handler) = on_change.clone() {
                                     handler.call(selection);
                                 }
+                                if let Some(mut compare) = compare {
+                                    let comparing = compare_draft();
+                                    compare.set(comparing);
+                                    if let Some(handler) = on_compare_change {
+                                        handler.call(
+                                            selection
+                                                .filter(|_| comparing)
+                                                .map(DateRangeComparison::with_previous_period),
+                                        );
+                                    }
+                                }
                                 if let Some(mut handle) = popover_handle {
                                     handle.state.set(false);
                                 }
                                 hover_signal.set(None);
                             }
                         },
-                        "确定"
+                        {i18n.t("date_range.confirm")}
                     }
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Locale;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn presets_count_back_from_today() {
+        let i18n = I18n::new(Locale::EnUs);
+        // 2024-05-15 is a Wednesday.
+        let today = date(5, 15);
+        let range = |preset: DateRangePreset| preset.range(today, Weekday::Monday);
+
+        let last_week = DateRangePreset::last_days(7, i18n);
+        assert_eq!(last_week.label(), "Last 7 days");
+        assert_eq!(range(last_week), DateRange::new(date(5, 9), today));
+        assert_eq!(
+            range(DateRangePreset::last_days(1, i18n)),
+            DateRange::new(today, today)
+        );
+        assert_eq!(
+            range(DateRangePreset::this_week(i18n)),
+            DateRange::new(date(5, 13), date(5, 19))
+        );
+        assert_eq!(
+            range(DateRangePreset::previous_month(i18n)),
+            DateRange::new(date(4, 1), date(4, 30))
+        );
+        assert_eq!(
+            range(DateRangePreset::quarter_to_date(i18n)),
+            DateRange::new(date(4, 1), today)
+        );
+        assert_eq!(
+            range(DateRangePreset::year_to_date(i18n)),
+            DateRange::new(date(1, 1), today)
+        );
+        assert_eq!(
+            DateRangePreset::last_days(1, I18n::new(Locale::ZhCn)).label(),
+            "最近 1 天"
+        );
+    }
+
+    #[test]
+    fn limits_cut_presets_down_and_cap_the_span() {
+        let limits = RangeLimits {
+            min: Some(date(3, 1)),
+            max: Some(date(5, 31)),
+            max_span: Some(31),
+            disabled_date: None,
+        };
+        assert_eq!(
+            limits.fit(DateRange::new(date(2, 20), date(3, 10))),
+            Some(DateRange::new(date(3, 1), date(3, 10)))
+        );
+        assert_eq!(limits.fit(DateRange::new(date(6, 1), date(6, 30))), None);
+        // Ninety days fit the bounds but not the span.
+        assert_eq!(limits.fit(DateRange::new(date(3, 1), date(5, 29))), None);
+        assert!(limits.allows(DateRange::new(date(5, 1), date(5, 31))));
+        assert!(!limits.allows(DateRange::new(date(4, 30), date(5, 31))));
+        assert!(!limits.day_allowed(date(6, 1)));
+    }
+
+    #[test]
+    fn previous_period_has_the_same_length() {
+        let june = DateRange::new(date(6, 1), date(6, 30));
+        assert_eq!(june.len_days(), 30);
+        assert_eq!(
+            june.previous_period(),
+            DateRange::new(date(5, 2), date(5, 31))
+        );
+        assert_eq!(
+            DateRangeComparison::with_previous_period(DateRange::new(date(6, 3), date(6, 3))),
+            DateRangeComparison {
+                primary: DateRange::new(date(6, 3), date(6, 3)),
+                previous: DateRange::new(date(6, 2), date(6, 2)),
+            }
+        );
+    }
+}
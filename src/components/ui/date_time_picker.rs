@@ -0,0 +1,224 @@
+use super::button::{Button, ButtonVariant};
+use super::calendar::Calendar;
+use super::popover::{Popover, PopoverHandle};
+use super::time_picker::{TimeGrid, TimePicker};
+use super::utils::{merge_class, use_today};
+use crate::i18n::use_i18n;
+use crate::time::{DateStyle, HourCycle, Locale, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use dioxus::prelude::*;
+
+/// The times offered on `day`: `min` and `max` only limit the time of day on their own dates.
+fn grid_for(
+    day: NaiveDate,
+    min: Option<NaiveDateTime>,
+    max: Option<NaiveDateTime>,
+    steps: TimeGrid,
+) -> TimeGrid {
+    TimeGrid {
+        min: min.filter(|min| min.date() == day).map(NaiveDateTime::time),
+        max: max.filter(|max| max.date() == day).map(NaiveDateTime::time),
+        ..steps
+    }
+}
+
+/// A date and time picked together: a trigger showing the value opens a `Calendar` next to a `TimePicker`.
+/// Picks stay a draft until confirmed; `min` and `max` disable the days outside them and, on their own days, the
+/// times outside them.
+#[component]
+pub fn DateTimePicker(
+    value: Signal<Option<NaiveDateTime>>,
+    #[props(optional)] on_change: Option<EventHandler<Option<NaiveDateTime>>>,
+    #[props(default)] show_seconds: bool,
+    /// Defaults to the locale's, the 24-hour clock for `zh-CN`.
+    #[props(optional)]
+    hour_cycle: Option<HourCycle>,
+    #[props(default = 1)] minute_step: u32,
+    #[props(default = 1)] second_step: u32,
+    #[props(optional)] min: Option<NaiveDateTime>,
+    #[props(optional)] max: Option<NaiveDateTime>,
+    /// Month titles, weekday names and the value on the trigger, the app's language by default.
+    #[props(optional)]
+    locale: Option<Locale>,
+    /// Defaults to the locale's, Monday for `zh-CN`.
+    #[props(optional)]
+    first_day_of_week: Option<Weekday>,
+    #[props(into, default)] placeholder: Option<String>,
+    #[props(into, default)] class: Option<String>,
+) -> Element {
+    let i18n = use_i18n();
+    let locale = locale.unwrap_or(i18n.locale());
+    let hour_cycle = hour_cycle.unwrap_or(locale.hour_cycle());
+    let pattern = format!(
+        "{} {}",
+        locale.pattern(DateStyle::Long),
+        locale.time_pattern(hour_cycle, show_seconds)
+    );
+    let label = match value() {
+        Some(selected) => selected.format_localized(&pattern, locale).to_string(),
+        None => placeholder.unwrap_or_else(|| i18n.t("date_time.placeholder")),
+    };
+
+    rsx! {
+        div {
+            class: merge_class("ui-date-time-picker", class),
+            Popover {
+                placement: "bottom".to_string(),
+                trigger: rsx! {
+                    button {
+                        class: "ui-date-time-picker-trigger",
+                        r#type: "button",
+                        "data-empty": if value().is_none() { "true" } else { "false" },
+                        span { class: "ui-date-time-picker-text", "{label}" }
+                        span { class: "ui-date-time-picker-icon", "🕒" }
+                    }
+                },
+                content: rsx! {
+                    DateTimePanel {
+                        value,
+                        on_change,
+                        show_seconds,
+                        hour_cycle,
+                        minute_step,
+                        second_step,
+                        min,
+                        max,
+                        locale,
+                        first_day_of_week,
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// The popover content of [`DateTimePicker`], a child of the `Popover` so it can close it.
+#[component]
+fn DateTimePanel(
+    mut value: Signal<Option<NaiveDateTime>>,
+    on_change: Option<EventHandler<Option<NaiveDateTime>>>,
+    show_seconds: bool,
+    hour_cycle: HourCycle,
+    minute_step: u32,
+    second_step: u32,
+    min: Option<NaiveDateTime>,
+    max: Option<NaiveDateTime>,
+    locale: Locale,
+    first_day_of_week: Option<Weekday>,
+) -> Element {
+    let i18n = use_i18n();
+    let today = use_today();
+    let mut date = use_signal(move || value().map(NaiveDateTime::date));
+    let mut time = use_signal(move || value().map(NaiveDateTime::time));
+    let popover = try_use_context::<PopoverHandle>();
+
+    let steps = TimeGrid {
+        minute_step,
+        second_step,
+        show_seconds,
+        min: None,
+        max: None,
+    };
+    let picked_date = date();
+    let grid = picked_date
+        .map(|day| grid_for(day, min, max, steps))
+        .unwrap_or(steps);
+    let draft = picked_date.and_then(|day| {
+        let time = time().or_else(|| grid.snap(NaiveTime::MIDNIGHT))?;
+        Some(NaiveDateTime::new(day, time))
+    });
+    let initial_month = picked_date
+        .or_else(|| min.map(NaiveDateTime::date).filter(|min| *min > today))
+        .or_else(|| max.map(NaiveDateTime::date).filter(|max| *max < today))
+        .unwrap_or(today);
+
+    let mut commit = move |next: Option<NaiveDateTime>| {
+        value.set(next);
+        if let Some(handler) = on_change {
+            handler.call(next);
+        }
+        if let Some(mut handle) = popover {
+            handle.state.set(false);
+        }
+    };
+
+    rsx! {
+        div {
+            class: "ui-date-time-picker-panel",
+            div {
+                class: "ui-date-time-picker-body",
+                Calendar {
+                    initial_month,
+                    selected: picked_date,
+                    min: min.map(NaiveDateTime::date),
+                    max: max.map(NaiveDateTime::date),
+                    locale,
+                    first_day_of_week: first_day_of_week.unwrap_or(locale.first_day_of_week()),
+                    on_select: move |day: NaiveDate| {
+                        date.set(Some(day));
+                        let grid = grid_for(day, min, max, steps);
+                        time.set(grid.snap(time().unwrap_or(NaiveTime::MIDNIGHT)));
+                    },
+                }
+                TimePicker {
+                    value: time,
+                    show_seconds,
+                    hour_cycle,
+                    minute_step,
+                    second_step,
+                    min: grid.min,
+                    max: grid.max,
+                    locale,
+                }
+            }
+            div {
+                class: "ui-date-time-picker-footer",
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    disabled: value().is_none() && picked_date.is_none(),
+                    on_click: move |_| commit(None),
+                    {i18n.t("date_time.clear")}
+                }
+                Button {
+                    disabled: draft.is_none(),
+                    on_click: move |_| {
+                        if draft.is_some() {
+                            commit(draft);
+                        }
+                    },
+                    {i18n.t("date_time.confirm")}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_limit_the_time_only_on_their_own_days() {
+        let day = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        let at = |day: NaiveDate, hour| {
+            NaiveDateTime::new(day, NaiveTime::from_hms_opt(hour, 0, 0).unwrap())
+        };
+        let steps = TimeGrid {
+            minute_step: 30,
+            second_step: 1,
+            show_seconds: false,
+            min: None,
+            max: None,
+        };
+        let (min, max) = (Some(at(day(3), 9)), Some(at(day(5), 18)));
+
+        let first = grid_for(day(3), min, max, steps);
+        assert_eq!(first.snap(NaiveTime::MIDNIGHT), Some(at(day(3), 9).time()));
+        assert_eq!(first.max, None);
+        assert_eq!(grid_for(day(4), min, max, steps), steps);
+        let last = grid_for(day(5), min, max, steps);
+        assert_eq!(
+            last.snap(NaiveTime::from_hms_opt(23, 0, 0).unwrap()),
+            Some(at(day(5), 18).time())
+        );
+    }
+}
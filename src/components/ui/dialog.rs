@@ -1,4 +1,5 @@
 use crate::components::ui::{Button, ButtonSize, ButtonVariant};
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 
 #[component]
@@ -9,6 +10,7 @@ pub fn Dialog(
     #[props(optional)] on_close: Option<EventHandler<()>>,
     children: Element,
 ) -> Element {
+    let i18n = use_i18n();
     if !open() {
         return rsx! { Fragment {} };
     }
@@ -51,7 +53,7 @@ pub fn Dialog(
                                 cb.call(());
                             }
                         },
-                        "Close"
+                        {i18n.t("dialog.close")}
                     }
                 }
             }
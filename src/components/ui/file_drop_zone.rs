@@ -1,4 +1,5 @@
 use super::utils::merge_class;
+use crate::i18n::I18n;
 use dioxus::html::events::{DragEvent, FormEvent};
 use dioxus::html::{FileData, HasFileData};
 use dioxus::prelude::*;
@@ -9,15 +10,56 @@ pub struct FileMetadata {
     pub content_type: Option<String>,
 }
 
-fn collect_metadata(files: Vec<FileData>) -> Vec<FileMetadata> {
-    files
-        .into_iter()
-        .map(|file| FileMetadata {
-            name: file.name(),
-            size: file.size(),
-            content_type: file.content_type(),
-        })
-        .collect()
+/// A selected file together with its contents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileContents {
+    pub metadata: FileMetadata,
+    /// The raw bytes, or why the file could not be read.
+    pub bytes: Result<Vec<u8>, String>,
+}
+
+impl FileContents {
+    /// The contents decoded as UTF-8, without a leading byte order mark. Errors are worded in the language of
+    /// `i18n`.
+    pub fn text(&self, i18n: I18n) -> Result<String, String> {
+        let bytes = self.bytes.clone()?;
+        let text = String::from_utf8(bytes).map_err(|_| i18n.t("file_drop_zone.not_utf8"))?;
+        Ok(text
+            .strip_prefix('\u{feff}')
+            .map(str::to_string)
+            .unwrap_or(text))
+    }
+}
+
+fn metadata(file: &FileData) -> FileMetadata {
+    FileMetadata {
+        name: file.name(),
+        size: file.size(),
+        content_type: file.content_type(),
+    }
+}
+
+fn collect_metadata(files: &[FileData]) -> Vec<FileMetadata> {
+    files.iter().map(metadata).collect()
+}
+
+/// Reads every file and hands the contents to `handler` once all of them are done.
+fn read_contents(files: Vec<FileData>, handler: EventHandler<Vec<FileContents>>) {
+    spawn(async move {
+        let mut contents = Vec::with_capacity(files.len());
+        for file in files {
+            let bytes = file
+                .read_bytes()
+                .await
+                .map(|bytes| bytes.to_vec())
+                .map_err(|err| err.to_string());
+            contents.push(FileContents {
+                metadata: metadata(&file),
+                bytes,
+            });
+        }
+        handler.call(contents);
+    });
 }
 
 #[component]
@@ -26,6 +68,9 @@ pub fn FileDropZone(
     #[props(default)] multiple: bool,
     #[props(into, default)] accept: Option<String>,
     #[props(optional)] on_files: Option<EventHandler<Vec<FileMetadata>>>,
+    /// Receives the file contents. Files are only read when this handler is set.
+    #[props(optional)]
+    on_file_contents: Option<EventHandler<Vec<FileContents>>>,
     #[props(optional)] content: Option<Element>,
 ) -> Element {
     let classes = merge_class("ui-dropzone", class);
@@ -92,10 +137,14 @@ pub fn FileDropZone(
                 move |event: DragEvent| {
                     event.prevent_default();
                     hovering.set(false);
-                    let files = collect_metadata(event.data().files());
-                    selected.set(files.clone());
+                    let files = event.data().files();
+                    let metadata = collect_metadata(&files);
+                    selected.set(metadata.clone());
                     if let Some(callback) = handler.clone() {
-                        callback.call(files);
+                        callback.call(metadata);
+                    }
+                    if let Some(callback) = on_file_contents {
+                        read_contents(files, callback);
                     }
                 }
             },
@@ -108,10 +157,14 @@ pub fn FileDropZone(
                     let mut selected = selected_files.clone();
                     let handler = on_files_handler.clone();
                     move |event: FormEvent| {
-                        let files = collect_metadata(event.files());
-                        selected.set(files.clone());
+                        let files = event.files();
+                        let metadata = collect_metadata(&files);
+                        selected.set(metadata.clone());
                         if let Some(callback) = handler.clone() {
-                            callback.call(files);
+                            callback.call(metadata);
+                        }
+                        if let Some(callback) = on_file_contents {
+                            read_contents(files, callback);
                         }
                     }
                 },
@@ -18,7 +18,9 @@ mod collapsible;
 mod combobox;
 mod command;
 mod context_menu;
+mod date_picker;
 mod date_range_picker;
+mod date_time_picker;
 mod dialog;
 mod dropdown_menu;
 mod file_drop_zone;
@@ -42,8 +44,10 @@ mod slider;
 mod steps;
 mod switch;
 mod table;
+mod table_view_switcher;
 mod tabs;
 mod textarea;
+mod time_picker;
 mod toast;
 mod toggle;
 mod toggle_group;
@@ -63,7 +67,9 @@ pub use collapsible::*;
 pub use combobox::*;
 pub use command::*;
 pub use context_menu::*;
+pub use date_picker::*;
 pub use date_range_picker::*;
+pub use date_time_picker::*;
 pub use dialog::*;
 pub use dropdown_menu::*;
 pub use file_drop_zone::*;
@@ -87,8 +93,10 @@ pub use slider::*;
 pub use steps::*;
 pub use switch::*;
 pub use table::*;
+pub use table_view_switcher::*;
 pub use tabs::*;
 pub use textarea::*;
+pub use time_picker::*;
 pub use toast::*;
 pub use toggle::*;
 pub use toggle_group::*;
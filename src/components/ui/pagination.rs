@@ -1,4 +1,5 @@
 use super::button::{Button, ButtonSize, ButtonVariant};
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 
 #[component]
@@ -7,6 +8,7 @@ pub fn Pagination(
     current_page: usize,
     #[props(optional)] on_page_change: Option<EventHandler<usize>>,
 ) -> Element {
+    let i18n = use_i18n();
     let mut current = use_signal(move || current_page.max(1).min(total_pages.max(1)));
     use_effect(move || {
         if current() != current_page {
@@ -19,7 +21,7 @@ pub fn Pagination(
     rsx! {
         nav {
             class: "ui-pagination",
-            aria_label: "Pagination",
+            aria_label: i18n.t("pagination.label"),
             Button {
                 variant: ButtonVariant::Outline,
                 size: ButtonSize::Sm,
@@ -31,7 +33,7 @@ pub fn Pagination(
                         cb.call(new_page);
                     }
                 },
-                "Prev"
+                {i18n.t("pagination.previous")}
             }
             for page in buttons {
                 {
@@ -72,7 +74,7 @@ pub fn Pagination(
                         cb.call(new_page);
                     }
                 },
-                "Next"
+                {i18n.t("pagination.next")}
             }
         }
     }
@@ -26,7 +26,13 @@ pub fn Select(
     #[props(optional)] on_change: Option<EventHandler<String>>,
 ) -> Element {
     let mut open = use_signal(|| false);
-    let mut current = use_signal(move || selected.clone());
+    let mut current = use_signal(|| selected.clone());
+    // Follow the caller when it changes the selection itself, e.g. when filters are reset.
+    use_effect(use_reactive((&selected,), move |(selected,)| {
+        if *current.peek() != selected {
+            current.set(selected);
+        }
+    }));
     let mut container_ref = use_signal(|| None as Option<Rc<MountedData>>);
 
     use_effect(move || {
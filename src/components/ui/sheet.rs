@@ -1,4 +1,5 @@
 use crate::components::ui::{Button, ButtonSize, ButtonVariant};
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 
 #[allow(dead_code)]
@@ -32,6 +33,7 @@ pub fn Sheet(
     #[props(optional)] on_close: Option<EventHandler<()>>,
     children: Element,
 ) -> Element {
+    let i18n = use_i18n();
     if !open() {
         return rsx! { Fragment {} };
     }
@@ -73,7 +75,7 @@ pub fn Sheet(
                             cb.call(());
                         }
                     },
-                    "Close"
+                    {i18n.t("dialog.close")}
                 }
             }
         }
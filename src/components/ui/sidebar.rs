@@ -1,4 +1,5 @@
 use super::utils::{data_bool, merge_class};
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 #[component]
 pub fn Sidebar(
@@ -282,11 +283,12 @@ pub fn SidebarTrigger(
     #[props(optional)] on_toggle: Option<EventHandler<bool>>,
 ) -> Element {
     let classes = merge_class("ui-sidebar-trigger", class);
+    let i18n = use_i18n();
     let label_text = label.unwrap_or_else(|| {
         if collapsed {
-            "展开侧边栏".to_string()
+            i18n.t("sidebar.expand")
         } else {
-            "收起侧边栏".to_string()
+            i18n.t("sidebar.collapse")
         }
     });
     let state = !collapsed;
@@ -1,12 +1,19 @@
 use std::{
-    cmp::max,
+    cell::RefCell,
+    cmp::{max, Ordering},
     collections::{HashMap, HashSet},
     rc::Rc,
 };
 
-use super::{utils::merge_class, Button, ButtonSize, ButtonVariant};
+use super::{
+    utils::merge_class, Button, ButtonSize, ButtonVariant, Calendar, Input, Select, SelectOption,
+    Switch,
+};
 use crate::components::ui::Checkbox;
+use crate::i18n::{use_i18n, I18n};
+use crate::time::NaiveDate;
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
 #[component]
 pub fn Table(#[props(into, default)] class: Option<String>, children: Element) -> Element {
     let classes = merge_class("ui-table", class);
@@ -104,22 +111,869 @@ pub fn TableCaption(#[props(into, default)] class: Option<String>, children: Ele
     }
 }
 
+/// How the cells of a sortable column compare.
+#[derive(Clone, Copy, Debug)]
+pub enum ColumnSortKind {
+    /// Case-insensitive text.
+    Text,
+    /// Numbers, ignoring currency symbols and thousands separators. Cells without a number sort last.
+    Numeric,
+    /// `YYYY-MM-DD` or `YYYY/MM/DD` dates. Cells without a date sort last.
+    Date,
+    /// The key the function derives from the cell text, e.g. the position of a status in its workflow.
+    Custom(fn(&str) -> i64),
+}
+
+impl PartialEq for ColumnSortKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ColumnSortKind::Custom(a), ColumnSortKind::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableSortDirection {
+    Ascending,
+    Descending,
+}
+
+/// One sorted column. Tables sort by a list of these, the first taking precedence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSort {
+    pub column_id: String,
+    pub direction: TableSortDirection,
+}
+
+impl TableSort {
+    pub fn asc(column_id: impl Into<String>) -> Self {
+        Self {
+            column_id: column_id.into(),
+            direction: TableSortDirection::Ascending,
+        }
+    }
+
+    pub fn desc(column_id: impl Into<String>) -> Self {
+        Self {
+            column_id: column_id.into(),
+            direction: TableSortDirection::Descending,
+        }
+    }
+}
+
+/// The sort after clicking the header of `column_id`, which cycles none → ascending → descending → none.
+/// A plain click makes the column the only sort; an `additive` (shift) click changes it in place and keeps the
+/// other columns, appending it when it was not sorted yet.
+pub fn next_sort(current: &[TableSort], column_id: &str, additive: bool) -> Vec<TableSort> {
+    let existing = current.iter().position(|sort| sort.column_id == column_id);
+    let next = match existing.map(|index| current[index].direction) {
+        None => Some(TableSort::asc(column_id)),
+        Some(TableSortDirection::Ascending) => Some(TableSort::desc(column_id)),
+        Some(TableSortDirection::Descending) => None,
+    };
+    if !additive {
+        return next.into_iter().collect();
+    }
+    let mut sorts = current.to_vec();
+    match (existing, next) {
+        (Some(index), Some(sort)) => sorts[index] = sort,
+        (Some(index), None) => {
+            sorts.remove(index);
+        }
+        (None, Some(sort)) => sorts.push(sort),
+        (None, None) => {}
+    }
+    sorts
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum SortKey {
+    Number(f64),
+    Date(NaiveDate),
+    Text(String),
+}
+
+fn sort_key(kind: ColumnSortKind, value: &str) -> Option<SortKey> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    match kind {
+        ColumnSortKind::Text => Some(SortKey::Text(value.to_lowercase())),
+        ColumnSortKind::Numeric => cell_number(value).map(SortKey::Number),
+        ColumnSortKind::Date => leading_date(value).map(SortKey::Date),
+        ColumnSortKind::Custom(key) => Some(SortKey::Number(key(value) as f64)),
+    }
+}
+
+/// Reads the number in a cell such as `¥1,280.00`, ignoring everything but digits, `.` and `-`.
+fn cell_number(value: &str) -> Option<f64> {
+    let number: String = value
+        .chars()
+        .filter(|ch| ch.is_ascii_digit() || matches!(ch, '.' | '-'))
+        .collect();
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Reads a `YYYY-MM-DD` or `YYYY/MM/DD` date from the start of a cell. Anything after the day, such as a time, is
+/// ignored.
+fn leading_date(value: &str) -> Option<NaiveDate> {
+    let mut parts = value.trim().splitn(3, ['-', '/']);
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day: String = parts
+        .next()?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    NaiveDate::from_ymd_opt(year, month, day.parse().ok()?)
+}
+
+/// The id and sort kind of every column, all [`sort_rows`] needs to know about them.
+pub fn sort_kinds<R>(columns: &[TableColumnConfig<R>]) -> Vec<(String, Option<ColumnSortKind>)> {
+    columns
+        .iter()
+        .map(|column| (column.id.clone(), column.sort))
+        .collect()
+}
+
+/// Orders `rows` by `sorts`, comparing each row's [`TableRecord::cell_text`] the way `kinds` (see [`sort_kinds`])
+/// says. Columns that are unknown or not sortable are skipped, empty or unreadable cells go last in either direction and equal rows keep their relative
+/// order.
+pub fn sort_rows<R: TableRecord>(
+    rows: &mut [R],
+    kinds: &[(String, Option<ColumnSortKind>)],
+    sorts: &[TableSort],
+) {
+    let criteria: Vec<(&str, ColumnSortKind, TableSortDirection)> = sorts
+        .iter()
+        .filter_map(|sort| {
+            let (column_id, kind) = kinds.iter().find(|(id, _)| *id == sort.column_id)?;
+            Some((column_id.as_str(), (*kind)?, sort.direction))
+        })
+        .collect();
+    if criteria.is_empty() {
+        return;
+    }
+
+    let keys: Vec<Vec<Option<SortKey>>> = rows
+        .iter()
+        .map(|row| {
+            criteria
+                .iter()
+                .map(|(column_id, kind, _)| sort_key(*kind, &row.cell_text(column_id)))
+                .collect()
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    order.sort_by(|a, b| {
+        for (index, (_, _, direction)) in criteria.iter().enumerate() {
+            let ordering = match (&keys[*a][index], &keys[*b][index]) {
+                (Some(a), Some(b)) => {
+                    let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+                    match direction {
+                        TableSortDirection::Ascending => ordering,
+                        TableSortDirection::Descending => ordering.reverse(),
+                    }
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    });
+    let sorted: Vec<R> = order.iter().map(|index| rows[*index].clone()).collect();
+    rows.clone_from_slice(&sorted);
+}
+
+/// A summary of one column over a set of rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    /// Number of rows.
+    Count,
+    /// Sum of the numeric cells.
+    Sum,
+    /// Mean of the numeric cells; cells without a number are left out.
+    Avg,
+}
+
+impl Aggregate {
+    pub fn label(&self, i18n: I18n) -> String {
+        i18n.t(match self {
+            Aggregate::Count => "table.aggregate.count",
+            Aggregate::Sum => "table.aggregate.sum",
+            Aggregate::Avg => "table.aggregate.avg",
+        })
+    }
+}
+
+/// Computes `aggregate` over the cells of `column_id`, reading numbers the way numeric sorting does. `None` when
+/// no cell holds a number to sum or average.
+pub fn aggregate_rows<'a, R: TableRecord>(
+    rows: impl IntoIterator<Item = &'a R>,
+    column_id: &str,
+    aggregate: Aggregate,
+) -> Option<f64> {
+    let mut count = 0usize;
+    let mut numbers = Vec::new();
+    for row in rows {
+        count += 1;
+        if aggregate != Aggregate::Count {
+            numbers.extend(cell_number(&row.cell_text(column_id)));
+        }
+    }
+    match aggregate {
+        Aggregate::Count => Some(count as f64),
+        _ if numbers.is_empty() => None,
+        Aggregate::Sum => Some(numbers.iter().sum()),
+        Aggregate::Avg => Some(numbers.iter().sum::<f64>() / numbers.len() as f64),
+    }
+}
+
+/// Aggregates worked out outside the table, e.g. by a server over every page of the data. They take the place of
+/// what [`InteractiveTable`] would compute from the rows it was given.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableSummary {
+    /// Number of rows the summary covers.
+    pub rows: usize,
+    /// Aggregate values by column id. Each column shows the ones it declares with
+    /// [`TableColumnConfig::aggregate`].
+    pub values: HashMap<String, Vec<(Aggregate, f64)>>,
+}
+
+impl TableSummary {
+    pub fn new(rows: usize) -> Self {
+        Self {
+            rows,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Records `value` as the `aggregate` of column `column_id`.
+    pub fn with(mut self, column_id: impl Into<String>, aggregate: Aggregate, value: f64) -> Self {
+        self.values
+            .entry(column_id.into())
+            .or_default()
+            .push((aggregate, value));
+        self
+    }
+
+    /// The aggregates `column` declares, in its order, as far as the summary has them. Counts fall back to
+    /// [`TableSummary::rows`].
+    fn values_for<R>(&self, column: &TableColumnConfig<R>) -> Vec<(Aggregate, f64)> {
+        let values = self
+            .values
+            .get(&column.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        column
+            .aggregates
+            .iter()
+            .filter_map(|aggregate| {
+                values
+                    .iter()
+                    .find(|(given, _)| given == aggregate)
+                    .copied()
+                    .or_else(|| {
+                        (*aggregate == Aggregate::Count)
+                            .then_some((Aggregate::Count, self.rows as f64))
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Rows sharing one value of the grouping column, as indices into the grouped rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowGroup {
+    /// The cell text of the grouping column.
+    pub key: String,
+    pub rows: Vec<usize>,
+}
+
+/// Groups rows by the text of `column_id`. Groups appear in the order their first row does, so a sorted table
+/// stays sorted inside each group.
+pub fn group_rows<R: TableRecord>(rows: &[R], column_id: &str) -> Vec<RowGroup> {
+    let mut groups: Vec<RowGroup> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let key = row.cell_text(column_id);
+        match positions.get(&key) {
+            Some(position) => groups[*position].rows.push(index),
+            None => {
+                positions.insert(key.clone(), groups.len());
+                groups.push(RowGroup {
+                    key,
+                    rows: vec![index],
+                });
+            }
+        }
+    }
+    groups
+}
+
+/// How tall the rows of a virtualized table are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowHeight {
+    /// Every row is this many pixels tall; rows are held at that height.
+    Fixed(f64),
+    /// Rows keep their natural height and are measured once rendered. Rows that were never rendered count as
+    /// `estimate` pixels.
+    Measured { estimate: f64 },
+}
+
+/// Renders only the rows inside a fixed-height scroll viewport, so tables with tens of thousands of rows stay
+/// responsive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableVirtualization {
+    /// CSS height of the scroll viewport, e.g. `480px`.
+    pub height: String,
+    pub row_height: RowHeight,
+    /// Extra rows rendered above and below the viewport so fast scrolling does not show blank space.
+    pub overscan: usize,
+}
+
+impl TableVirtualization {
+    pub fn new(height: impl Into<String>, row_height: RowHeight) -> Self {
+        Self {
+            height: height.into(),
+            row_height,
+            overscan: 6,
+        }
+    }
+
+    pub fn overscan(mut self, rows: usize) -> Self {
+        self.overscan = rows;
+        self
+    }
+}
+
+/// The rows to render, `start..end`, and the blank space standing in for the rows above and below them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualWindow {
+    pub start: usize,
+    pub end: usize,
+    pub padding_top: f64,
+    pub padding_bottom: f64,
+}
+
+/// Works out which of `row_count` rows intersect the viewport scrolled to `scroll_top`. `measured` returns the
+/// known height of a row in [`RowHeight::Measured`] mode.
+pub fn virtual_window(
+    row_count: usize,
+    row_height: RowHeight,
+    measured: impl Fn(usize) -> Option<f64>,
+    scroll_top: f64,
+    viewport_height: f64,
+    overscan: usize,
+) -> VirtualWindow {
+    let scroll_top = scroll_top.max(0.0);
+    let viewport_height = viewport_height.max(0.0);
+
+    if let RowHeight::Fixed(height) = row_height {
+        let height = height.max(1.0);
+        let first = ((scroll_top / height).floor() as usize).min(row_count);
+        let visible = (viewport_height / height).ceil() as usize + 1;
+        let start = first.saturating_sub(overscan);
+        let end = (first + visible + overscan).min(row_count);
+        return VirtualWindow {
+            start,
+            end,
+            padding_top: start as f64 * height,
+            padding_bottom: (row_count - end) as f64 * height,
+        };
+    }
+    window_at(
+        &row_offsets(row_count, row_height, measured),
+        scroll_top,
+        viewport_height,
+        overscan,
+    )
+}
+
+/// Where each of `row_count` rows starts, followed by the total height. Rows that were never measured count as
+/// the estimate of [`RowHeight::Measured`].
+fn row_offsets(
+    row_count: usize,
+    row_height: RowHeight,
+    measured: impl Fn(usize) -> Option<f64>,
+) -> Vec<f64> {
+    let height_of = |index: usize| match row_height {
+        RowHeight::Fixed(height) => height.max(1.0),
+        RowHeight::Measured { estimate } => measured(index).unwrap_or(estimate).max(0.0),
+    };
+    let mut offsets = Vec::with_capacity(row_count + 1);
+    let mut offset = 0.0;
+    offsets.push(offset);
+    for index in 0..row_count {
+        offset += height_of(index);
+        offsets.push(offset);
+    }
+    offsets
+}
+
+/// [`virtual_window`] over rows laid out at `offsets`, found by binary search.
+fn window_at(
+    offsets: &[f64],
+    scroll_top: f64,
+    viewport_height: f64,
+    overscan: usize,
+) -> VirtualWindow {
+    let scroll_top = scroll_top.max(0.0);
+    let bottom = scroll_top + viewport_height.max(0.0);
+    let row_count = offsets.len().saturating_sub(1);
+    let total = offsets.last().copied().unwrap_or_default();
+    // The first row reaching below the top edge, and the rows starting above the bottom edge.
+    let first = offsets[1..].partition_point(|end| *end <= scroll_top);
+    let last = offsets
+        .partition_point(|start| *start < bottom)
+        .clamp(first, row_count);
+
+    let start = first.saturating_sub(overscan);
+    let end = (last + overscan).min(row_count);
+    VirtualWindow {
+        start,
+        end,
+        padding_top: offsets[start],
+        padding_bottom: total - offsets[end],
+    }
+}
+
+/// Scroll position, viewport size and measured row heights of a virtualized table.
+#[derive(Clone, Copy)]
+struct VirtualScroll {
+    scroll_top: Signal<f64>,
+    viewport_height: Signal<f64>,
+    /// Measured row heights by row key.
+    measured: Signal<HashMap<String, f64>>,
+}
+
+fn use_virtual_scroll() -> VirtualScroll {
+    VirtualScroll {
+        scroll_top: use_signal(|| 0.0),
+        // Replaced by the real height as soon as the viewport is mounted.
+        viewport_height: use_signal(|| 600.0),
+        measured: use_signal(HashMap::new),
+    }
+}
+
+impl VirtualScroll {
+    /// The rows of `keys` to render. Row offsets are kept until rows change or are measured, and the window is a
+    /// memo, so scrolling re-renders the table only when it brings other rows into view. Without `config` every
+    /// row is rendered.
+    fn use_window(
+        self,
+        config: Option<TableVirtualization>,
+        keys: Memo<Rc<Vec<String>>>,
+    ) -> Memo<VirtualWindow> {
+        let VirtualScroll {
+            scroll_top,
+            viewport_height,
+            measured,
+        } = self;
+        let row_height = config.as_ref().map(|config| config.row_height);
+        let overscan = config.as_ref().map_or(0, |config| config.overscan);
+        let offsets = use_memo(use_reactive((&row_height,), move |(row_height,)| {
+            let keys = keys.read();
+            match row_height {
+                Some(row_height @ RowHeight::Measured { .. }) => {
+                    let measured = measured.read();
+                    Some(Rc::new(row_offsets(keys.len(), row_height, |index| {
+                        measured.get(&keys[index]).copied()
+                    })))
+                }
+                _ => None,
+            }
+        }));
+        use_memo(use_reactive(
+            (&row_height, &overscan),
+            move |(row_height, overscan)| {
+                let row_count = keys.read().len();
+                match (row_height, &*offsets.read()) {
+                    (Some(RowHeight::Measured { .. }), Some(offsets)) => {
+                        window_at(offsets, scroll_top(), viewport_height(), overscan)
+                    }
+                    (Some(row_height), _) => virtual_window(
+                        row_count,
+                        row_height,
+                        |_| None,
+                        scroll_top(),
+                        viewport_height(),
+                        overscan,
+                    ),
+                    (None, _) => VirtualWindow {
+                        start: 0,
+                        end: row_count,
+                        padding_top: 0.0,
+                        padding_bottom: 0.0,
+                    },
+                }
+            },
+        ))
+    }
+
+    fn on_scroll(self) -> impl FnMut(ScrollEvent) {
+        let VirtualScroll {
+            mut scroll_top,
+            mut viewport_height,
+            ..
+        } = self;
+        move |event: ScrollEvent| {
+            scroll_top.set(event.data().scroll_top());
+            viewport_height.set(event.data().client_height() as f64);
+        }
+    }
+
+    fn on_viewport_mounted(self) -> impl FnMut(MountedEvent) {
+        let mut viewport_height = self.viewport_height;
+        move |event: MountedEvent| {
+            let element = event.data();
+            spawn(async move {
+                if let Ok(rect) = element.get_client_rect().await {
+                    viewport_height.set(rect.height());
+                }
+            });
+        }
+    }
+
+    /// Records the height of a rendered row when rows are measured.
+    fn on_row_mounted(
+        self,
+        key: String,
+        row_height: Option<RowHeight>,
+    ) -> impl FnMut(MountedEvent) {
+        let mut measured = self.measured;
+        move |event: MountedEvent| {
+            if !matches!(row_height, Some(RowHeight::Measured { .. })) {
+                return;
+            }
+            let element = event.data();
+            let key = key.clone();
+            spawn(async move {
+                if let Ok(rect) = element.get_client_rect().await {
+                    let height = rect.height();
+                    let known = measured.peek().get(&key).copied();
+                    if height > 0.0 && known.is_none_or(|known| (known - height).abs() > 0.5) {
+                        measured.write().insert(key, height);
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// A row the interactive table can show. The text of a cell is what sorting compares and what columns without a
+/// renderer display.
+pub trait TableRecord: Clone + PartialEq + 'static {
+    /// Unique among the table's rows; selection is tracked by it.
+    fn row_id(&self) -> String;
+
+    fn cell_text(&self, column_id: &str) -> String;
+}
+
+/// Draws a cell from the typed row. Renderers compare by identity, so clone one instead of recreating it when
+/// the column list should compare equal across renders.
+pub struct CellRenderer<R>(Rc<dyn Fn(&R) -> Element>);
+
+impl<R> CellRenderer<R> {
+    pub fn new(render: impl Fn(&R) -> Element + 'static) -> Self {
+        Self(Rc::new(render))
+    }
+
+    pub fn render(&self, row: &R) -> Element {
+        (self.0)(row)
+    }
+}
+
+impl<R> Clone for CellRenderer<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R> PartialEq for CellRenderer<R> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Checks a value entered in an editable cell before it is committed. The error message is shown on the cell and
+/// the editor stays open.
+pub struct CellValidator<R>(Rc<ValidateFn<R>>);
+
+type ValidateFn<R> = dyn Fn(&R, &str) -> Result<(), String>;
+
+impl<R> CellValidator<R> {
+    pub fn new(validate: impl Fn(&R, &str) -> Result<(), String> + 'static) -> Self {
+        Self(Rc::new(validate))
+    }
+
+    pub fn check(&self, row: &R, value: &str) -> Result<(), String> {
+        (self.0)(row, value)
+    }
+}
+
+impl<R> Clone for CellValidator<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R> PartialEq for CellValidator<R> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Formats an aggregate for display; see [`TableColumnConfig::format_aggregate`].
+pub struct AggregateFormatter(Rc<dyn Fn(Aggregate, f64) -> String>);
+
+impl AggregateFormatter {
+    pub fn new(format: impl Fn(Aggregate, f64) -> String + 'static) -> Self {
+        Self(Rc::new(format))
+    }
+
+    pub fn format(&self, aggregate: Aggregate, value: f64) -> String {
+        (self.0)(aggregate, value)
+    }
+}
+
+impl Clone for AggregateFormatter {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl PartialEq for AggregateFormatter {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Turns the cell text rows are grouped by into the group header title; see [`TableColumnConfig::format_group`].
+pub struct GroupFormatter(Rc<dyn Fn(&str) -> String>);
+
+impl GroupFormatter {
+    pub fn new(format: impl Fn(&str) -> String + 'static) -> Self {
+        Self(Rc::new(format))
+    }
+
+    pub fn format(&self, key: &str) -> String {
+        (self.0)(key)
+    }
+}
+
+impl Clone for GroupFormatter {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl PartialEq for GroupFormatter {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// How an editable column edits its cells in place. Editors work on the cell text: dates are `YYYY-MM-DD`,
+/// switches are `true` / `false` and selects use the option value.
+#[derive(Clone, PartialEq)]
+pub enum CellEditor {
+    Text,
+    Select(Vec<SelectOption>),
+    Date,
+    Switch,
+}
+
+/// An edit committed in an [`InteractiveTable`] cell. The table shows `new_value` right away and keeps showing
+/// it until the row's own cell text changes; call [`CellEdit::reject`] when saving fails.
+#[derive(Clone, PartialEq)]
+pub struct CellEdit {
+    pub row_id: String,
+    pub column_id: String,
+    pub old_value: String,
+    pub new_value: String,
+    edits: CellEdits,
+}
+
+impl CellEdit {
+    /// Drops the optimistic value, so the cell shows the row's own text again, and shows `message` on the cell.
+    pub fn reject(&self, message: impl Into<String>) {
+        let mut edits = self.edits;
+        let key = (self.row_id.clone(), self.column_id.clone());
+        edits.pending.write().remove(&key);
+        edits.errors.write().insert(key, message.into());
+    }
+}
+
+/// Row id and column id of a cell.
+type CellKey = (String, String);
+
+/// A committed edit the row does not reflect yet.
+#[derive(Clone, PartialEq)]
+struct PendingEdit {
+    /// The row's cell text when the edit was committed.
+    base: String,
+    value: String,
+}
+
+/// Editing state shared by every cell of one table.
+#[derive(Clone, Copy, PartialEq)]
+struct CellEdits {
+    editing: Signal<Option<CellKey>>,
+    draft: Signal<String>,
+    pending: Signal<HashMap<CellKey, PendingEdit>>,
+    errors: Signal<HashMap<CellKey, String>>,
+}
+
+fn use_cell_edits() -> CellEdits {
+    CellEdits {
+        editing: use_signal(|| None),
+        draft: use_signal(String::new),
+        pending: use_signal(HashMap::new),
+        errors: use_signal(HashMap::new),
+    }
+}
+
+impl CellEdits {
+    /// The value the cell shows: the pending edit while the row still has the text it was made against,
+    /// otherwise the row's own text. The flag is true when the value is still pending.
+    fn value(&self, key: &CellKey, text: String) -> (String, bool) {
+        match self.pending.read().get(key) {
+            Some(pending) if pending.base == text => (pending.value.clone(), true),
+            _ => (text, false),
+        }
+    }
+
+    fn open(mut self, key: CellKey, value: String) {
+        self.errors.write().remove(&key);
+        self.draft.set(value);
+        self.editing.set(Some(key));
+    }
+
+    fn cancel(mut self, key: &CellKey) {
+        self.errors.write().remove(key);
+        if self.editing.peek().as_ref() == Some(key) {
+            self.editing.set(None);
+        }
+    }
+
+    fn commit<R: TableRecord>(
+        mut self,
+        row: &R,
+        column: &TableColumnConfig<R>,
+        value: String,
+        on_cell_edit: Option<EventHandler<CellEdit>>,
+    ) {
+        let key = (row.row_id(), column.id.clone());
+        let text = row.cell_text(&column.id);
+        let (old_value, _) = self.value(&key, text.clone());
+        if value == old_value {
+            self.cancel(&key);
+            return;
+        }
+        if let Some(Err(message)) = column
+            .validate
+            .as_ref()
+            .map(|validate| validate.check(row, &value))
+        {
+            self.errors.write().insert(key, message);
+            return;
+        }
+
+        self.errors.write().remove(&key);
+        self.editing.set(None);
+        if value == text {
+            self.pending.write().remove(&key);
+        } else {
+            self.pending.write().insert(
+                key.clone(),
+                PendingEdit {
+                    base: text,
+                    value: value.clone(),
+                },
+            );
+        }
+        if let Some(handler) = on_cell_edit {
+            handler.call(CellEdit {
+                row_id: key.0,
+                column_id: key.1,
+                old_value,
+                new_value: value,
+                edits: self,
+            });
+        }
+    }
+}
+
+/// The side of the table a column sticks to while the other columns scroll horizontally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnPin {
+    Left,
+    Right,
+}
+
+impl ColumnPin {
+    fn side(self) -> &'static str {
+        match self {
+            ColumnPin::Left => "left",
+            ColumnPin::Right => "right",
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
-pub struct TableColumnConfig {
+pub struct TableColumnConfig<R = TableRowData> {
     pub id: String,
     pub label: String,
     pub toggleable: bool,
     pub visible_by_default: bool,
+    /// `None` keeps the header static.
+    pub sort: Option<ColumnSortKind>,
+    /// `None` shows the cell text.
+    pub render: Option<CellRenderer<R>>,
+    /// `None` keeps the cells read-only.
+    pub editor: Option<CellEditor>,
+    pub validate: Option<CellValidator<R>>,
+    /// Initial width in pixels. `None` sizes the column to its content until it is resized.
+    pub width: Option<f64>,
+    /// Narrowest width resizing can reach.
+    pub min_width: f64,
+    pub pin: Option<ColumnPin>,
+    /// Shown in group header rows and the grand-total footer, in this order.
+    pub aggregates: Vec<Aggregate>,
+    /// `None` shows counts as integers and other aggregates with two decimals.
+    pub aggregate_format: Option<AggregateFormatter>,
+    /// `None` titles group header rows with the cell text itself.
+    pub group_format: Option<GroupFormatter>,
 }
 
 #[allow(dead_code)]
-impl TableColumnConfig {
+impl<R> TableColumnConfig<R> {
     pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
         Self {
             id: id.into(),
             label: label.into(),
             toggleable: true,
             visible_by_default: true,
+            sort: None,
+            render: None,
+            editor: None,
+            validate: None,
+            width: None,
+            min_width: 64.0,
+            pin: None,
+            aggregates: Vec::new(),
+            aggregate_format: None,
+            group_format: None,
         }
     }
 
@@ -132,6 +986,64 @@ impl TableColumnConfig {
         self.visible_by_default = false;
         self
     }
+
+    pub fn sortable(mut self, kind: ColumnSortKind) -> Self {
+        self.sort = Some(kind);
+        self
+    }
+
+    pub fn render(mut self, render: impl Fn(&R) -> Element + 'static) -> Self {
+        self.render = Some(CellRenderer::new(render));
+        self
+    }
+
+    /// Lets the cells be edited in place: double-click a cell or focus it and press Enter, then Enter commits and
+    /// Escape cancels.
+    pub fn editable(mut self, editor: CellEditor) -> Self {
+        self.editor = Some(editor);
+        self
+    }
+
+    pub fn validate(mut self, validate: impl Fn(&R, &str) -> Result<(), String> + 'static) -> Self {
+        self.validate = Some(CellValidator::new(validate));
+        self
+    }
+
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn min_width(mut self, width: f64) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    /// Keeps the column visible at the given side while the others scroll horizontally.
+    pub fn pin(mut self, side: ColumnPin) -> Self {
+        self.pin = Some(side);
+        self
+    }
+
+    /// Adds an aggregate to the group header rows and the grand-total footer. Sums and averages read the number
+    /// in each cell, so text such as `¥1,280.00` still counts.
+    pub fn aggregate(mut self, aggregate: Aggregate) -> Self {
+        if !self.aggregates.contains(&aggregate) {
+            self.aggregates.push(aggregate);
+        }
+        self
+    }
+
+    pub fn format_aggregate(mut self, format: impl Fn(Aggregate, f64) -> String + 'static) -> Self {
+        self.aggregate_format = Some(AggregateFormatter::new(format));
+        self
+    }
+
+    /// Titles the group header rows when the table is grouped by this column, e.g. to translate a status label.
+    pub fn format_group(mut self, format: impl Fn(&str) -> String + 'static) -> Self {
+        self.group_format = Some(GroupFormatter::new(format));
+        self
+    }
 }
 
 #[derive(Clone, PartialEq)]
@@ -168,19 +1080,391 @@ impl TableRowData {
     }
 }
 
+impl TableRecord for TableRowData {
+    fn row_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn cell_text(&self, column_id: &str) -> String {
+        self.cells.get(column_id).cloned().unwrap_or_default()
+    }
+}
+
+/// How an [`InteractiveTable`] arranges its columns. Ids of columns the table does not have are ignored, and
+/// columns missing from `order` are appended in definition order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableLayout {
+    /// Column ids in display order.
+    pub order: Vec<String>,
+    /// Ids of the shown columns. Columns that cannot be hidden are always shown.
+    pub visible: Vec<String>,
+    /// Widths in pixels of the columns that have one.
+    pub widths: HashMap<String, f64>,
+    pub pins: HashMap<String, ColumnPin>,
+}
+
+impl TableLayout {
+    /// The layout the columns start with.
+    pub fn from_columns<R>(columns: &[TableColumnConfig<R>]) -> Self {
+        Self {
+            order: columns.iter().map(|column| column.id.clone()).collect(),
+            visible: columns
+                .iter()
+                .filter(|column| column.visible_by_default || !column.toggleable)
+                .map(|column| column.id.clone())
+                .collect(),
+            widths: columns
+                .iter()
+                .filter_map(|column| Some((column.id.clone(), column.width?)))
+                .collect(),
+            pins: columns
+                .iter()
+                .filter_map(|column| Some((column.id.clone(), column.pin?)))
+                .collect(),
+        }
+    }
+
+    /// The shown columns of `columns`, falling back to the first column so the table is never empty.
+    fn visible_columns<R>(&self, columns: &[TableColumnConfig<R>]) -> HashSet<String> {
+        let mut visible: HashSet<String> = columns
+            .iter()
+            .filter(|column| !column.toggleable || self.visible.contains(&column.id))
+            .map(|column| column.id.clone())
+            .collect();
+        if visible.is_empty() {
+            if let Some(first) = columns.first() {
+                visible.insert(first.id.clone());
+            }
+        }
+        visible
+    }
+}
+
+/// Width of the selection column, see `.ui-data-table-checkbox-cell`.
+const CHECKBOX_COLUMN_WIDTH: f64 = 48.0;
+
+/// Extra width of the selection column when rows have an expand toggle, see `[data-expandable]`.
+const ROW_TOGGLE_WIDTH: f64 = 32.0;
+
+/// Width a pinned column without one of its own gets, so the pinned columns next to it know where to stick.
+const PINNED_COLUMN_WIDTH: f64 = 160.0;
+
+/// `order` without ids that are no longer columns, followed by any new columns in definition order.
+fn reconcile_order<R>(order: &[String], columns: &[TableColumnConfig<R>]) -> Vec<String> {
+    let mut reconciled: Vec<String> = order
+        .iter()
+        .filter(|id| columns.iter().any(|column| &column.id == *id))
+        .cloned()
+        .collect();
+    for column in columns {
+        if !reconciled.contains(&column.id) {
+            reconciled.push(column.id.clone());
+        }
+    }
+    reconciled
+}
+
+/// Moves `column_id` to the position of `target`, shifting the columns in between.
+fn move_column(order: &[String], column_id: &str, target: &str) -> Vec<String> {
+    let mut next = order.to_vec();
+    let from = order.iter().position(|id| id == column_id);
+    let to = order.iter().position(|id| id == target);
+    if let (Some(from), Some(to)) = (from, to) {
+        let id = next.remove(from);
+        next.insert(to, id);
+    }
+    next
+}
+
+/// Left-pinned columns first and right-pinned columns last, each group keeping the order it has in `order`.
+fn arrange_columns(order: &[String], pins: &HashMap<String, ColumnPin>) -> Vec<String> {
+    let group = |pin: Option<ColumnPin>| {
+        order
+            .iter()
+            .filter(move |id| pins.get(*id).copied() == pin)
+            .cloned()
+    };
+    group(Some(ColumnPin::Left))
+        .chain(group(None))
+        .chain(group(Some(ColumnPin::Right)))
+        .collect()
+}
+
+/// Sticky offsets of columns laid out left to right from their pins and widths. Left offsets start after
+/// `leading`, the width of what is always stuck to the left edge.
+fn pinned_offsets(columns: &[(Option<ColumnPin>, f64)], leading: f64) -> Vec<Option<f64>> {
+    let mut offsets = vec![None; columns.len()];
+    let mut left = leading;
+    for (index, (pin, width)) in columns.iter().enumerate() {
+        if *pin == Some(ColumnPin::Left) {
+            offsets[index] = Some(left);
+            left += width;
+        }
+    }
+    let mut right = 0.0;
+    for (index, (pin, width)) in columns.iter().enumerate().rev() {
+        if *pin == Some(ColumnPin::Right) {
+            offsets[index] = Some(right);
+            right += width;
+        }
+    }
+    offsets
+}
+
+/// A visible column with what its header and cells need to be sized and pinned.
+struct PlacedColumn<R> {
+    config: TableColumnConfig<R>,
+    pin: Option<&'static str>,
+    style: Option<String>,
+}
+
+fn place_columns<R: Clone>(
+    columns: &[TableColumnConfig<R>],
+    widths: &HashMap<String, f64>,
+    pins: &HashMap<String, ColumnPin>,
+    leading: f64,
+) -> Vec<PlacedColumn<R>> {
+    let sized: Vec<(Option<ColumnPin>, Option<f64>)> = columns
+        .iter()
+        .map(|column| {
+            let pin = pins.get(&column.id).copied();
+            let width = widths
+                .get(&column.id)
+                .copied()
+                .or(pin.map(|_| PINNED_COLUMN_WIDTH));
+            (pin, width)
+        })
+        .collect();
+    let offsets = pinned_offsets(
+        &sized
+            .iter()
+            .map(|(pin, width)| (*pin, width.unwrap_or_default()))
+            .collect::<Vec<_>>(),
+        leading,
+    );
+    columns
+        .iter()
+        .zip(sized)
+        .zip(offsets)
+        .map(|((column, (pin, width)), offset)| {
+            let mut style = String::new();
+            if let Some(width) = width {
+                style.push_str(&format!(
+                    "width: {width}px; min-width: {width}px; max-width: {width}px;"
+                ));
+            }
+            if let (Some(pin), Some(offset)) = (pin, offset) {
+                style.push_str(&format!(" position: sticky; {}: {offset}px;", pin.side()));
+            }
+            PlacedColumn {
+                config: column.clone(),
+                pin: pin.map(ColumnPin::side),
+                style: (!style.is_empty()).then_some(style),
+            }
+        })
+        .collect()
+}
+
+/// The aggregates of `column` over `rows`, e.g. `合计 ¥1280.00 · 平均 ¥320.00`. Empty when the column has none.
+fn aggregate_summary<R: TableRecord>(
+    column: &TableColumnConfig<R>,
+    rows: &[&R],
+    i18n: I18n,
+) -> String {
+    format_aggregates(
+        column,
+        &aggregate_values(rows, &column.id, &column.aggregates),
+        i18n,
+    )
+}
+
+/// Each of `aggregates` over the cells of `column_id`, skipping those with no number to work on.
+fn aggregate_values<R: TableRecord>(
+    rows: &[&R],
+    column_id: &str,
+    aggregates: &[Aggregate],
+) -> Vec<(Aggregate, f64)> {
+    aggregates
+        .iter()
+        .filter_map(|aggregate| {
+            aggregate_rows(rows.iter().copied(), column_id, *aggregate)
+                .map(|value| (*aggregate, value))
+        })
+        .collect()
+}
+
+/// Labels `values` and formats them the way `column` asks, see [`aggregate_summary`].
+fn format_aggregates<R>(
+    column: &TableColumnConfig<R>,
+    values: &[(Aggregate, f64)],
+    i18n: I18n,
+) -> String {
+    values
+        .iter()
+        .map(|(aggregate, value)| {
+            let text = match (&column.aggregate_format, aggregate) {
+                (Some(format), _) => format.format(*aggregate, *value),
+                (None, Aggregate::Count) => format!("{value:.0}"),
+                (None, _) => format!("{value:.2}"),
+            };
+            format!("{} {text}", aggregate.label(i18n))
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// A row of the table body: a group header, a data row or the detail panel of an expanded row, by index into the
+/// groups or rows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BodyItem {
+    Group(usize),
+    Row(usize),
+    Detail(usize),
+}
+
+/// Lays out the table body: each group header followed by its rows unless the group is collapsed, and each
+/// expanded row followed by its detail panel.
+fn body_items(
+    row_count: usize,
+    groups: Option<&[RowGroup]>,
+    collapsed: &HashSet<String>,
+    expanded: impl Fn(usize) -> bool,
+) -> Vec<BodyItem> {
+    let with_detail = |index: usize| {
+        std::iter::once(BodyItem::Row(index))
+            .chain(expanded(index).then_some(BodyItem::Detail(index)))
+    };
+    match groups {
+        Some(groups) => groups
+            .iter()
+            .enumerate()
+            .flat_map(|(index, group)| {
+                let members = if collapsed.contains(&group.key) {
+                    &[][..]
+                } else {
+                    group.rows.as_slice()
+                };
+                std::iter::once(BodyItem::Group(index))
+                    .chain(members.iter().copied().flat_map(&with_detail))
+            })
+            .collect(),
+        None => (0..row_count).flat_map(with_detail).collect(),
+    }
+}
+
+/// The key of a body item, for measuring its height.
+fn body_key(item: BodyItem, groups: &[RowGroup], row_ids: &[String]) -> String {
+    match item {
+        BodyItem::Group(index) => format!("group:{}", groups[index].key),
+        BodyItem::Row(index) => row_ids[index].clone(),
+        BodyItem::Detail(index) => format!("detail:{}", row_ids[index]),
+    }
+}
+
+/// A column border being dragged.
+#[derive(Clone, PartialEq)]
+struct ColumnResize {
+    column_id: String,
+    start_x: f64,
+    start_width: f64,
+    min_width: f64,
+}
+
 #[component]
-pub fn InteractiveTable(
-    #[props(into)] columns: Vec<TableColumnConfig>,
-    #[props(into)] rows: Vec<TableRowData>,
+pub fn InteractiveTable<R: TableRecord>(
+    #[props(into)] columns: Vec<TableColumnConfig<R>>,
+    #[props(into)] rows: Vec<R>,
     #[props(into, default)] class: Option<String>,
     #[props(into, default)] table_class: Option<String>,
     #[props(into, default)] default_selected: Option<Vec<String>>,
     #[props(into, default)] empty_state: Option<String>,
     #[props(optional)] on_selection_change: Option<EventHandler<Vec<String>>>,
-    #[props(optional)] on_visibility_change: Option<EventHandler<Vec<String>>>,
+    /// Receives the visible column ids in display order.
+    #[props(optional)]
+    on_visibility_change: Option<EventHandler<Vec<String>>>,
+    /// Receives every column id in display order after a column is dragged in the column menu.
+    #[props(optional)]
+    on_column_order_change: Option<EventHandler<Vec<String>>>,
+    /// Receives the width of every resized column when a resize ends.
+    #[props(optional)]
+    on_column_resize: Option<EventHandler<HashMap<String, f64>>>,
+    /// Receives the pin of every pinned column after one is pinned or unpinned in the column menu.
+    #[props(optional)]
+    on_column_pin_change: Option<EventHandler<HashMap<String, ColumnPin>>>,
+    /// The column layout. The table takes it over whenever it changes, so saved layouts can be restored;
+    /// `None` starts from the column definitions.
+    #[props(into, default)]
+    layout: Option<TableLayout>,
+    /// Receives the whole layout after any change to column order, visibility, widths or pins.
+    #[props(optional)]
+    on_layout_change: Option<EventHandler<TableLayout>>,
+    /// The current sort. The table takes it over whenever it changes, so server-driven tables can pass back the
+    /// sort they applied.
+    #[props(into, default)]
+    sort: Vec<TableSort>,
+    /// Leaves `rows` in the given order and only reports header clicks through `on_sort_change`.
+    #[props(default)]
+    manual_sort: bool,
+    #[props(optional)] on_sort_change: Option<EventHandler<Vec<TableSort>>>,
+    /// Renders only the rows in view. Selection and column visibility still cover every row.
+    #[props(optional)]
+    virtualization: Option<TableVirtualization>,
+    /// Called when an edit in a [`TableColumnConfig::editable`] column passes validation.
+    #[props(optional)]
+    on_cell_edit: Option<EventHandler<CellEdit>>,
+    /// Groups the rows by the text of this column under collapsible header rows showing each group's aggregates.
+    #[props(into, default)]
+    group_by: Option<String>,
+    /// Lets rows expand into a full-width panel drawn by this callback. Panels are only rendered while their row
+    /// is expanded.
+    #[props(optional)]
+    render_detail: Option<Callback<R, Element>>,
+    /// Receives the expanded row ids in row order.
+    #[props(optional)]
+    on_expanded_change: Option<EventHandler<Vec<String>>>,
+    /// Heading of the footer row with the column aggregates, the `table.total` text by default. Say what the
+    /// rows cover when they are only part of the data, e.g. one page of it.
+    #[props(into, default)]
+    total_label: Option<String>,
+    /// Aggregates for the footer row over more than `rows`, e.g. every page a server holds. `None` aggregates
+    /// `rows`.
+    #[props(into, default)]
+    summary: Option<TableSummary>,
+    /// Row counts and aggregates for the group header rows by group key, i.e. the cell text of the `group_by`
+    /// column. Groups without an entry aggregate their rows in `rows`.
+    #[props(into, default)]
+    group_summaries: Option<HashMap<String, TableSummary>>,
 ) -> Element {
+    let i18n = use_i18n();
     let wrapper_class = merge_class("ui-data-table", class);
     let inner_table_class = merge_class("ui-table", table_class);
+    let scroll = use_virtual_scroll();
+    let edits = use_cell_edits();
+
+    let mut sort_state = use_signal(|| sort.clone());
+    use_effect(use_reactive((&sort,), move |(sort,)| sort_state.set(sort)));
+    let sort_snapshot = sort_state();
+    // Sorted rows and their ids are kept until the rows, the sort or the sortable columns change.
+    let kinds = sort_kinds(&columns);
+    let sorted_rows = use_memo(use_reactive(
+        (&rows, &kinds, &manual_sort),
+        move |(mut rows, kinds, manual_sort)| {
+            if !manual_sort {
+                sort_rows(&mut rows, &kinds, &sort_state());
+            }
+            Rc::new(rows)
+        },
+    ));
+    let row_ids = use_memo(move || {
+        Rc::new(
+            sorted_rows
+                .read()
+                .iter()
+                .map(TableRecord::row_id)
+                .collect::<Vec<_>>(),
+        )
+    });
+    let rows = sorted_rows();
 
     let initial_selected: HashSet<String> =
         default_selected.unwrap_or_default().into_iter().collect();
@@ -189,39 +1473,75 @@ pub fn InteractiveTable(
         move || initial_selected.clone()
     });
 
-    let initial_visible: HashSet<String> = {
-        let mut visible = HashSet::new();
-        for column in &columns {
-            if column.visible_by_default || !column.toggleable {
-                visible.insert(column.id.clone());
+    let initial_layout = use_hook(|| {
+        layout
+            .clone()
+            .unwrap_or_else(|| TableLayout::from_columns(&columns))
+    });
+    let mut visible_columns = use_signal(|| initial_layout.visible_columns(&columns));
+    let mut column_order = use_signal(|| initial_layout.order.clone());
+    let mut column_widths = use_signal(|| initial_layout.widths.clone());
+    let mut column_pins = use_signal(|| initial_layout.pins.clone());
+    {
+        let columns = columns.clone();
+        use_effect(use_reactive((&layout,), move |(layout,)| {
+            let Some(layout) = layout else {
+                return;
+            };
+            // Only touch what differs, so echoing the table's own changes back does not re-render it.
+            let visible = layout.visible_columns(&columns);
+            if *visible_columns.peek() != visible {
+                visible_columns.set(visible);
             }
-        }
-        if visible.is_empty() {
-            if let Some(first) = columns.first() {
-                visible.insert(first.id.clone());
+            if *column_order.peek() != layout.order {
+                column_order.set(layout.order);
+            }
+            if *column_widths.peek() != layout.widths {
+                column_widths.set(layout.widths);
+            }
+            if *column_pins.peek() != layout.pins {
+                column_pins.set(layout.pins);
             }
+        }));
+    }
+    let emit_layout = {
+        let columns = Rc::new(columns.clone());
+        move || {
+            let Some(handler) = on_layout_change else {
+                return;
+            };
+            let pins = column_pins.peek().clone();
+            let order = arrange_columns(&reconcile_order(&column_order.peek(), &columns), &pins);
+            let visible = visible_columns.peek();
+            handler.call(TableLayout {
+                visible: order
+                    .iter()
+                    .filter(|id| visible.contains(*id))
+                    .cloned()
+                    .collect(),
+                order,
+                widths: column_widths.peek().clone(),
+                pins,
+            });
         }
-        visible
     };
-    let visible_columns = use_signal({
-        let initial_visible = initial_visible.clone();
-        move || initial_visible.clone()
-    });
+    let mut resizing = use_signal(|| None::<ColumnResize>);
+    let mut collapsed_groups = use_signal(HashSet::<String>::new);
+    let mut expanded_rows = use_signal(HashSet::<String>::new);
+    let mut dragging = use_signal(|| None::<String>);
+    let head_refs = use_hook(|| Rc::new(RefCell::new(HashMap::<String, Rc<MountedData>>::new())));
 
-    let toggleable_columns = Rc::new(
-        columns
-            .iter()
-            .filter(|column| column.toggleable)
-            .cloned()
-            .collect::<Vec<_>>(),
-    );
-    let column_order = Rc::new(
-        columns
-            .iter()
-            .map(|column| column.id.clone())
-            .collect::<Vec<_>>(),
-    );
-    let row_order = Rc::new(rows.iter().map(|row| row.id.clone()).collect::<Vec<_>>());
+    let pins_snapshot = column_pins();
+    let widths_snapshot = column_widths();
+    let ordered_ids = Rc::new(arrange_columns(
+        &reconcile_order(&column_order(), &columns),
+        &pins_snapshot,
+    ));
+    let ordered_columns: Vec<TableColumnConfig<R>> = ordered_ids
+        .iter()
+        .filter_map(|id| columns.iter().find(|column| &column.id == id).cloned())
+        .collect();
+    let row_order = row_ids();
     let min_visible_columns = max(
         columns.iter().filter(|column| !column.toggleable).count(),
         1,
@@ -233,6 +1553,20 @@ pub fn InteractiveTable(
 
     let selected_snapshot = selected_rows();
     let visible_snapshot = visible_columns();
+    let placed_columns = place_columns(
+        &ordered_columns
+            .iter()
+            .filter(|column| visible_snapshot.contains(&column.id))
+            .cloned()
+            .collect::<Vec<_>>(),
+        &widths_snapshot,
+        &pins_snapshot,
+        if render_detail.is_some() {
+            CHECKBOX_COLUMN_WIDTH + ROW_TOGGLE_WIDTH
+        } else {
+            CHECKBOX_COLUMN_WIDTH
+        },
+    );
 
     let selected_count = row_order
         .iter()
@@ -240,15 +1574,143 @@ pub fn InteractiveTable(
         .count();
     let all_selected = !rows.is_empty() && selected_count == row_order.len();
 
-    let empty_message = empty_state.unwrap_or_else(|| "暂无数据".to_string());
+    let empty_message = empty_state.unwrap_or_else(|| i18n.t("table.empty"));
+    let total_label = total_label.unwrap_or_else(|| i18n.t("table.total"));
+
+    let row_groups = use_memo(use_reactive((&group_by,), move |(group_by,)| {
+        Rc::new(
+            group_by
+                .map(|column_id| group_rows(&sorted_rows.read(), &column_id))
+                .unwrap_or_default(),
+        )
+    }));
+    let groups = row_groups();
+    let group_column = group_by
+        .as_deref()
+        .and_then(|column_id| columns.iter().find(|column| column.id == column_id));
+    let group_label = group_column
+        .map(|column| column.label.clone())
+        .unwrap_or_default();
+    let group_format = group_column.and_then(|column| column.group_format.clone());
+    let collapsed_snapshot = collapsed_groups();
+    let expanded_snapshot = expanded_rows();
+    let grouped = group_by.is_some();
+    let has_detail = render_detail.is_some();
+    let body_layout = use_memo(use_reactive(
+        (&grouped, &has_detail),
+        move |(grouped, has_detail)| {
+            let row_ids = row_ids.read();
+            let groups = row_groups.read();
+            let expanded = expanded_rows.read();
+            Rc::new(body_items(
+                row_ids.len(),
+                grouped.then_some(groups.as_slice()),
+                &collapsed_groups.read(),
+                |index| has_detail && expanded.contains(&row_ids[index]),
+            ))
+        },
+    ));
+    let body_keys = use_memo(move || {
+        let row_ids = row_ids.read();
+        let groups = row_groups.read();
+        Rc::new(
+            body_layout
+                .read()
+                .iter()
+                .map(|item| body_key(*item, &groups, &row_ids))
+                .collect::<Vec<_>>(),
+        )
+    });
+    let body = body_layout();
+    let body_key = |item: BodyItem| body_key(item, &groups, &row_order);
+    let expanded_count = row_order
+        .iter()
+        .filter(|id| expanded_snapshot.contains(*id))
+        .count();
+    let set_expanded = {
+        let row_order = row_order.clone();
+        move |next: HashSet<String>| {
+            if let Some(handler) = on_expanded_change {
+                handler.call(
+                    row_order
+                        .iter()
+                        .filter(|id| next.contains(*id))
+                        .cloned()
+                        .collect(),
+                );
+            }
+            expanded_rows.set(next);
+        }
+    };
+    // Keyed on what is aggregated rather than on `columns`, whose renderers are rebuilt on every render; only the
+    // cheap formatting below runs each time.
+    let aggregated_columns = columns
+        .iter()
+        .filter(|column| !column.aggregates.is_empty())
+        .map(|column| (column.id.clone(), column.aggregates.clone()))
+        .collect::<Vec<_>>();
+    let column_totals = use_memo(use_reactive(
+        (&aggregated_columns,),
+        move |(aggregated_columns,)| {
+            let rows = sorted_rows.read();
+            let all_rows = rows.iter().collect::<Vec<_>>();
+            Rc::new(
+                aggregated_columns
+                    .into_iter()
+                    .map(|(id, aggregates)| {
+                        let values = aggregate_values(&all_rows, &id, &aggregates);
+                        (id, values)
+                    })
+                    .collect::<HashMap<_, _>>(),
+            )
+        },
+    ));
+    let totals: Vec<String> = {
+        let column_totals = column_totals.read();
+        placed_columns
+            .iter()
+            .map(|placed| match &summary {
+                Some(summary) => {
+                    format_aggregates(&placed.config, &summary.values_for(&placed.config), i18n)
+                }
+                None => column_totals
+                    .get(&placed.config.id)
+                    .map(|values| format_aggregates(&placed.config, values, i18n))
+                    .unwrap_or_default(),
+            })
+            .collect()
+    };
+    let has_totals = totals.iter().any(|total| !total.is_empty());
+
+    let window = scroll.use_window(virtualization.clone(), body_keys)();
+    let row_height = virtualization.as_ref().map(|config| config.row_height);
+    let fixed_row_style = match row_height {
+        Some(RowHeight::Fixed(height)) => Some(format!("height: {height}px;")),
+        _ => None,
+    };
+    let column_span = 1 + placed_columns.len();
 
     let mut selection_signal_header = selected_rows.clone();
     let row_order_for_header = row_order.clone();
     let selection_handler_header = selection_handler.clone();
 
+    let mut finish_resize = {
+        let emit_layout = emit_layout.clone();
+        move || {
+            if resizing.peek().is_some() {
+                resizing.set(None);
+                if let Some(handler) = on_column_resize {
+                    handler.call(column_widths.peek().clone());
+                }
+                emit_layout();
+            }
+        }
+    };
+
     rsx! {
         div {
             class: wrapper_class,
+            "data-expandable": render_detail.is_some(),
             onclick: {
                 let mut open = columns_menu_open.clone();
                 move |_| {
@@ -259,7 +1721,30 @@ pub fn InteractiveTable(
             },
             div {
                 class: "ui-data-table-toolbar",
-                if !toggleable_columns.is_empty() {
+                if render_detail.is_some() {
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Sm,
+                        disabled: rows.is_empty() || expanded_count == row_order.len(),
+                        on_click: {
+                            let mut set_expanded = set_expanded.clone();
+                            let row_order = row_order.clone();
+                            move |_| set_expanded(row_order.iter().cloned().collect())
+                        },
+                        {i18n.t("table.expand_all")}
+                    }
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Sm,
+                        disabled: expanded_count == 0,
+                        on_click: {
+                            let mut set_expanded = set_expanded.clone();
+                            move |_| set_expanded(HashSet::new())
+                        },
+                        {i18n.t("table.collapse_all")}
+                    }
+                }
+                if !ordered_columns.is_empty() {
                     div {
                         class: "ui-data-table-columns",
                         Button {
@@ -288,7 +1773,7 @@ pub fn InteractiveTable(
                                 rect { x: "14", y: "14", width: "7", height: "7" }
                                 rect { x: "3", y: "14", width: "7", height: "7" }
                             }
-                            "列控制"
+                            {i18n.t("table.columns")}
                         }
                         if columns_menu_open() {
                             div {
@@ -296,50 +1781,137 @@ pub fn InteractiveTable(
                                 onclick: move |evt| {
                                     evt.stop_propagation();
                                 },
-                                for column in toggleable_columns.iter() {
+                                for column in ordered_columns.iter() {
                                     {
                                         let column_id = column.id.clone();
                                         let column_label = column.label.clone();
-                                        let mut visible_signal = visible_columns.clone();
+                                        let toggleable = column.toggleable;
+                                        let mut visible_signal = visible_columns;
                                         let handler = visibility_handler.clone();
-                                        let column_order = column_order.clone();
+                                        let ordered_ids = ordered_ids.clone();
                                         let min_visible = min_visible_columns;
+                                        let pin = pins_snapshot.get(&column_id).copied();
 
+                                        let visibility_id = column_id.clone();
                                         let is_visible = visible_snapshot.contains(&column_id);
+                                        let is_dragging = dragging.read().as_ref() == Some(&column_id);
+
+                                        let emit_visibility = {
+                                            let ordered_ids = ordered_ids.clone();
+                                            let emit_layout = emit_layout.clone();
+                                            move |next: &HashSet<String>| {
+                                                if let Some(handler) = handler.clone() {
+                                                    let payload = ordered_ids
+                                                        .iter()
+                                                        .filter(|id| next.contains(*id))
+                                                        .cloned()
+                                                        .collect::<Vec<_>>();
+                                                    handler.call(payload);
+                                                }
+                                                emit_layout();
+                                            }
+                                        };
+                                        let toggle_pin = {
+                                            let column_id = column_id.clone();
+                                            let emit_layout = emit_layout.clone();
+                                            move |side: ColumnPin| {
+                                                let pins = {
+                                                    let mut pins = column_pins.write();
+                                                    if pins.get(&column_id) == Some(&side) {
+                                                        pins.remove(&column_id);
+                                                    } else {
+                                                        pins.insert(column_id.clone(), side);
+                                                    }
+                                                    pins.clone()
+                                                };
+                                                if let Some(handler) = on_column_pin_change {
+                                                    handler.call(pins);
+                                                }
+                                                emit_layout();
+                                            }
+                                        };
+                                        let emit_order = emit_layout.clone();
 
                                         rsx! {
-                                            label {
+                                            div {
+                                                key: "{column_id}",
                                                 class: "ui-data-table-columns-option",
-                                                Checkbox {
-                                                    checked: is_visible,
-                                                    on_checked_change: move |checked| {
-                                                        let mut next = visible_signal();
-                                                        if checked {
-                                                            if next.insert(column_id.clone()) {
-                                                                visible_signal.set(next.clone());
-                                                                if let Some(handler) = handler.clone() {
-                                                                    let payload = column_order
-                                                                        .iter()
-                                                                        .filter(|id| next.contains(*id))
-                                                                        .cloned()
-                                                                        .collect::<Vec<_>>();
-                                                                    handler.call(payload);
+                                                draggable: "true",
+                                                "data-dragging": is_dragging,
+                                                ondragstart: {
+                                                    let column_id = column_id.clone();
+                                                    move |evt: DragEvent| {
+                                                        let transfer = evt.data_transfer();
+                                                        _ = transfer.set_data("text/plain", &column_id);
+                                                        transfer.set_effect_allowed("move");
+                                                        dragging.set(Some(column_id.clone()));
+                                                    }
+                                                },
+                                                ondragover: move |evt: DragEvent| evt.prevent_default(),
+                                                ondrop: {
+                                                    let column_id = column_id.clone();
+                                                    move |evt: DragEvent| {
+                                                        evt.prevent_default();
+                                                        let source = dragging.peek().clone();
+                                                        dragging.set(None);
+                                                        let Some(source) = source.filter(|source| *source != column_id) else {
+                                                            return;
+                                                        };
+                                                        let next = move_column(&ordered_ids, &source, &column_id);
+                                                        column_order.set(next.clone());
+                                                        if let Some(handler) = on_column_order_change {
+                                                            handler.call(next);
+                                                        }
+                                                        emit_order();
+                                                    }
+                                                },
+                                                ondragend: move |_| dragging.set(None),
+                                                span { class: "ui-data-table-columns-grip", "aria-hidden": "true", "⋮⋮" }
+                                                label {
+                                                    class: "ui-data-table-columns-check",
+                                                    Checkbox {
+                                                        checked: is_visible,
+                                                        disabled: !toggleable,
+                                                        on_checked_change: move |checked| {
+                                                            let mut next = visible_signal();
+                                                            if checked {
+                                                                if next.insert(visibility_id.clone()) {
+                                                                    visible_signal.set(next.clone());
+                                                                    emit_visibility(&next);
                                                                 }
+                                                            } else if next.len() > min_visible && next.remove(&visibility_id) {
+                                                                visible_signal.set(next.clone());
+                                                                emit_visibility(&next);
                                                             }
-                                                        } else if next.len() > min_visible && next.remove(&column_id) {
-                                                            visible_signal.set(next.clone());
-                                                            if let Some(handler) = handler.clone() {
-                                                                let payload = column_order
-                                                                    .iter()
-                                                                    .filter(|id| next.contains(*id))
-                                                                    .cloned()
-                                                                    .collect::<Vec<_>>();
-                                                                handler.call(payload);
-                                                            }
-                                                        }
-                                                    },
+                                                        },
+                                                    }
+                                                    span { "{column_label}" }
+                                                }
+                                                div {
+                                                    class: "ui-data-table-columns-pins",
+                                                    button {
+                                                        r#type: "button",
+                                                        class: "ui-data-table-columns-pin",
+                                                        title: i18n.t("table.pin_left"),
+                                                        "data-active": pin == Some(ColumnPin::Left),
+                                                        onclick: {
+                                                            let mut toggle_pin = toggle_pin.clone();
+                                                            move |_| toggle_pin(ColumnPin::Left)
+                                                        },
+                                                        "⇤"
+                                                    }
+                                                    button {
+                                                        r#type: "button",
+                                                        class: "ui-data-table-columns-pin",
+                                                        title: i18n.t("table.pin_right"),
+                                                        "data-active": pin == Some(ColumnPin::Right),
+                                                        onclick: {
+                                                            let mut toggle_pin = toggle_pin.clone();
+                                                            move |_| toggle_pin(ColumnPin::Right)
+                                                        },
+                                                        "⇥"
+                                                    }
                                                 }
-                                                span { "{column_label}" }
                                             }
                                         }
                                     }
@@ -351,12 +1923,16 @@ pub fn InteractiveTable(
                 if selected_count > 0 {
                     span {
                         class: "ui-data-table-selection-indicator",
-                        "已选择 {selected_count} 行"
+                        {i18n.t_count("table.selected", selected_count, &[])}
                     }
                 }
             }
             div {
                 class: "ui-data-table-scroll",
+                "data-virtual": virtualization.is_some(),
+                style: virtualization.as_ref().map(|config| format!("height: {}; overflow-y: auto;", config.height)),
+                onscroll: scroll.on_scroll(),
+                onmounted: scroll.on_viewport_mounted(),
                 Table {
                     class: Some(inner_table_class.clone()),
                     TableHeader {
@@ -409,33 +1985,227 @@ pub fn InteractiveTable(
                                     },
                                 }
                             }
-                            for column in columns.iter().cloned() {
-                                if visible_snapshot.contains(&column.id) {
-                                    TableHead { "{column.label}" }
+                            for placed in placed_columns.iter() {
+                                {
+                                    let column = placed.config.clone();
+                                    let position = sort_snapshot
+                                        .iter()
+                                        .position(|sort| sort.column_id == column.id);
+                                    let direction = position.map(|index| sort_snapshot[index].direction);
+                                    let indicator = match direction {
+                                        Some(TableSortDirection::Ascending) => "↑",
+                                        Some(TableSortDirection::Descending) => "↓",
+                                        None => "↕",
+                                    };
+                                    let rank = position
+                                        .filter(|_| sort_snapshot.len() > 1)
+                                        .map(|index| (index + 1).to_string());
+                                    let column_id = column.id.clone();
+                                    let head_refs = head_refs.clone();
+
+                                    rsx! {
+                                        th {
+                                            key: "{column.id}",
+                                            class: "ui-table-head ui-data-table-head",
+                                            style: placed.style.clone(),
+                                            "data-pinned": placed.pin,
+                                            onmounted: {
+                                                let head_refs = head_refs.clone();
+                                                let column_id = column_id.clone();
+                                                move |event: MountedEvent| {
+                                                    head_refs.borrow_mut().insert(column_id.clone(), event.data());
+                                                }
+                                            },
+                                            if column.sort.is_some() {
+                                                button {
+                                                    r#type: "button",
+                                                    class: "ui-data-table-sort",
+                                                    "data-sorted": direction.is_some(),
+                                                    title: i18n.t("table.sort_hint"),
+                                                    onclick: {
+                                                        let column_id = column_id.clone();
+                                                        move |evt: MouseEvent| {
+                                                            let next = next_sort(
+                                                                &sort_state.peek(),
+                                                                &column_id,
+                                                                evt.modifiers().shift(),
+                                                            );
+                                                            sort_state.set(next.clone());
+                                                            if let Some(handler) = on_sort_change {
+                                                                handler.call(next);
+                                                            }
+                                                        }
+                                                    },
+                                                    span { "{column.label}" }
+                                                    span { class: "ui-data-table-sort-indicator", "{indicator}" }
+                                                    if let Some(rank) = rank {
+                                                        span { class: "ui-data-table-sort-rank", "{rank}" }
+                                                    }
+                                                }
+                                            } else {
+                                                "{column.label}"
+                                            }
+                                            span {
+                                                class: "ui-data-table-resize",
+                                                title: i18n.t("table.resize_hint"),
+                                                onclick: move |evt| evt.stop_propagation(),
+                                                onmousedown: move |evt: MouseEvent| {
+                                                    evt.prevent_default();
+                                                    evt.stop_propagation();
+                                                    let start_x = evt.client_coordinates().x;
+                                                    let known = column_widths.peek().get(&column_id).copied();
+                                                    let mounted = head_refs.borrow().get(&column_id).cloned();
+                                                    let column_id = column_id.clone();
+                                                    let min_width = column.min_width;
+                                                    spawn(async move {
+                                                        let start_width = match (known, mounted) {
+                                                            (Some(width), _) => width,
+                                                            (None, Some(mounted)) => mounted
+                                                                .get_client_rect()
+                                                                .await
+                                                                .map(|rect| rect.width())
+                                                                .unwrap_or(min_width),
+                                                            (None, None) => min_width,
+                                                        };
+                                                        resizing.set(Some(ColumnResize {
+                                                            column_id,
+                                                            start_x,
+                                                            start_width,
+                                                            min_width,
+                                                        }));
+                                                    });
+                                                },
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
                     TableBody {
-                        for row in rows.iter().cloned() {
-                            {
-                                let row_id = row.id.clone();
-                                let is_selected = selected_snapshot.contains(&row_id);
-                                let mut selection_signal = selected_rows.clone();
-                                let handler = selection_handler.clone();
-                                let row_order = row_order.clone();
-
-                                rsx! {
-                                    TableRow {
-                                        class: Some(if is_selected { "is-selected".to_string() } else { String::new() }),
-                                        TableCell {
-                                            class: Some("ui-data-table-checkbox-cell".to_string()),
-                                            Checkbox {
-                                                checked: is_selected,
-                                                on_checked_change: move |checked| {
-                                                    let mut next = selection_signal();
-                                                    if checked {
-                                                        if next.insert(row_id.clone()) {
+                        if window.padding_top > 0.0 {
+                            tr {
+                                class: "ui-data-table-spacer",
+                                style: "height: {window.padding_top}px;",
+                                td { colspan: "{column_span}" }
+                            }
+                        }
+                        for item in body[window.start..window.end].iter().copied() {
+                            if let BodyItem::Group(index) = item {
+                                {
+                                    let group = &groups[index];
+                                    let key = group.key.clone();
+                                    let is_collapsed = collapsed_snapshot.contains(&key);
+                                    let members = group.rows.iter().map(|index| &rows[*index]).collect::<Vec<_>>();
+                                    let given = group_summaries.as_ref().and_then(|summaries| summaries.get(&key));
+                                    let summaries = placed_columns
+                                        .iter()
+                                        .map(|placed| match given {
+                                            Some(given) => format_aggregates(&placed.config, &given.values_for(&placed.config), i18n),
+                                            None => aggregate_summary(&placed.config, &members, i18n),
+                                        })
+                                        .collect::<Vec<_>>();
+                                    let title = match &group_format {
+                                        _ if key.is_empty() => i18n.t("table.group_blank"),
+                                        Some(format) => format.format(&key),
+                                        None => key.clone(),
+                                    };
+                                    let count = given.map_or(members.len(), |given| given.rows);
+                                    let on_group_mounted = scroll.on_row_mounted(body_key(item), row_height);
+
+                                    rsx! {
+                                        tr {
+                                            key: "group:{key}",
+                                            class: "ui-table-row ui-data-table-group",
+                                            "data-collapsed": is_collapsed,
+                                            style: fixed_row_style.clone(),
+                                            onmounted: on_group_mounted,
+                                            td {
+                                                class: "ui-table-cell ui-data-table-checkbox-cell",
+                                                button {
+                                                    r#type: "button",
+                                                    class: "ui-data-table-group-toggle",
+                                                    "aria-expanded": !is_collapsed,
+                                                    title: if is_collapsed { i18n.t("table.expand_group") } else { i18n.t("table.collapse_group") },
+                                                    onclick: move |_| {
+                                                        let mut next = collapsed_groups.peek().clone();
+                                                        if !next.remove(&key) {
+                                                            next.insert(key.clone());
+                                                        }
+                                                        collapsed_groups.set(next);
+                                                    },
+                                                    if is_collapsed { "▸" } else { "▾" }
+                                                }
+                                            }
+                                            for (position, placed) in placed_columns.iter().enumerate() {
+                                                td {
+                                                    class: "ui-table-cell",
+                                                    style: placed.style.clone(),
+                                                    "data-pinned": placed.pin,
+                                                    if position == 0 {
+                                                        span { class: "ui-data-table-group-label", {i18n.t_with("table.group_heading", &[("column", &group_label), ("value", &title)])} }
+                                                        span { class: "ui-data-table-group-count", {i18n.t_count("table.group_rows", count, &[])} }
+                                                    }
+                                                    if !summaries[position].is_empty() {
+                                                        span { class: "ui-data-table-aggregate", "{summaries[position]}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            } else if let BodyItem::Row(index) = item {
+                                {
+                                    let row = rows[index].clone();
+                                    let row_id = row.row_id();
+                                    let is_selected = selected_snapshot.contains(&row_id);
+                                    let mut selection_signal = selected_rows.clone();
+                                    let handler = selection_handler.clone();
+                                    let row_order = row_order.clone();
+                                    let row_key = row_id.clone();
+                                    let on_row_mounted = scroll.on_row_mounted(row_id.clone(), row_height);
+                                    let is_expanded = expanded_snapshot.contains(&row_id);
+                                    let mut toggle_detail = {
+                                        let mut set_expanded = set_expanded.clone();
+                                        let row_id = row_id.clone();
+                                        move |expand: bool| {
+                                            let mut next = expanded_rows.peek().clone();
+                                            let changed = if expand {
+                                                next.insert(row_id.clone())
+                                            } else {
+                                                next.remove(&row_id)
+                                            };
+                                            if changed {
+                                                set_expanded(next);
+                                            }
+                                        }
+                                    };
+
+                                    rsx! {
+                                        tr {
+                                            key: "{row_key}",
+                                            class: if is_selected { "ui-table-row is-selected" } else { "ui-table-row" },
+                                            style: fixed_row_style.clone(),
+                                            onmounted: on_row_mounted,
+                                            TableCell {
+                                                class: Some("ui-data-table-checkbox-cell".to_string()),
+                                                Checkbox {
+                                                    checked: is_selected,
+                                                    on_checked_change: move |checked| {
+                                                        let mut next = selection_signal();
+                                                        if checked {
+                                                            if next.insert(row_id.clone()) {
+                                                                selection_signal.set(next.clone());
+                                                                if let Some(handler) = handler.clone() {
+                                                                    let payload = row_order
+                                                                        .iter()
+                                                                        .filter(|id| next.contains(*id))
+                                                                        .cloned()
+                                                                        .collect::<Vec<_>>();
+                                                                    handler.call(payload);
+                                                                }
+                                                            }
+                                                        } else if next.remove(&row_id) {
                                                             selection_signal.set(next.clone());
                                                             if let Some(handler) = handler.clone() {
                                                                 let payload = row_order
@@ -446,34 +2216,95 @@ pub fn InteractiveTable(
                                                                 handler.call(payload);
                                                             }
                                                         }
-                                                    } else if next.remove(&row_id) {
-                                                        selection_signal.set(next.clone());
-                                                        if let Some(handler) = handler.clone() {
-                                                            let payload = row_order
-                                                                .iter()
-                                                                .filter(|id| next.contains(*id))
-                                                                .cloned()
-                                                                .collect::<Vec<_>>();
-                                                            handler.call(payload);
-                                                        }
+                                                    }
+                                                }
+                                                if render_detail.is_some() {
+                                                    button {
+                                                        r#type: "button",
+                                                        class: "ui-data-table-row-toggle",
+                                                        "aria-expanded": is_expanded,
+                                                        title: if is_expanded { i18n.t("table.collapse_detail") } else { i18n.t("table.expand_detail") },
+                                                        onclick: {
+                                                            let mut toggle_detail = toggle_detail.clone();
+                                                            move |_| toggle_detail(!is_expanded)
+                                                        },
+                                                        onkeydown: move |evt: KeyboardEvent| match evt.key() {
+                                                            Key::ArrowRight => {
+                                                                evt.prevent_default();
+                                                                toggle_detail(true);
+                                                            }
+                                                            Key::ArrowLeft => {
+                                                                evt.prevent_default();
+                                                                toggle_detail(false);
+                                                            }
+                                                            _ => {}
+                                                        },
+                                                        if is_expanded { "▾" } else { "▸" }
                                                     }
                                                 }
                                             }
-                                        }
-                                        for column in columns.iter().cloned() {
-                                            if visible_snapshot.contains(&column.id) {
-                                                {
-                                                    let value = row
-                                                        .cells
-                                                        .get(&column.id)
-                                                        .cloned()
-                                                        .unwrap_or_default();
-                                                    rsx! { TableCell { "{value}" } }
+                                            for placed in placed_columns.iter() {
+                                                if placed.config.editor.is_some() {
+                                                    EditableCell {
+                                                        row: row.clone(),
+                                                        column: placed.config.clone(),
+                                                        edits,
+                                                        on_cell_edit,
+                                                        style: placed.style.clone(),
+                                                        pinned: placed.pin,
+                                                    }
+                                                } else {
+                                                    td {
+                                                        class: "ui-table-cell",
+                                                        style: placed.style.clone(),
+                                                        "data-pinned": placed.pin,
+                                                        if let Some(render) = &placed.config.render {
+                                                            {render.render(&row)}
+                                                        } else {
+                                                            {row.cell_text(&placed.config.id)}
+                                                        }
+                                                    }
                                                 }
                                             }
                                         }
                                     }
                                 }
+                            } else if let (BodyItem::Detail(index), Some(render_detail)) = (item, render_detail) {
+                                tr {
+                                    key: "detail:{row_order[index]}",
+                                    class: "ui-data-table-detail",
+                                    onmounted: scroll.on_row_mounted(body_key(item), row_height),
+                                    td {
+                                        class: "ui-table-cell",
+                                        colspan: "{column_span}",
+                                        div { class: "ui-data-table-detail-panel", {render_detail.call(rows[index].clone())} }
+                                    }
+                                }
+                            }
+                        }
+                        if window.padding_bottom > 0.0 {
+                            tr {
+                                class: "ui-data-table-spacer",
+                                style: "height: {window.padding_bottom}px;",
+                                td { colspan: "{column_span}" }
+                            }
+                        }
+                    }
+                    if has_totals && !rows.is_empty() {
+                        TableFooter {
+                            tr {
+                                class: "ui-table-row ui-data-table-total",
+                                td { class: "ui-table-cell ui-data-table-checkbox-cell", "{total_label}" }
+                                for (placed, total) in placed_columns.iter().zip(totals.iter()) {
+                                    td {
+                                        class: "ui-table-cell",
+                                        style: placed.style.clone(),
+                                        "data-pinned": placed.pin,
+                                        if !total.is_empty() {
+                                            span { class: "ui-data-table-aggregate", "{total}" }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -485,6 +2316,591 @@ pub fn InteractiveTable(
                     "{empty_message}"
                 }
             }
+            if let Some(resize) = resizing() {
+                div {
+                    class: "ui-data-table-resize-overlay",
+                    onmousemove: move |evt: MouseEvent| {
+                        let width = (resize.start_width + evt.client_coordinates().x - resize.start_x)
+                            .max(resize.min_width)
+                            .round();
+                        column_widths.write().insert(resize.column_id.clone(), width);
+                    },
+                    onmouseup: {
+                        let mut finish_resize = finish_resize.clone();
+                        move |_| finish_resize()
+                    },
+                    onmouseleave: move |_| finish_resize(),
+                }
+            }
+        }
+    }
+}
+
+/// Text shown for an edited value before the row reflects it.
+fn edited_text(editor: &CellEditor, value: &str, i18n: I18n) -> String {
+    match editor {
+        CellEditor::Select(options) => options
+            .iter()
+            .find(|option| option.value == value)
+            .map(|option| option.label.clone())
+            .unwrap_or_else(|| value.to_string()),
+        CellEditor::Switch => i18n.t(if value == "true" {
+            "table.yes"
+        } else {
+            "table.no"
+        }),
+        CellEditor::Text | CellEditor::Date => value.to_string(),
+    }
+}
+
+/// A cell of an editable column. Double-click or Enter opens the editor; Enter commits text and Escape cancels.
+/// Selects, dates and switches commit as soon as a value is picked.
+#[component]
+fn EditableCell<R: TableRecord>(
+    row: R,
+    column: TableColumnConfig<R>,
+    edits: CellEdits,
+    #[props(!optional)] on_cell_edit: Option<EventHandler<CellEdit>>,
+    #[props(!optional)] style: Option<String>,
+    #[props(!optional)] pinned: Option<&'static str>,
+) -> Element {
+    let i18n = use_i18n();
+    let key: CellKey = (row.row_id(), column.id.clone());
+    let text = row.cell_text(&column.id);
+
+    // Forget an optimistic value once the row moves on from the text it was made against.
+    use_effect(use_reactive((&key, &text), move |(key, text)| {
+        let mut pending = edits.pending;
+        let stale = pending
+            .peek()
+            .get(&key)
+            .is_some_and(|edit| edit.base != text);
+        if stale {
+            pending.write().remove(&key);
+        }
+    }));
+
+    let Some(editor) = column.editor.clone() else {
+        return rsx! {
+            td { class: "ui-table-cell", style, "data-pinned": pinned, "{text}" }
+        };
+    };
+    let (value, is_pending) = edits.value(&key, text);
+    let is_editing = edits.editing.read().as_ref() == Some(&key);
+    let error = edits.errors.read().get(&key).cloned();
+    let mut draft = edits.draft;
+
+    let open = {
+        let key = key.clone();
+        let value = value.clone();
+        move || edits.open(key.clone(), value.clone())
+    };
+    let commit = {
+        let row = row.clone();
+        let column = column.clone();
+        move |value: String| edits.commit(&row, &column, value, on_cell_edit)
+    };
+    let commit_on_enter = commit.clone();
+    let commits_on_enter = editor == CellEditor::Text;
+    let editor_kind = match editor {
+        CellEditor::Date => "date",
+        _ => "inline",
+    };
+    let is_invalid = error.is_some();
+    let content = match (&column.render, is_pending) {
+        (Some(render), false) => render.render(&row),
+        _ => rsx! { "{edited_text(&editor, &value, i18n)}" },
+    };
+
+    rsx! {
+        td {
+            class: "ui-table-cell ui-data-table-cell-editable",
+            style,
+            "data-pinned": pinned,
+            tabindex: if is_editing { "-1" } else { "0" },
+            title: i18n.t("table.edit_hint"),
+            "data-editing": is_editing,
+            "data-pending": is_pending,
+            "data-invalid": is_invalid,
+            ondoubleclick: {
+                let open = open.clone();
+                move |_| {
+                    if !is_editing {
+                        open();
+                    }
+                }
+            },
+            onkeydown: move |evt: KeyboardEvent| match evt.key() {
+                    Key::Enter if !is_editing => {
+                        evt.prevent_default();
+                        open();
+                    }
+                    Key::Enter if commits_on_enter => {
+                        evt.prevent_default();
+                        commit_on_enter(draft());
+                    }
+                    Key::Escape if is_editing => {
+                        evt.stop_propagation();
+                        edits.cancel(&key);
+                    }
+                    _ => {}
+            },
+            if is_editing {
+                div {
+                    class: "ui-data-table-cell-editor",
+                    "data-editor": editor_kind,
+                    match editor {
+                        CellEditor::Text => rsx! {
+                            Input {
+                                value: draft(),
+                                autofocus: true,
+                                on_input: move |evt: FormEvent| draft.set(evt.value()),
+                            }
+                        },
+                        CellEditor::Select(options) => rsx! {
+                            Select {
+                                placeholder: i18n.t("table.select_placeholder"),
+                                options,
+                                selected: Some(draft()),
+                                on_change: move |value: String| commit(value),
+                            }
+                        },
+                        CellEditor::Date => rsx! {
+                            Calendar {
+                                initial_month: leading_date(&draft()).unwrap_or_else(NaiveDate::today),
+                                selected: leading_date(&draft()),
+                                on_select: move |date: NaiveDate| commit(date.format("%Y-%m-%d").to_string()),
+                            }
+                        },
+                        CellEditor::Switch => rsx! {
+                            Switch {
+                                checked: draft() == "true",
+                                on_checked_change: move |checked: bool| commit(checked.to_string()),
+                            }
+                        },
+                    }
+                }
+            } else {
+                {content}
+            }
+            if let Some(error) = error {
+                span { class: "ui-data-table-cell-error", "{error}" }
+            }
+        }
+    }
+}
+
+/// A [`Table`] that renders only the rows inside its scroll viewport. `render_row` receives a row index and
+/// returns that row's cells; the table creates the row element itself so it can be measured. `children` go into
+/// the table before the body, which is where `TableCaption`, `TableHeader` and `TableFooter` belong.
+#[component]
+pub fn VirtualTable(
+    #[props(into, default)] class: Option<String>,
+    row_count: usize,
+    /// Number of columns, so the spacer rows span the whole table.
+    columns: usize,
+    virtualization: TableVirtualization,
+    render_row: Callback<usize, Element>,
+    children: Element,
+) -> Element {
+    let scroll = use_virtual_scroll();
+    let keys = use_memo(use_reactive((&row_count,), |(row_count,)| {
+        Rc::new(
+            (0..row_count)
+                .map(|index| index.to_string())
+                .collect::<Vec<_>>(),
+        )
+    }));
+    let window = scroll.use_window(Some(virtualization.clone()), keys)();
+    let row_height = virtualization.row_height;
+    let fixed_row_style = match row_height {
+        RowHeight::Fixed(height) => Some(format!("height: {height}px;")),
+        RowHeight::Measured { .. } => None,
+    };
+
+    rsx! {
+        div {
+            class: "ui-data-table-scroll",
+            "data-virtual": true,
+            style: "height: {virtualization.height}; overflow-y: auto;",
+            onscroll: scroll.on_scroll(),
+            onmounted: scroll.on_viewport_mounted(),
+            Table {
+                class: class,
+                {children}
+                TableBody {
+                    if window.padding_top > 0.0 {
+                        tr {
+                            class: "ui-data-table-spacer",
+                            style: "height: {window.padding_top}px;",
+                            td { colspan: "{columns}" }
+                        }
+                    }
+                    for index in window.start..window.end {
+                        tr {
+                            key: "{index}",
+                            class: "ui-table-row",
+                            style: fixed_row_style.clone(),
+                            onmounted: scroll.on_row_mounted(index.to_string(), Some(row_height)),
+                            {render_row.call(index)}
+                        }
+                    }
+                    if window.padding_bottom > 0.0 {
+                        tr {
+                            class: "ui-data-table-spacer",
+                            style: "height: {window.padding_bottom}px;",
+                            td { colspan: "{columns}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Locale;
+
+    fn window(row_height: RowHeight, measured: &[f64], scroll_top: f64) -> VirtualWindow {
+        virtual_window(
+            1_000,
+            row_height,
+            |index| measured.get(index).copied(),
+            scroll_top,
+            100.0,
+            2,
+        )
+    }
+
+    fn ids(rows: &[TableRowData]) -> Vec<&str> {
+        rows.iter().map(|row| row.id.as_str()).collect()
+    }
+
+    fn rows() -> Vec<TableRowData> {
+        vec![
+            TableRowData::from_pairs(
+                "a",
+                [
+                    ("name", "beta"),
+                    ("total", "¥1,200.00"),
+                    ("date", "2024-06-09"),
+                ],
+            ),
+            TableRowData::from_pairs(
+                "b",
+                [
+                    ("name", "Alpha"),
+                    ("total", "¥99.50"),
+                    ("date", "2024-06-10"),
+                ],
+            ),
+            TableRowData::from_pairs(
+                "c",
+                [("name", "alpha"), ("total", "—"), ("date", "2024/6/1")],
+            ),
+            TableRowData::from_pairs("d", [("name", "gamma"), ("total", "¥99.50"), ("date", "")]),
+        ]
+    }
+
+    fn columns() -> Vec<TableColumnConfig> {
+        vec![
+            TableColumnConfig::new("name", "Name").sortable(ColumnSortKind::Text),
+            TableColumnConfig::new("total", "Total").sortable(ColumnSortKind::Numeric),
+            TableColumnConfig::new("date", "Date").sortable(ColumnSortKind::Date),
+            TableColumnConfig::new("note", "Note"),
+        ]
+    }
+
+    #[test]
+    fn header_clicks_cycle_and_shift_adds_columns() {
+        let sorts = next_sort(&[], "name", false);
+        assert_eq!(sorts, [TableSort::asc("name")]);
+        let sorts = next_sort(&sorts, "name", false);
+        assert_eq!(sorts, [TableSort::desc("name")]);
+        assert!(next_sort(&sorts, "name", false).is_empty());
+
+        let sorts = next_sort(&[TableSort::desc("name")], "total", true);
+        assert_eq!(sorts, [TableSort::desc("name"), TableSort::asc("total")]);
+        let sorts = next_sort(&sorts, "name", true);
+        assert_eq!(sorts, [TableSort::asc("total")]);
+        // A plain click drops every other column.
+        let sorts = next_sort(
+            &[TableSort::asc("name"), TableSort::asc("total")],
+            "total",
+            false,
+        );
+        assert_eq!(sorts, [TableSort::desc("total")]);
+    }
+
+    #[test]
+    fn sorts_by_column_kind_with_missing_values_last() {
+        let columns = columns();
+
+        let mut sorted = rows();
+        sort_rows(
+            &mut sorted,
+            &sort_kinds(&columns),
+            &[TableSort::asc("total")],
+        );
+        assert_eq!(ids(&sorted), ["b", "d", "a", "c"]);
+        sort_rows(
+            &mut sorted,
+            &sort_kinds(&columns),
+            &[TableSort::desc("total")],
+        );
+        assert_eq!(ids(&sorted), ["a", "b", "d", "c"]);
+
+        let mut sorted = rows();
+        sort_rows(
+            &mut sorted,
+            &sort_kinds(&columns),
+            &[TableSort::asc("date")],
+        );
+        assert_eq!(ids(&sorted), ["c", "a", "b", "d"]);
+
+        // Ties on the first column fall through to the next one; text ignores case.
+        let mut sorted = rows();
+        sort_rows(
+            &mut sorted,
+            &sort_kinds(&columns),
+            &[TableSort::asc("name"), TableSort::desc("date")],
+        );
+        assert_eq!(ids(&sorted), ["b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn ignores_unsortable_columns_and_supports_custom_keys() {
+        let mut sorted = rows();
+        sort_rows(
+            &mut sorted,
+            &sort_kinds(&columns()),
+            &[TableSort::asc("note")],
+        );
+        assert_eq!(ids(&sorted), ["a", "b", "c", "d"]);
+
+        fn length(value: &str) -> i64 {
+            value.chars().count() as i64
         }
+        let columns: Vec<TableColumnConfig> =
+            vec![TableColumnConfig::new("name", "Name").sortable(ColumnSortKind::Custom(length))];
+        let mut sorted = rows();
+        sort_rows(
+            &mut sorted,
+            &sort_kinds(&columns),
+            &[TableSort::desc("name")],
+        );
+        assert_eq!(ids(&sorted), ["b", "c", "d", "a"]);
+    }
+
+    #[test]
+    fn fixed_rows_window_covers_viewport_plus_overscan() {
+        let top = window(RowHeight::Fixed(20.0), &[], 0.0);
+        assert_eq!((top.start, top.end), (0, 8));
+        assert_eq!(top.padding_top, 0.0);
+        assert_eq!(top.padding_bottom, 992.0 * 20.0);
+
+        let middle = window(RowHeight::Fixed(20.0), &[], 1_010.0);
+        assert_eq!((middle.start, middle.end), (48, 58));
+        assert_eq!(middle.padding_top, 48.0 * 20.0);
+
+        let bottom = window(RowHeight::Fixed(20.0), &[], 1_000_000.0);
+        assert_eq!((bottom.start, bottom.end), (998, 1_000));
+        assert_eq!(bottom.padding_bottom, 0.0);
+    }
+
+    #[test]
+    fn measured_rows_use_known_heights_and_estimate_the_rest() {
+        // Rows 0..3 are 50px tall, the rest are estimated at 20px.
+        let measured = [50.0, 50.0, 50.0];
+        let window = window(RowHeight::Measured { estimate: 20.0 }, &measured, 160.0);
+        // Row 3 spans 150..170, so it is the first visible row; the viewport ends at 260, inside row 8.
+        assert_eq!((window.start, window.end), (1, 11));
+        assert_eq!(window.padding_top, 50.0);
+        assert_eq!(window.padding_bottom, 989.0 * 20.0);
+    }
+
+    #[test]
+    fn offsets_skip_empty_rows_and_stop_at_the_end() {
+        let heights = [10.0, 0.0, 30.0, 20.0];
+        let offsets = row_offsets(
+            heights.len(),
+            RowHeight::Measured { estimate: 0.0 },
+            |index| heights.get(index).copied(),
+        );
+        assert_eq!(offsets, [0.0, 10.0, 10.0, 40.0, 60.0]);
+        assert_eq!(
+            window_at(&offsets, 10.0, 25.0, 0),
+            VirtualWindow {
+                start: 2,
+                end: 3,
+                padding_top: 10.0,
+                padding_bottom: 20.0,
+            }
+        );
+        let past_the_end = window_at(&offsets, 1_000.0, 25.0, 1);
+        assert_eq!((past_the_end.start, past_the_end.end), (3, 4));
+        assert_eq!(past_the_end.padding_top, 40.0);
+    }
+
+    #[test]
+    fn dragging_a_column_takes_the_target_position_and_keeps_new_columns() {
+        let order: Vec<String> = ["name", "total", "date"].map(String::from).to_vec();
+
+        assert_eq!(
+            move_column(&order, "name", "date"),
+            ["total", "date", "name"]
+        );
+        assert_eq!(
+            move_column(&order, "date", "name"),
+            ["date", "name", "total"]
+        );
+        assert_eq!(move_column(&order, "gone", "name"), order);
+
+        let stored: Vec<String> = ["date", "gone", "name"].map(String::from).to_vec();
+        assert_eq!(
+            reconcile_order(&stored, &columns()),
+            ["date", "name", "total", "note"]
+        );
+    }
+
+    #[test]
+    fn pinned_columns_move_to_their_side_and_stack_offsets() {
+        let order: Vec<String> = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        let pins = HashMap::from([
+            ("c".to_string(), ColumnPin::Left),
+            ("a".to_string(), ColumnPin::Right),
+            ("e".to_string(), ColumnPin::Left),
+        ]);
+        assert_eq!(arrange_columns(&order, &pins), ["c", "e", "b", "d", "a"]);
+
+        let offsets = pinned_offsets(
+            &[
+                (Some(ColumnPin::Left), 100.0),
+                (Some(ColumnPin::Left), 80.0),
+                (None, 200.0),
+                (Some(ColumnPin::Right), 60.0),
+                (Some(ColumnPin::Right), 90.0),
+            ],
+            48.0,
+        );
+        assert_eq!(
+            offsets,
+            [Some(48.0), Some(148.0), None, Some(90.0), Some(0.0)]
+        );
+    }
+
+    #[test]
+    fn groups_follow_first_appearance_and_aggregate_numbers_in_cells() {
+        let rows = rows();
+        let groups = group_rows(&rows, "total");
+        let keys: Vec<(&str, &[usize])> = groups
+            .iter()
+            .map(|group| (group.key.as_str(), group.rows.as_slice()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("¥1,200.00", &[0][..]),
+                ("¥99.50", &[1, 3][..]),
+                ("—", &[2][..])
+            ]
+        );
+
+        assert_eq!(aggregate_rows(&rows, "total", Aggregate::Count), Some(4.0));
+        assert_eq!(aggregate_rows(&rows, "total", Aggregate::Sum), Some(1399.0));
+        assert_eq!(
+            aggregate_rows(&rows, "total", Aggregate::Avg),
+            Some(1399.0 / 3.0)
+        );
+        assert_eq!(aggregate_rows(&rows, "note", Aggregate::Sum), None);
+
+        let column = TableColumnConfig::<TableRowData>::new("total", "Total")
+            .aggregate(Aggregate::Count)
+            .aggregate(Aggregate::Sum)
+            .aggregate(Aggregate::Sum);
+        let members = groups[1]
+            .rows
+            .iter()
+            .map(|index| &rows[*index])
+            .collect::<Vec<_>>();
+        let i18n = I18n::new(Locale::ZhCn);
+        assert_eq!(
+            aggregate_summary(&column, &members, i18n),
+            "计数 2 · 合计 199.00"
+        );
+        let column = column.format_aggregate(|_, value| format!("¥{value:.1}"));
+        assert_eq!(
+            aggregate_summary(&column, &members, i18n),
+            "计数 ¥2.0 · 合计 ¥199.0"
+        );
+    }
+
+    #[test]
+    fn summaries_supply_the_aggregates_a_column_declares() {
+        let column = TableColumnConfig::<TableRowData>::new("total", "Total")
+            .aggregate(Aggregate::Count)
+            .aggregate(Aggregate::Sum);
+        let summary = TableSummary::new(12)
+            .with("total", Aggregate::Avg, 50.0)
+            .with("total", Aggregate::Sum, 600.0)
+            .with("other", Aggregate::Sum, 1.0);
+        assert_eq!(
+            summary.values_for(&column),
+            [(Aggregate::Count, 12.0), (Aggregate::Sum, 600.0)]
+        );
+        assert_eq!(
+            format_aggregates(
+                &column,
+                &summary.values_for(&column),
+                I18n::new(Locale::ZhCn)
+            ),
+            "计数 12 · 合计 600.00"
+        );
+        let plain = TableColumnConfig::<TableRowData>::new("name", "Name");
+        assert!(summary.values_for(&plain).is_empty());
+    }
+
+    #[test]
+    fn body_lists_group_headers_and_details_of_expanded_rows() {
+        let groups = [
+            RowGroup {
+                key: "线上".to_string(),
+                rows: vec![0, 2],
+            },
+            RowGroup {
+                key: "批发".to_string(),
+                rows: vec![1],
+            },
+        ];
+        let none = HashSet::new();
+        let expanded = |index: usize| index == 2;
+
+        assert_eq!(
+            body_items(3, None, &none, expanded),
+            [
+                BodyItem::Row(0),
+                BodyItem::Row(1),
+                BodyItem::Row(2),
+                BodyItem::Detail(2)
+            ]
+        );
+        assert_eq!(
+            body_items(3, Some(&groups), &none, expanded),
+            [
+                BodyItem::Group(0),
+                BodyItem::Row(0),
+                BodyItem::Row(2),
+                BodyItem::Detail(2),
+                BodyItem::Group(1),
+                BodyItem::Row(1)
+            ]
+        );
+        let collapsed = HashSet::from(["线上".to_string()]);
+        assert_eq!(
+            body_items(3, Some(&groups), &collapsed, expanded),
+            [BodyItem::Group(0), BodyItem::Group(1), BodyItem::Row(1)]
+        );
     }
 }
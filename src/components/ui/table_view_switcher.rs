@@ -0,0 +1,423 @@
+use super::{
+    Badge, BadgeVariant, Button, ButtonSize, ButtonVariant, DropdownMenu, DropdownMenuItem, Input,
+    Select, SelectOption, TableLayout,
+};
+use crate::i18n::{use_i18n, I18n};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A named snapshot of a table: its column layout plus whatever state the page keeps next to it, such as
+/// filters and sort.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableView<S> {
+    pub id: String,
+    pub name: String,
+    pub layout: TableLayout,
+    pub state: S,
+}
+
+/// The saved views of one table.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableViews<S> {
+    pub views: Vec<TableView<S>>,
+    /// Id of the view applied when the table is opened.
+    pub default_view: Option<String>,
+}
+
+impl<S> Default for TableViews<S> {
+    fn default() -> Self {
+        Self {
+            views: Vec::new(),
+            default_view: None,
+        }
+    }
+}
+
+impl<S: Clone> TableViews<S> {
+    pub fn get(&self, id: &str) -> Option<&TableView<S>> {
+        self.views.iter().find(|view| view.id == id)
+    }
+
+    pub fn default_view(&self) -> Option<&TableView<S>> {
+        self.get(self.default_view.as_deref()?)
+    }
+
+    /// Saves a new view and returns its id. Blank names fall back to "视图" in the language of `i18n`; taken
+    /// names get a number appended.
+    pub fn add(&mut self, name: &str, layout: TableLayout, state: S, i18n: I18n) -> String {
+        let id = self.next_id();
+        let name = match name.trim() {
+            "" => self.unique_name(&i18n.t("table_views.untitled")),
+            name => self.unique_name(name),
+        };
+        self.views.push(TableView {
+            id: id.clone(),
+            name,
+            layout,
+            state,
+        });
+        id
+    }
+
+    /// Overwrites the layout and state of a view, keeping its name.
+    pub fn update(&mut self, id: &str, layout: TableLayout, state: S) -> bool {
+        match self.views.iter_mut().find(|view| view.id == id) {
+            Some(view) => {
+                view.layout = layout;
+                view.state = state;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Renames a view. Blank names are refused.
+    pub fn rename(&mut self, id: &str, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() {
+            return false;
+        }
+        let name = if self
+            .views
+            .iter()
+            .any(|view| view.id != id && view.name == name)
+        {
+            self.unique_name(name)
+        } else {
+            name.to_string()
+        };
+        match self.views.iter_mut().find(|view| view.id == id) {
+            Some(view) => {
+                view.name = name;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Copies a view right after the original and returns the copy's id. The copy is named "… 副本" in the
+    /// language of `i18n`.
+    pub fn duplicate(&mut self, id: &str, i18n: I18n) -> Option<String> {
+        let index = self.views.iter().position(|view| view.id == id)?;
+        let original = self.views[index].clone();
+        let copy = TableView {
+            id: self.next_id(),
+            name: self
+                .unique_name(&i18n.t_with("table_views.copy_name", &[("name", &original.name)])),
+            ..original
+        };
+        let copy_id = copy.id.clone();
+        self.views.insert(index + 1, copy);
+        Some(copy_id)
+    }
+
+    /// Deletes a view; deleting the default view leaves the table without one.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.views.len();
+        self.views.retain(|view| view.id != id);
+        if self.default_view.as_deref() == Some(id) {
+            self.default_view = None;
+        }
+        self.views.len() != before
+    }
+
+    /// Makes `id` the default view, or clears the default with `None`.
+    pub fn set_default(&mut self, id: Option<&str>) {
+        self.default_view = id.filter(|id| self.get(id).is_some()).map(str::to_string);
+    }
+
+    fn next_id(&self) -> String {
+        let last = self
+            .views
+            .iter()
+            .filter_map(|view| view.id.strip_prefix("view-")?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("view-{}", last + 1)
+    }
+
+    fn unique_name(&self, name: &str) -> String {
+        let taken = |candidate: &str| self.views.iter().any(|view| view.name == candidate);
+        if !taken(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|number| format!("{name} {number}"))
+            .find(|candidate| !taken(candidate))
+            .unwrap_or_default()
+    }
+}
+
+/// What the name field is for.
+#[derive(Clone, Copy, PartialEq)]
+enum Naming {
+    SaveAs,
+    Rename,
+}
+
+/// Picks, saves, renames, duplicates and deletes the saved views of a table. The switcher keeps no copy of the
+/// views: every change is reported through `on_views_change` so the page can store it, and picking or creating a
+/// view reports it through `on_apply`.
+#[component]
+pub fn TableViewSwitcher<S: Clone + PartialEq + 'static>(
+    views: TableViews<S>,
+    /// Id of the view the table currently shows.
+    #[props(into, default)]
+    active: Option<String>,
+    /// The table's current layout, captured by "保存" and "另存为".
+    current_layout: TableLayout,
+    /// The page state captured next to the layout.
+    current_state: S,
+    on_views_change: EventHandler<TableViews<S>>,
+    on_apply: EventHandler<TableView<S>>,
+) -> Element {
+    let i18n = use_i18n();
+    let mut naming = use_signal(|| None::<Naming>);
+    let mut name = use_signal(String::new);
+
+    let active_view = active.as_deref().and_then(|id| views.get(id)).cloned();
+    let modified = active_view
+        .as_ref()
+        .is_some_and(|view| view.layout != current_layout || view.state != current_state);
+    let is_default = active_view
+        .as_ref()
+        .is_some_and(|view| views.default_view.as_deref() == Some(view.id.as_str()));
+
+    let options = views
+        .views
+        .iter()
+        .map(|view| {
+            let label = if views.default_view.as_deref() == Some(view.id.as_str()) {
+                i18n.t_with("table_views.default_name", &[("name", &view.name)])
+            } else {
+                view.name.clone()
+            };
+            SelectOption::new(label, view.id.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let mut items = vec![DropdownMenuItem::new(
+        i18n.t("table_views.save_as"),
+        "save-as",
+    )];
+    if active_view.is_some() {
+        items.push(DropdownMenuItem::new(
+            i18n.t("table_views.rename"),
+            "rename",
+        ));
+        items.push(DropdownMenuItem::new(
+            i18n.t("table_views.duplicate"),
+            "duplicate",
+        ));
+        items.push(if is_default {
+            DropdownMenuItem::new(i18n.t("table_views.clear_default"), "clear-default")
+        } else {
+            DropdownMenuItem::new(i18n.t("table_views.set_default"), "set-default")
+        });
+        items.push(DropdownMenuItem::new(i18n.t("table_views.delete"), "delete").destructive());
+    }
+
+    let on_action = {
+        let views = views.clone();
+        let active_view = active_view.clone();
+        move |action: String| {
+            let mut next = views.clone();
+            let active_id = active_view.as_ref().map(|view| view.id.clone());
+            match (action.as_str(), active_id) {
+                ("save-as", _) => {
+                    name.set(String::new());
+                    naming.set(Some(Naming::SaveAs));
+                }
+                ("rename", Some(_)) => {
+                    name.set(
+                        active_view
+                            .as_ref()
+                            .map(|view| view.name.clone())
+                            .unwrap_or_default(),
+                    );
+                    naming.set(Some(Naming::Rename));
+                }
+                ("duplicate", Some(id)) => {
+                    if let Some(copy) = next.duplicate(&id, i18n) {
+                        let view = next.get(&copy).cloned();
+                        on_views_change.call(next);
+                        if let Some(view) = view {
+                            on_apply.call(view);
+                        }
+                    }
+                }
+                ("set-default", Some(id)) => {
+                    next.set_default(Some(&id));
+                    on_views_change.call(next);
+                }
+                ("clear-default", Some(_)) => {
+                    next.set_default(None);
+                    on_views_change.call(next);
+                }
+                ("delete", Some(id)) if next.remove(&id) => on_views_change.call(next),
+                _ => {}
+            }
+        }
+    };
+
+    let save = {
+        let views = views.clone();
+        let active = active.clone();
+        let layout = current_layout.clone();
+        let state = current_state.clone();
+        move |_| {
+            let Some(id) = active.as_deref() else {
+                return;
+            };
+            let mut next = views.clone();
+            if next.update(id, layout.clone(), state.clone()) {
+                on_views_change.call(next);
+            }
+        }
+    };
+
+    let confirm_name = {
+        let views = views.clone();
+        let active = active.clone();
+        move |_| {
+            let mut next = views.clone();
+            match (naming(), active.as_deref()) {
+                (Some(Naming::SaveAs), _) => {
+                    let id = next.add(&name(), current_layout.clone(), current_state.clone(), i18n);
+                    let view = next.get(&id).cloned();
+                    on_views_change.call(next);
+                    if let Some(view) = view {
+                        on_apply.call(view);
+                    }
+                }
+                (Some(Naming::Rename), Some(id)) => {
+                    if !next.rename(id, &name()) {
+                        return;
+                    }
+                    on_views_change.call(next);
+                }
+                _ => {}
+            }
+            naming.set(None);
+        }
+    };
+
+    rsx! {
+        div {
+            class: "ui-table-views",
+            span { class: "ui-table-views-label", {i18n.t("table_views.label")} }
+            Select {
+                placeholder: i18n.t("table_views.unsaved"),
+                options,
+                selected: active_view.as_ref().map(|view| view.id.clone()),
+                on_change: {
+                    let views = views.clone();
+                    move |id: String| {
+                        if let Some(view) = views.get(&id) {
+                            on_apply.call(view.clone());
+                        }
+                    }
+                },
+            }
+            if modified {
+                Badge { variant: BadgeVariant::Outline, {i18n.t("table_views.modified")} }
+            }
+            Button {
+                variant: ButtonVariant::Outline,
+                size: ButtonSize::Sm,
+                disabled: !modified,
+                on_click: save,
+                {i18n.t("table_views.save")}
+            }
+            DropdownMenu {
+                label: i18n.t("table_views.more"),
+                items,
+                on_select: on_action,
+            }
+            if let Some(mode) = naming() {
+                div {
+                    class: "ui-table-views-naming",
+                    Input {
+                        value: name(),
+                        autofocus: true,
+                        placeholder: if mode == Naming::SaveAs { i18n.t("table_views.new_name") } else { i18n.t("table_views.name") },
+                        on_input: move |event: FormEvent| name.set(event.value()),
+                    }
+                    Button {
+                        size: ButtonSize::Sm,
+                        disabled: mode == Naming::Rename && name().trim().is_empty(),
+                        on_click: confirm_name,
+                        {i18n.t("table_views.confirm")}
+                    }
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Sm,
+                        on_click: move |_| naming.set(None),
+                        {i18n.t("table_views.cancel")}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Locale;
+
+    const ZH: I18n = I18n::new(Locale::ZhCn);
+
+    fn views() -> TableViews<u32> {
+        let mut views = TableViews::default();
+        views.add("待发货", TableLayout::default(), 1, ZH);
+        views.add("逾期", TableLayout::default(), 2, ZH);
+        views
+    }
+
+    fn names(views: &TableViews<u32>) -> Vec<&str> {
+        views.views.iter().map(|view| view.name.as_str()).collect()
+    }
+
+    #[test]
+    fn new_and_copied_views_get_unique_names_and_ids() {
+        let mut views = views();
+        let copy = views.duplicate("view-1", ZH).unwrap();
+        let second_copy = views.duplicate("view-1", ZH).unwrap();
+        let unnamed = views.add("  ", TableLayout::default(), 3, ZH);
+        views.add("逾期", TableLayout::default(), 4, ZH);
+
+        assert_eq!(
+            names(&views),
+            [
+                "待发货",
+                "待发货 副本 2",
+                "待发货 副本",
+                "逾期",
+                "视图",
+                "逾期 2"
+            ]
+        );
+        assert_eq!((copy.as_str(), second_copy.as_str()), ("view-3", "view-4"));
+        assert_eq!(unnamed, "view-5");
+        assert_eq!(views.get(&copy).unwrap().state, 1);
+    }
+
+    #[test]
+    fn deleting_the_default_view_clears_the_default() {
+        let mut views = views();
+        views.set_default(Some("view-2"));
+        assert_eq!(views.default_view().map(|view| view.state), Some(2));
+
+        views.set_default(Some("view-9"));
+        assert_eq!(views.default_view, None);
+
+        views.set_default(Some("view-2"));
+        assert!(!views.rename("view-2", " "));
+        assert!(views.rename("view-2", "待发货"));
+        assert!(views.remove("view-2"));
+        assert_eq!(views.default_view, None);
+        assert_eq!(names(&views), ["待发货"]);
+        assert!(!views.remove("view-2"));
+    }
+}
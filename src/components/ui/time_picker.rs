@@ -0,0 +1,296 @@
+use super::utils::merge_class;
+use crate::i18n::use_i18n;
+use crate::time::{HourCycle, Locale, NaiveTime};
+use dioxus::prelude::*;
+
+/// The times a picker offers: every `minute_step` minutes (and `second_step` seconds when seconds are shown)
+/// between `min` and `max`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct TimeGrid {
+    pub minute_step: u32,
+    pub second_step: u32,
+    pub show_seconds: bool,
+    pub min: Option<NaiveTime>,
+    pub max: Option<NaiveTime>,
+}
+
+impl TimeGrid {
+    pub fn minutes(self) -> impl Iterator<Item = u32> {
+        (0..60).step_by(self.minute_step.max(1) as usize)
+    }
+
+    pub fn seconds(self) -> impl Iterator<Item = u32> {
+        let step = if self.show_seconds {
+            self.second_step.max(1)
+        } else {
+            60
+        };
+        (0..60).step_by(step as usize)
+    }
+
+    /// Whether any moment from `start` to `end` lies between `min` and `max`.
+    fn overlaps(self, start: NaiveTime, end: NaiveTime) -> bool {
+        self.min.is_none_or(|min| end >= min) && self.max.is_none_or(|max| start <= max)
+    }
+
+    fn times(self) -> impl Iterator<Item = NaiveTime> {
+        (0..24).flat_map(move |hour| {
+            self.minutes().flat_map(move |minute| {
+                self.seconds()
+                    .filter_map(move |second| NaiveTime::from_hms_opt(hour, minute, second))
+            })
+        })
+    }
+
+    /// The offered time closest to `time`: `time` itself when it is offered, otherwise the next one, or the
+    /// last one before it when nothing later is allowed. `None` when `min` and `max` leave nothing to offer.
+    pub fn snap(self, time: NaiveTime) -> Option<NaiveTime> {
+        let allowed = |candidate: &NaiveTime| self.overlaps(*candidate, *candidate);
+        self.times()
+            .filter(allowed)
+            .find(|candidate| *candidate >= time)
+            .or_else(|| self.times().filter(allowed).last())
+    }
+
+    fn hour_enabled(self, hour: u32) -> bool {
+        match (
+            NaiveTime::from_hms_opt(hour, 0, 0),
+            NaiveTime::from_hms_milli_opt(hour, 59, 59, 999),
+        ) {
+            (Some(start), Some(end)) => self.overlaps(start, end),
+            _ => false,
+        }
+    }
+
+    fn minute_enabled(self, hour: u32, minute: u32) -> bool {
+        match (
+            NaiveTime::from_hms_opt(hour, minute, 0),
+            NaiveTime::from_hms_milli_opt(hour, minute, 59, 999),
+        ) {
+            (Some(start), Some(end)) => self.overlaps(start, end),
+            _ => false,
+        }
+    }
+
+    fn second_enabled(self, hour: u32, minute: u32, second: u32) -> bool {
+        NaiveTime::from_hms_opt(hour, minute, second).is_some_and(|time| self.overlaps(time, time))
+    }
+
+    fn period_enabled(self, pm: bool) -> bool {
+        let hours = if pm { 12..24 } else { 0..12 };
+        hours.into_iter().any(|hour| self.hour_enabled(hour))
+    }
+}
+
+/// One column of a [`TimePicker`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TimePart {
+    Hour(u32),
+    Minute(u32),
+    Second(u32),
+    Period(bool),
+}
+
+/// `base` with one part replaced, snapped onto the grid.
+fn pick(base: NaiveTime, part: TimePart, grid: TimeGrid) -> Option<NaiveTime> {
+    let (hour, minute, second) = (base.hour(), base.minute(), base.second());
+    let time = match part {
+        TimePart::Hour(hour) => NaiveTime::from_hms_opt(hour, minute, second),
+        TimePart::Minute(minute) => NaiveTime::from_hms_opt(hour, minute, second),
+        TimePart::Second(second) => NaiveTime::from_hms_opt(hour, minute, second),
+        TimePart::Period(pm) => NaiveTime::from_hms_opt(hour % 12 + 12 * pm as u32, minute, second),
+    }?;
+    grid.snap(time)
+}
+
+/// Hour, minute and optional second columns for picking a time of day. The value is controlled through `value`;
+/// picking a part keeps the others and moves onto the nearest time the steps and `min` / `max` allow.
+#[component]
+pub fn TimePicker(
+    mut value: Signal<Option<NaiveTime>>,
+    #[props(optional)] on_change: Option<EventHandler<NaiveTime>>,
+    #[props(default)] show_seconds: bool,
+    /// Defaults to the locale's, the 24-hour clock for `zh-CN`.
+    #[props(optional)]
+    hour_cycle: Option<HourCycle>,
+    #[props(default = 1)] minute_step: u32,
+    #[props(default = 1)] second_step: u32,
+    #[props(optional)] min: Option<NaiveTime>,
+    #[props(optional)] max: Option<NaiveTime>,
+    /// Day period names, the app's language by default.
+    #[props(optional)]
+    locale: Option<Locale>,
+    #[props(into, default)] class: Option<String>,
+) -> Element {
+    let i18n = use_i18n();
+    let locale = locale.unwrap_or(i18n.locale());
+    let hour_cycle = hour_cycle.unwrap_or(locale.hour_cycle());
+    let grid = TimeGrid {
+        minute_step,
+        second_step,
+        show_seconds,
+        min,
+        max,
+    };
+
+    let current = value();
+    let base = current
+        .or_else(|| grid.snap(NaiveTime::MIDNIGHT))
+        .unwrap_or(NaiveTime::MIDNIGHT);
+    let (pm, _) = base.hour12();
+    let hours: Vec<u32> = match hour_cycle {
+        HourCycle::H24 => (0..24).collect(),
+        HourCycle::H12 if pm => (12..24).collect(),
+        HourCycle::H12 => (0..12).collect(),
+    };
+    let hour_label = move |hour: u32| match hour_cycle {
+        HourCycle::H24 => format!("{hour:02}"),
+        HourCycle::H12 => format!("{:02}", (hour + 11) % 12 + 1),
+    };
+
+    let mut select = move |part: TimePart| {
+        let Some(time) = pick(base, part, grid) else {
+            return;
+        };
+        value.set(Some(time));
+        if let Some(handler) = on_change {
+            handler.call(time);
+        }
+    };
+    let state = move |selected: bool| if selected { "selected" } else { "idle" };
+
+    rsx! {
+        div {
+            class: merge_class("ui-time-picker", class),
+            div {
+                class: "ui-time-picker-column",
+                role: "listbox",
+                "aria-label": i18n.t("time_picker.hour"),
+                for hour in hours {
+                    button {
+                        key: "{hour}",
+                        class: "ui-time-picker-option",
+                        r#type: "button",
+                        "data-state": state(current.is_some_and(|time| time.hour() == hour)),
+                        disabled: !grid.hour_enabled(hour),
+                        onclick: move |_| select(TimePart::Hour(hour)),
+                        {hour_label(hour)}
+                    }
+                }
+            }
+            div {
+                class: "ui-time-picker-column",
+                role: "listbox",
+                "aria-label": i18n.t("time_picker.minute"),
+                for minute in grid.minutes() {
+                    button {
+                        key: "{minute}",
+                        class: "ui-time-picker-option",
+                        r#type: "button",
+                        "data-state": state(current.is_some_and(|time| time.minute() == minute)),
+                        disabled: !grid.minute_enabled(base.hour(), minute),
+                        onclick: move |_| select(TimePart::Minute(minute)),
+                        "{minute:02}"
+                    }
+                }
+            }
+            if show_seconds {
+                div {
+                    class: "ui-time-picker-column",
+                    role: "listbox",
+                    "aria-label": i18n.t("time_picker.second"),
+                    for second in grid.seconds() {
+                        button {
+                            key: "{second}",
+                            class: "ui-time-picker-option",
+                            r#type: "button",
+                            "data-state": state(current.is_some_and(|time| time.second() == second)),
+                            disabled: !grid.second_enabled(base.hour(), base.minute(), second),
+                            onclick: move |_| select(TimePart::Second(second)),
+                            "{second:02}"
+                        }
+                    }
+                }
+            }
+            if hour_cycle == HourCycle::H12 {
+                div {
+                    class: "ui-time-picker-column",
+                    role: "listbox",
+                    "aria-label": i18n.t("time_picker.period"),
+                    for period in [false, true] {
+                        button {
+                            key: "{period}",
+                            class: "ui-time-picker-option",
+                            r#type: "button",
+                            "data-state": state(current.is_some_and(|time| time.hour12().0 == period)),
+                            disabled: !grid.period_enabled(period),
+                            onclick: move |_| select(TimePart::Period(period)),
+                            {locale.day_period(period)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32, second: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn picks_snap_onto_the_steps_within_bounds() {
+        let grid = TimeGrid {
+            minute_step: 15,
+            second_step: 1,
+            show_seconds: false,
+            min: Some(time(9, 10, 0)),
+            max: Some(time(17, 30, 0)),
+        };
+        assert_eq!(grid.minutes().collect::<Vec<_>>(), vec![0, 15, 30, 45]);
+        assert_eq!(grid.seconds().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(grid.snap(time(10, 7, 30)), Some(time(10, 15, 0)));
+        assert_eq!(grid.snap(time(6, 0, 0)), Some(time(9, 15, 0)));
+        assert_eq!(grid.snap(time(23, 0, 0)), Some(time(17, 30, 0)));
+
+        let base = time(10, 45, 0);
+        // 17:45 is past `max`, so the pick settles on the last time allowed.
+        assert_eq!(pick(base, TimePart::Hour(17), grid), Some(time(17, 30, 0)));
+        assert_eq!(pick(base, TimePart::Minute(0), grid), Some(time(10, 0, 0)));
+        assert_eq!(
+            pick(base, TimePart::Period(true), grid),
+            Some(time(17, 30, 0))
+        );
+
+        assert!(grid.hour_enabled(9) && !grid.hour_enabled(8) && !grid.hour_enabled(18));
+        assert!(!grid.minute_enabled(9, 0) && grid.minute_enabled(9, 15));
+        assert!(grid.period_enabled(false) && grid.period_enabled(true));
+    }
+
+    #[test]
+    fn seconds_and_empty_ranges() {
+        let grid = TimeGrid {
+            minute_step: 1,
+            second_step: 20,
+            show_seconds: true,
+            min: None,
+            max: Some(time(0, 0, 30)),
+        };
+        assert_eq!(grid.seconds().collect::<Vec<_>>(), vec![0, 20, 40]);
+        assert_eq!(grid.snap(time(0, 0, 5)), Some(time(0, 0, 20)));
+        assert!(grid.second_enabled(0, 0, 20) && !grid.second_enabled(0, 0, 40));
+        assert!(!grid.period_enabled(true));
+
+        let empty = TimeGrid {
+            min: Some(time(12, 0, 1)),
+            max: Some(time(12, 0, 59)),
+            show_seconds: false,
+            ..grid
+        };
+        assert_eq!(empty.snap(time(12, 0, 0)), None);
+    }
+}
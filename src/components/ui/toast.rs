@@ -1,3 +1,4 @@
+use crate::i18n::use_i18n;
 use dioxus::prelude::*;
 
 #[component]
@@ -12,6 +13,7 @@ pub fn Toast(
     #[props(into, default)] description: Option<String>,
     #[props(optional)] on_close: Option<EventHandler<()>>,
 ) -> Element {
+    let i18n = use_i18n();
     if !open {
         return rsx! { Fragment {} };
     }
@@ -34,7 +36,7 @@ pub fn Toast(
                             cb.call(());
                         }
                     },
-                    "Dismiss"
+                    {i18n.t("toast.dismiss")}
                 }
             }
         }
@@ -1,3 +1,6 @@
+use crate::time::{NaiveDate, Tz};
+use dioxus::prelude::*;
+
 /// Merge a base CSS class with an optional extra class string.
 /// If the extra string is empty or only whitespace, returns just the base class.
 pub fn merge_class(base: &str, extra: Option<String>) -> String {
@@ -17,6 +20,15 @@ pub fn data_bool(value: bool) -> &'static str {
     }
 }
 
+/// Today in the business time zone. When the app shell provides the zone as a `Signal<Tz>` context, the caller
+/// re-renders when the zone is switched.
+pub fn use_today() -> NaiveDate {
+    match try_use_context::<Signal<Tz>>() {
+        Some(zone) => NaiveDate::today_in(zone()),
+        None => NaiveDate::today(),
+    }
+}
+
 /// Trait for types that can be converted to a static string representation.
 /// Useful for variant enums that need to be used as CSS classes or data attributes.
 pub trait AsStaticStr {
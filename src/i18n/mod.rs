@@ -0,0 +1,256 @@
+//! UI strings by key, in every language the app ships in.
+//!
+//! Each language has a catalog of `key = text` lines, embedded at compile time from `zh-CN.txt` and `en-US.txt`
+//! next to this file. Texts can refer to arguments as `{name}` (`{{` and `}}` stand for literal braces). Texts
+//! that depend on a count come as `key.one` / `key.other` variants picked by the language's plural rules, and
+//! get the count as `{count}`. A key missing from a catalog falls back to the default language and then to the
+//! key itself, so a forgotten translation shows up on screen instead of an empty string.
+//!
+//! Components call [`use_i18n`] to translate in the language the app shell provides as a `Signal<Locale>`
+//! context, and re-render when it is switched.
+
+use crate::time::Locale;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::sync::OnceLock;
+
+const ZH_CN: &str = include_str!("zh-CN.txt");
+const EN_US: &str = include_str!("en-US.txt");
+
+type Catalog = HashMap<&'static str, &'static str>;
+
+/// Arguments substituted into a text, by name.
+pub type Args<'a> = [(&'a str, &'a dyn Display)];
+
+/// Translates keys into one language.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct I18n {
+    locale: Locale,
+}
+
+impl I18n {
+    pub const fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    pub fn locale(self) -> Locale {
+        self.locale
+    }
+
+    pub fn t(self, key: &str) -> String {
+        self.t_with(key, &[])
+    }
+
+    /// The text for `key` with its `{name}` placeholders filled in from `args`.
+    pub fn t_with(self, key: &str, args: &Args<'_>) -> String {
+        interpolate(self.lookup(key).unwrap_or(key), args)
+    }
+
+    /// The `key.one` / `key.other` variant for `count`, with `{count}` and `args` filled in.
+    pub fn t_count(self, key: &str, count: usize, args: &Args<'_>) -> String {
+        let variant = format!("{key}.{}", plural_category(self.locale, count));
+        let text = self
+            .lookup(&variant)
+            .or_else(|| self.lookup(&format!("{key}.other")))
+            .unwrap_or(key);
+        let mut all: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+        all.extend_from_slice(args);
+        interpolate(text, &all)
+    }
+
+    /// `items` run together with the language's list separator (`、` or `, `).
+    pub fn list<S: AsRef<str>>(self, items: &[S]) -> String {
+        let mut items = items.iter().map(AsRef::as_ref);
+        let first = items.next().unwrap_or_default().to_string();
+        items.fold(first, |head, item| {
+            self.t_with("list", &[("head", &head), ("item", &item)])
+        })
+    }
+
+    fn lookup(self, key: &str) -> Option<&'static str> {
+        catalog(self.locale)
+            .get(key)
+            .or_else(|| catalog(Locale::default()).get(key))
+            .copied()
+    }
+}
+
+/// Translations in the language the app shell provides, [`Locale::default`] outside of it. The caller
+/// re-renders when the language is switched.
+pub fn use_i18n() -> I18n {
+    match try_use_context::<Signal<Locale>>() {
+        Some(locale) => I18n::new(locale()),
+        None => I18n::default(),
+    }
+}
+
+/// CLDR plural category of `count`: Chinese has no plural forms, English tells one from the rest.
+fn plural_category(locale: Locale, count: usize) -> &'static str {
+    match locale {
+        Locale::ZhCn => "other",
+        Locale::EnUs if count == 1 => "one",
+        Locale::EnUs => "other",
+    }
+}
+
+fn catalog(locale: Locale) -> &'static Catalog {
+    static ZH: OnceLock<Catalog> = OnceLock::new();
+    static EN: OnceLock<Catalog> = OnceLock::new();
+    let (cell, source) = match locale {
+        Locale::ZhCn => (&ZH, ZH_CN),
+        Locale::EnUs => (&EN, EN_US),
+    };
+    cell.get_or_init(|| {
+        parse_catalog(source).unwrap_or_else(|err| panic!("{} catalog: {err}", locale.key()))
+    })
+}
+
+/// Reads `key = text` lines; blank lines and lines starting with `#` are skipped.
+fn parse_catalog(source: &'static str) -> Result<Catalog, String> {
+    let mut entries = Catalog::new();
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, text)) = line.split_once('=') else {
+            return Err(format!("line {}: expected `key = text`", index + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("line {}: invalid key '{key}'", index + 1));
+        }
+        if entries.insert(key, text.trim()).is_some() {
+            return Err(format!("line {}: duplicate key '{key}'", index + 1));
+        }
+    }
+    Ok(entries)
+}
+
+/// Replaces `{name}` with the argument of that name. Placeholders without an argument are kept as they are.
+fn interpolate(text: &str, args: &Args<'_>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}']) {
+        out.push_str(&rest[..index]);
+        let tail = &rest[index..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let placeholder = tail
+            .strip_prefix('{')
+            .and_then(|inner| inner.split_once('}'))
+            .and_then(|(name, after)| {
+                let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+                Some((*value, after))
+            });
+        match placeholder {
+            Some((value, after)) => {
+                let _ = write!(out, "{value}");
+                rest = after;
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Keys with their plural suffix removed.
+    fn base_keys(catalog: &Catalog) -> BTreeSet<&'static str> {
+        catalog
+            .keys()
+            .map(|key| {
+                key.strip_suffix(".one")
+                    .or_else(|| key.strip_suffix(".other"))
+                    .unwrap_or(key)
+            })
+            .collect()
+    }
+
+    fn placeholders(text: &str) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        let mut rest = text.replace("{{", "").replace("}}", "");
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            names.insert(rest[start + 1..start + end].to_string());
+            rest = rest[start + end + 1..].to_string();
+        }
+        names
+    }
+
+    #[test]
+    fn catalogs_translate_the_same_keys_with_the_same_arguments() {
+        let zh = parse_catalog(ZH_CN).unwrap();
+        let en = parse_catalog(EN_US).unwrap();
+        assert_eq!(base_keys(&zh), base_keys(&en));
+
+        for (key, text) in &en {
+            let base = key
+                .strip_suffix(".one")
+                .or_else(|| key.strip_suffix(".other"))
+                .unwrap_or(key);
+            let other = zh
+                .get(key)
+                .or_else(|| zh.get(format!("{base}.other").as_str()))
+                .unwrap_or_else(|| panic!("{key} missing from zh-CN"));
+            // English `.one` texts often spell out the count instead of using it.
+            if !key.ends_with(".one") {
+                assert_eq!(placeholders(text), placeholders(other), "{key}");
+            }
+        }
+        for key in en.keys().filter(|key| key.ends_with(".one")) {
+            let other = format!("{}.other", key.strip_suffix(".one").unwrap());
+            assert!(en.contains_key(other.as_str()), "{other} missing");
+        }
+    }
+
+    #[test]
+    fn catalog_lines_are_validated() {
+        assert_eq!(
+            parse_catalog("# heading\n\na = b = c\n").unwrap().get("a"),
+            Some(&"b = c")
+        );
+        assert_eq!(
+            parse_catalog("a = 1\nb\n"),
+            Err("line 2: expected `key = text`".to_string())
+        );
+        assert_eq!(
+            parse_catalog("a = 1\na = 2"),
+            Err("line 2: duplicate key 'a'".to_string())
+        );
+    }
+
+    #[test]
+    fn arguments_and_plurals_are_filled_in() {
+        assert_eq!(
+            interpolate("{{{name}}} {missing} {", &[("name", &"x")]),
+            "{x} {missing} {"
+        );
+
+        let en = I18n::new(Locale::EnUs);
+        let zh = I18n::new(Locale::ZhCn);
+        assert_eq!(en.t("dialog.close"), "Close");
+        assert_eq!(zh.t("dialog.close"), "关闭");
+        assert_eq!(en.t_count("table.selected", 1, &[]), "1 row selected");
+        assert_eq!(en.t_count("table.selected", 3, &[]), "3 rows selected");
+        assert_eq!(zh.t_count("table.selected", 3, &[]), "已选择 3 行");
+        assert_eq!(en.list(&["a", "b", "c"]), "a, b, c");
+        assert_eq!(zh.list(&["a", "b"]), "a、b");
+        assert_eq!(zh.list::<&str>(&[]), "");
+        assert_eq!(en.t("no.such.key"), "no.such.key");
+    }
+}
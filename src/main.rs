@@ -2,8 +2,12 @@
 // need dioxus
 use dioxus::prelude::*;
 
+mod i18n;
+mod orders;
+mod platform;
 mod time;
-use views::{Components, Home, Navbar, Orders};
+use orders::query::OrderQuery;
+use views::{Components, Home, Navbar, OrderDetail, Orders};
 
 /// Define a components module that contains all shared components for our app.
 mod components;
@@ -28,8 +32,11 @@ enum Route {
         #[route("/components")]
         // The components gallery reuses the full UI showcase so new primitives stay discoverable.
         Components {},
-        #[route("/orders")]
-        Orders {},
+        // Filters, sort and page of the order list live in the query string, see `orders::query_string`.
+        #[route("/orders?:..query")]
+        Orders { query: OrderQuery },
+        #[route("/orders/:number")]
+        OrderDetail { number: String },
 }
 
 // We can import assets in dioxus with the `asset!` macro. This macro takes a path to an asset relative to the crate root.
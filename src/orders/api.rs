@@ -0,0 +1,207 @@
+//! Fullstack server functions exposing the order repository. On the client these compile to HTTP calls; on the
+//! server they run against the repository selected by [`repository`].
+
+use super::bulk::{BulkAction, BulkOutcome};
+use super::edit::OrderEdit;
+use super::import::{ColumnMapping, CsvTable, ImportOutcome};
+use super::query::{OrderPage, OrderQuery};
+use super::transition::OrderAction;
+use super::Order;
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use crate::time::NaiveDate;
+use crate::time::{Locale, Tz};
+
+#[cfg(feature = "server")]
+use super::repository::OrderRepository;
+
+/// Name of the environment variable pointing at the SQLite database file. When it is not set the server keeps
+/// the seeded demo orders in memory.
+#[cfg(feature = "server")]
+pub const DATABASE_ENV: &str = "ORDERS_DATABASE";
+
+/// Author recorded on notes added through [`add_order_note`]. The template has no sign-in, so every note is
+/// attributed to the same operator.
+#[cfg(feature = "server")]
+pub const NOTE_AUTHOR: &str = "管理员";
+
+/// The process-wide order repository, opened lazily on first use. When the database cannot be opened or seeded
+/// every call reports why, instead of taking the server down.
+#[cfg(feature = "server")]
+pub fn repository() -> Result<&'static dyn OrderRepository, ServerFnError> {
+    use super::repository::{InMemoryOrderRepository, RepositoryError};
+    use super::sqlite::SqliteOrderRepository;
+    use std::sync::OnceLock;
+
+    static REPOSITORY: OnceLock<Result<Box<dyn OrderRepository>, RepositoryError>> =
+        OnceLock::new();
+    REPOSITORY
+        .get_or_init(|| match std::env::var(DATABASE_ENV) {
+            Ok(path) => {
+                let repository = SqliteOrderRepository::open(&path)?;
+                repository.seed_if_empty()?;
+                Ok(Box::new(repository))
+            }
+            Err(_) => Ok(Box::new(InMemoryOrderRepository::seeded())),
+        })
+        .as_ref()
+        .map(|repository| repository.as_ref())
+        .map_err(ServerFnError::new)
+}
+
+/// The bulk updates [`restore_orders`] can undo.
+#[cfg(feature = "server")]
+fn undo_journal() -> &'static std::sync::Mutex<super::bulk::BulkUndoJournal> {
+    use std::sync::{Mutex, OnceLock};
+
+    static JOURNAL: OnceLock<Mutex<super::bulk::BulkUndoJournal>> = OnceLock::new();
+    JOURNAL.get_or_init(Mutex::default)
+}
+
+#[server]
+pub async fn query_orders(query: OrderQuery) -> Result<OrderPage, ServerFnError> {
+    repository()?.query(&query).map_err(ServerFnError::new)
+}
+
+#[server]
+pub async fn get_order(number: String) -> Result<Option<Order>, ServerFnError> {
+    repository()?.find(&number).map_err(ServerFnError::new)
+}
+
+/// Replaces the order's tags, dropping blanks and duplicates, and returns the updated order.
+#[server]
+pub async fn set_order_tags(number: String, tags: Vec<String>) -> Result<Order, ServerFnError> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !cleaned.iter().any(|existing| existing == tag) {
+            cleaned.push(tag.to_string());
+        }
+    }
+    repository()?
+        .update(&number, &mut |order| {
+            order.tags = cleaned.clone();
+            Ok(())
+        })
+        .map_err(ServerFnError::new)
+}
+
+/// Changes a single field of the order and returns the updated order. Dates are checked against today in the
+/// caller's business `zone`.
+#[server]
+pub async fn edit_order(number: String, edit: OrderEdit, zone: Tz) -> Result<Order, ServerFnError> {
+    edit.validate(NaiveDate::today_in(zone))
+        .map_err(ServerFnError::new)?;
+    repository()?
+        .update(&number, &mut |order| {
+            edit.apply(order);
+            Ok(())
+        })
+        .map_err(ServerFnError::new)
+}
+
+/// Appends an internal note dated today in the caller's business `zone` and returns the updated order. Errors
+/// are worded in the caller's `locale`.
+#[server]
+pub async fn add_order_note(
+    number: String,
+    body: String,
+    zone: Tz,
+    locale: Locale,
+) -> Result<Order, ServerFnError> {
+    use super::OrderNote;
+
+    let body = body.trim().to_string();
+    if body.is_empty() {
+        let i18n = crate::i18n::I18n::new(locale);
+        return Err(ServerFnError::new(i18n.t("orders.detail.note_blank")));
+    }
+    let created_on = NaiveDate::today_in(zone);
+    repository()?
+        .update(&number, &mut |order| {
+            order.notes.push(OrderNote {
+                author: NOTE_AUTHOR.to_string(),
+                body: body.clone(),
+                created_on,
+            });
+            Ok(())
+        })
+        .map_err(ServerFnError::new)
+}
+
+/// Performs a status action, rejecting it when it is not a legal transition for the stored order, and returns the
+/// updated order. The status change is dated today in the caller's business `zone`.
+#[server]
+pub async fn apply_order_action(
+    number: String,
+    action: OrderAction,
+    zone: Tz,
+) -> Result<Order, ServerFnError> {
+    let on = NaiveDate::today_in(zone);
+    repository()?
+        .update(&number, &mut |order| Ok(order.apply(action, on)?))
+        .map_err(ServerFnError::new)
+}
+
+/// Applies `action` to each order independently and reports the result per order. Status changes are dated
+/// today in the caller's business `zone`.
+#[server]
+pub async fn bulk_update_orders(
+    numbers: Vec<String>,
+    action: BulkAction,
+    zone: Tz,
+) -> Result<BulkOutcome, ServerFnError> {
+    let (mut outcome, changes) = action.run(repository()?, &numbers, NaiveDate::today_in(zone));
+    outcome.undo_token = undo_journal()
+        .lock()
+        .map_err(|_| ServerFnError::new("bulk undo journal poisoned"))?
+        .record(changes, std::time::Instant::now());
+    Ok(outcome)
+}
+
+/// Undoes the bulk update behind `token`, putting back only the fields it changed. `None` once the update can no
+/// longer be undone: it was undone already, [`UNDO_WINDOW_MS`](super::bulk::UNDO_WINDOW_MS) has passed or too
+/// many updates came after it.
+#[server]
+pub async fn restore_orders(token: u64) -> Result<Option<BulkOutcome>, ServerFnError> {
+    let repository = repository()?;
+    let changes = undo_journal()
+        .lock()
+        .map_err(|_| ServerFnError::new("bulk undo journal poisoned"))?
+        .take(token, std::time::Instant::now());
+    Ok(changes.map(|changes| super::bulk::undo(repository, &changes)))
+}
+
+/// Every order matching `query`, in query order and without pagination.
+#[server]
+pub async fn export_orders(query: OrderQuery) -> Result<Vec<Order>, ServerFnError> {
+    repository()?.matching(&query).map_err(ServerFnError::new)
+}
+
+/// Validates the rows of an uploaded CSV file against `mapping` and saves the valid orders, skipping numbers that
+/// are already taken. The rows are validated here again rather than trusting the client's preview. Errors are
+/// worded in the caller's `locale`.
+#[server]
+pub async fn import_orders(
+    table: CsvTable,
+    mapping: ColumnMapping,
+    locale: Locale,
+) -> Result<ImportOutcome, ServerFnError> {
+    use super::import::{import, missing_fields, validate};
+
+    let missing = missing_fields(&mapping);
+    if !missing.is_empty() {
+        let i18n = crate::i18n::I18n::new(locale);
+        let fields: Vec<String> = missing.iter().map(|field| field.label_in(i18n)).collect();
+        return Err(ServerFnError::new(i18n.t_with(
+            "orders.import.unmapped",
+            &[("fields", &i18n.list(&fields))],
+        )));
+    }
+    let orders: Vec<Order> = validate(&table, &mapping)
+        .into_iter()
+        .filter_map(|row| row.result.ok())
+        .collect();
+    import(repository()?, &orders).map_err(ServerFnError::new)
+}
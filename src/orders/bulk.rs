@@ -0,0 +1,458 @@
+//! Changes applied to several orders at once. Every order is updated on its own, so one order that cannot take
+//! the change does not block the others. The server keeps what each action changed, the touched fields before
+//! and after, in a [`BulkUndoJournal`] and hands out a token to undo it with for [`UNDO_WINDOW_MS`]; an undo only
+//! puts back those fields, and skips orders that were changed again since. Cancelling cannot be undone.
+
+use super::transition::TransitionError;
+use crate::i18n::I18n;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "server", test))]
+use super::repository::{OrderRepository, RepositoryError};
+#[cfg(any(feature = "server", test))]
+use super::{FulfillmentStatus, Order, OrderStatus, PaymentStatus, StatusChange};
+#[cfg(any(feature = "server", test))]
+use crate::time::NaiveDate;
+#[cfg(any(feature = "server", test))]
+use std::collections::VecDeque;
+#[cfg(any(feature = "server", test))]
+use std::time::{Duration, Instant};
+
+/// How long after a bulk update it can be undone. The server refuses older undo tokens.
+pub const UNDO_WINDOW_MS: u32 = 10_000;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BulkAction {
+    /// Walks each order through the remaining fulfillment steps until it is delivered.
+    MarkFulfilled,
+    AddTag(String),
+    RemoveTag(String),
+    SetFlagged(bool),
+    Cancel,
+}
+
+impl BulkAction {
+    /// The name shown in the UI, in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        match self {
+            BulkAction::MarkFulfilled => i18n.t("order.bulk.mark_fulfilled"),
+            BulkAction::AddTag(tag) => i18n.t_with("order.bulk.add_tag", &[("tag", tag)]),
+            BulkAction::RemoveTag(tag) => i18n.t_with("order.bulk.remove_tag", &[("tag", tag)]),
+            BulkAction::SetFlagged(true) => i18n.t("order.bulk.flag"),
+            BulkAction::SetFlagged(false) => i18n.t("order.bulk.unflag"),
+            BulkAction::Cancel => i18n.t("order.action.cancel"),
+        }
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, BulkAction::Cancel)
+    }
+
+    /// Cancelling refunds paid orders and ends them for good, which putting the old statuses back would hide, so
+    /// it cannot be undone.
+    pub fn is_undoable(&self) -> bool {
+        !matches!(self, BulkAction::Cancel)
+    }
+}
+
+/// Why a single order was left unchanged.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BulkFailure {
+    NotFound,
+    Transition(TransitionError),
+    Storage(String),
+    /// The order was changed again after the bulk update, so undoing it would overwrite that change.
+    ChangedSince,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BulkRowResult {
+    pub number: String,
+    pub failure: Option<BulkFailure>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BulkOutcome {
+    /// One entry per requested order, in request order.
+    pub rows: Vec<BulkRowResult>,
+    /// Undoes the change through `restore_orders`; `None` when no order was changed.
+    pub undo_token: Option<u64>,
+}
+
+impl BulkOutcome {
+    pub fn succeeded(&self) -> usize {
+        self.rows.iter().filter(|row| row.failure.is_none()).count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &BulkFailure)> {
+        self.rows
+            .iter()
+            .filter_map(|row| Some((row.number.as_str(), row.failure.as_ref()?)))
+    }
+}
+
+#[cfg(any(feature = "server", test))]
+impl BulkAction {
+    fn apply(&self, order: &mut Order, on: NaiveDate) -> Result<(), TransitionError> {
+        use super::transition::OrderAction;
+
+        match self {
+            BulkAction::MarkFulfilled => order.fulfill(on),
+            BulkAction::AddTag(tag) => {
+                if !order.tags.contains(tag) {
+                    order.tags.push(tag.clone());
+                }
+                Ok(())
+            }
+            BulkAction::RemoveTag(tag) => {
+                order.tags.retain(|existing| existing != tag);
+                Ok(())
+            }
+            BulkAction::SetFlagged(flagged) => {
+                order.flagged = *flagged;
+                Ok(())
+            }
+            BulkAction::Cancel => order.apply(OrderAction::Cancel, on),
+        }
+    }
+
+    /// The fields this action writes, as they are on `order`.
+    fn fields(&self, order: &Order) -> BulkFields {
+        match self {
+            BulkAction::MarkFulfilled | BulkAction::Cancel => BulkFields::statuses(order),
+            BulkAction::AddTag(_) | BulkAction::RemoveTag(_) => {
+                BulkFields::Tags(order.tags.clone())
+            }
+            BulkAction::SetFlagged(_) => BulkFields::Flagged(order.flagged),
+        }
+    }
+
+    /// Applies the action to every order in `numbers`, dating status changes `on`. Also returns what changed on
+    /// each order, for [`BulkUndoJournal::record`]; nothing when the action [cannot be undone](Self::is_undoable).
+    pub fn run(
+        &self,
+        repository: &dyn OrderRepository,
+        numbers: &[String],
+        on: NaiveDate,
+    ) -> (BulkOutcome, Vec<BulkChange>) {
+        let mut outcome = BulkOutcome::default();
+        let mut changes = Vec::new();
+        for number in numbers {
+            let mut before = None;
+            let result = repository.update(number, &mut |order| {
+                before = Some(self.fields(order));
+                Ok(self.apply(order, on)?)
+            });
+            let failure = match result {
+                Ok(order) => {
+                    let after = self.fields(&order);
+                    let before = before.take().filter(|_| self.is_undoable());
+                    changes.extend(before.filter(|before| *before != after).map(|before| {
+                        BulkChange {
+                            number: number.clone(),
+                            before,
+                            after,
+                        }
+                    }));
+                    None
+                }
+                Err(err) => Some(BulkFailure::from(err)),
+            };
+            outcome.rows.push(BulkRowResult {
+                number: number.clone(),
+                failure,
+            });
+        }
+        (outcome, changes)
+    }
+}
+
+#[cfg(any(feature = "server", test))]
+impl From<RepositoryError> for BulkFailure {
+    fn from(err: RepositoryError) -> Self {
+        match err {
+            RepositoryError::NotFound(_) => BulkFailure::NotFound,
+            RepositoryError::Transition(err) => BulkFailure::Transition(err),
+            RepositoryError::Storage(message) => BulkFailure::Storage(message),
+            RepositoryError::Conflict(_) => BulkFailure::ChangedSince,
+        }
+    }
+}
+
+/// The fields of an order one kind of bulk action writes.
+#[cfg(any(feature = "server", test))]
+#[derive(Clone, Debug, PartialEq)]
+enum BulkFields {
+    Statuses {
+        status: OrderStatus,
+        payment_status: PaymentStatus,
+        fulfillment_status: FulfillmentStatus,
+        payment_history: Vec<StatusChange<PaymentStatus>>,
+        fulfillment_history: Vec<StatusChange<FulfillmentStatus>>,
+    },
+    Tags(Vec<String>),
+    Flagged(bool),
+}
+
+#[cfg(any(feature = "server", test))]
+impl BulkFields {
+    fn statuses(order: &Order) -> Self {
+        BulkFields::Statuses {
+            status: order.status,
+            payment_status: order.payment_status,
+            fulfillment_status: order.fulfillment_status,
+            payment_history: order.payment_history.clone(),
+            fulfillment_history: order.fulfillment_history.clone(),
+        }
+    }
+
+    /// The same fields as `self`, as they are on `order`.
+    fn read_from(&self, order: &Order) -> Self {
+        match self {
+            BulkFields::Statuses { .. } => BulkFields::statuses(order),
+            BulkFields::Tags(_) => BulkFields::Tags(order.tags.clone()),
+            BulkFields::Flagged(_) => BulkFields::Flagged(order.flagged),
+        }
+    }
+
+    fn write_to(&self, order: &mut Order) {
+        match self.clone() {
+            BulkFields::Statuses {
+                status,
+                payment_status,
+                fulfillment_status,
+                payment_history,
+                fulfillment_history,
+            } => {
+                order.status = status;
+                order.payment_status = payment_status;
+                order.fulfillment_status = fulfillment_status;
+                order.payment_history = payment_history;
+                order.fulfillment_history = fulfillment_history;
+            }
+            BulkFields::Tags(tags) => order.tags = tags,
+            BulkFields::Flagged(flagged) => order.flagged = flagged,
+        }
+    }
+}
+
+/// What a bulk action changed on one order.
+#[cfg(any(feature = "server", test))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkChange {
+    number: String,
+    before: BulkFields,
+    after: BulkFields,
+}
+
+/// Puts back the fields `changes` record, order by order. Orders whose fields no longer are what the action left
+/// them at are skipped and reported as [`BulkFailure::ChangedSince`]; every other field stays as it is now.
+#[cfg(any(feature = "server", test))]
+pub fn undo(repository: &dyn OrderRepository, changes: &[BulkChange]) -> BulkOutcome {
+    let mut outcome = BulkOutcome::default();
+    for change in changes {
+        let result = repository.update(&change.number, &mut |order| {
+            if change.after.read_from(order) != change.after {
+                return Err(RepositoryError::Conflict(change.number.clone()));
+            }
+            change.before.write_to(order);
+            Ok(())
+        });
+        outcome.rows.push(BulkRowResult {
+            number: change.number.clone(),
+            failure: result.err().map(BulkFailure::from),
+        });
+    }
+    outcome
+}
+
+/// The most recent bulk updates, kept on the server so they can be undone by token. Older entries are dropped
+/// once [`BulkUndoJournal::CAPACITY`] is reached, an entry can be undone only once, and only within
+/// [`UNDO_WINDOW_MS`] of the update.
+#[cfg(any(feature = "server", test))]
+#[derive(Debug, Default)]
+pub struct BulkUndoJournal {
+    next_token: u64,
+    entries: VecDeque<(u64, Instant, Vec<BulkChange>)>,
+}
+
+#[cfg(any(feature = "server", test))]
+impl BulkUndoJournal {
+    pub const CAPACITY: usize = 32;
+
+    /// Keeps `changes`, made `at`, and returns the token to undo them with, `None` when there is nothing to undo.
+    pub fn record(&mut self, changes: Vec<BulkChange>, at: Instant) -> Option<u64> {
+        if changes.is_empty() {
+            return None;
+        }
+        self.next_token += 1;
+        if self.entries.len() == Self::CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((self.next_token, at, changes));
+        Some(self.next_token)
+    }
+
+    /// The changes behind `token`, unless they were taken already or the undo window had passed by `now`.
+    pub fn take(&mut self, token: u64, now: Instant) -> Option<Vec<BulkChange>> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(entry, _, _)| *entry == token)?;
+        let (_, at, changes) = self.entries.remove(index)?;
+        let window = Duration::from_millis(UNDO_WINDOW_MS.into());
+        (now.saturating_duration_since(at) <= window).then_some(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::repository::InMemoryOrderRepository;
+    use crate::orders::{FulfillmentStatus, OrderStatus, PaymentStatus};
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()
+    }
+
+    fn numbers(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_string()).collect()
+    }
+
+    #[test]
+    fn reports_partial_failures_per_row() {
+        let repository = InMemoryOrderRepository::seeded();
+        // DX-1050 is still picking, DX-1047 is already delivered and DX-0000 does not exist.
+        let (outcome, changes) = BulkAction::Cancel.run(
+            &repository,
+            &numbers(&["DX-1050", "DX-1047", "DX-0000"]),
+            today(),
+        );
+
+        assert_eq!(outcome.succeeded(), 1);
+        let failures: Vec<_> = outcome.failures().map(|(number, _)| number).collect();
+        assert_eq!(failures, ["DX-1047", "DX-0000"]);
+        assert!(matches!(
+            outcome.rows[1].failure,
+            Some(BulkFailure::Transition(_))
+        ));
+        assert_eq!(outcome.rows[2].failure, Some(BulkFailure::NotFound));
+        // The refund already went out, so the cancellation is not kept for undoing.
+        assert!(changes.is_empty());
+
+        let cancelled = repository.find("DX-1050").unwrap().unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert_eq!(cancelled.payment_status, PaymentStatus::Refunded);
+    }
+
+    #[test]
+    fn marks_fulfilled_through_remaining_steps() {
+        let repository = InMemoryOrderRepository::seeded();
+        let (outcome, _) =
+            BulkAction::MarkFulfilled.run(&repository, &numbers(&["DX-1041", "DX-1049"]), today());
+
+        assert_eq!(outcome.succeeded(), 1);
+        let fulfilled = repository.find("DX-1041").unwrap().unwrap();
+        assert_eq!(fulfilled.status, OrderStatus::Fulfilled);
+        assert_eq!(fulfilled.fulfillment_status, FulfillmentStatus::Delivered);
+        // Unpaid orders cannot start picking, so they stay untouched.
+        let unpaid = repository.find("DX-1049").unwrap().unwrap();
+        assert_eq!(unpaid.fulfillment_status, FulfillmentStatus::Unfulfilled);
+    }
+
+    #[test]
+    fn undo_reverts_only_the_touched_fields() {
+        let repository = InMemoryOrderRepository::seeded();
+        let targets = numbers(&["DX-1049", "DX-1048"]);
+        let before: Vec<_> = targets
+            .iter()
+            .map(|number| repository.find(number).unwrap().unwrap())
+            .collect();
+
+        let (outcome, changes) =
+            BulkAction::AddTag("VIP".to_string()).run(&repository, &targets, today());
+        assert_eq!(outcome.succeeded(), 2);
+        // Flagging the order in between is not part of the tag change, so the undo keeps it.
+        repository
+            .update("DX-1049", &mut |order| {
+                order.flagged = !order.flagged;
+                Ok(())
+            })
+            .unwrap();
+
+        let undone = undo(&repository, &changes);
+        assert_eq!(undone.succeeded(), 2);
+        let reverted = repository.find("DX-1049").unwrap().unwrap();
+        assert_eq!(reverted.tags, before[0].tags);
+        assert_eq!(reverted.flagged, !before[0].flagged);
+        assert_eq!(repository.find("DX-1048").unwrap().unwrap(), before[1]);
+    }
+
+    #[test]
+    fn undo_skips_orders_changed_since() {
+        let repository = InMemoryOrderRepository::seeded();
+        let targets = numbers(&["DX-1050", "DX-1041"]);
+        let (_, changes) = BulkAction::MarkFulfilled.run(&repository, &targets, today());
+        assert_eq!(changes.len(), 2);
+        // Someone reopens DX-1041 for a return after the bulk update.
+        repository
+            .update("DX-1041", &mut |order| {
+                order.fulfillment_status = FulfillmentStatus::Returned;
+                Ok(())
+            })
+            .unwrap();
+
+        let undone = undo(&repository, &changes);
+        assert_eq!(undone.rows[0].failure, None);
+        assert_eq!(undone.rows[1].failure, Some(BulkFailure::ChangedSince));
+        assert_ne!(
+            repository
+                .find("DX-1050")
+                .unwrap()
+                .unwrap()
+                .fulfillment_status,
+            FulfillmentStatus::Delivered
+        );
+        let kept = repository.find("DX-1041").unwrap().unwrap();
+        assert_eq!(
+            (kept.status, kept.fulfillment_status),
+            (OrderStatus::Fulfilled, FulfillmentStatus::Returned)
+        );
+    }
+
+    #[test]
+    fn journal_hands_out_tokens_once_and_within_the_window() {
+        let repository = InMemoryOrderRepository::seeded();
+        let mut journal = BulkUndoJournal::default();
+        let now = Instant::now();
+        // DX-1049 is flagged already, so flagging it changes nothing worth undoing.
+        let (_, unchanged) =
+            BulkAction::SetFlagged(true).run(&repository, &numbers(&["DX-1049"]), today());
+        assert_eq!(journal.record(unchanged, now), None);
+        let (_, changes) =
+            BulkAction::SetFlagged(false).run(&repository, &numbers(&["DX-1049"]), today());
+        let token = journal.record(changes.clone(), now).unwrap();
+        assert_eq!(journal.take(token, now), Some(changes));
+        assert_eq!(journal.take(token, now), None);
+
+        let flag = || {
+            vec![BulkChange {
+                number: "DX-1049".to_string(),
+                before: BulkFields::Flagged(false),
+                after: BulkFields::Flagged(true),
+            }]
+        };
+        for _ in 0..=BulkUndoJournal::CAPACITY {
+            journal.record(flag(), now);
+        }
+        assert_eq!(journal.take(token + 1, now), None);
+        assert!(journal.take(token + 2, now).is_some());
+
+        let late = journal.record(flag(), now).unwrap();
+        let window = Duration::from_millis(UNDO_WINDOW_MS.into());
+        assert_eq!(
+            journal.take(late, now + window + Duration::from_millis(1)),
+            None
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Single fields operators change in place from the orders table. Statuses are not among them: they only move
+//! through the actions in [`super::transition`].
+
+use super::SalesChannel;
+use crate::time::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(any(feature = "server", test))]
+use super::Order;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OrderEdit {
+    CustomerName(String),
+    PlacedOn(NaiveDate),
+    Channel(SalesChannel),
+    Flagged(bool),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EditError {
+    BlankCustomerName,
+    /// Orders cannot be placed after the given day.
+    FutureDate(NaiveDate),
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::BlankCustomerName => write!(f, "customer name must not be blank"),
+            EditError::FutureDate(today) => write!(
+                f,
+                "order date must not be after {}",
+                today.format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl OrderEdit {
+    pub fn validate(&self, today: NaiveDate) -> Result<(), EditError> {
+        match self {
+            OrderEdit::CustomerName(name) if name.trim().is_empty() => {
+                Err(EditError::BlankCustomerName)
+            }
+            OrderEdit::PlacedOn(date) if *date > today => Err(EditError::FutureDate(today)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(any(feature = "server", test))]
+impl OrderEdit {
+    /// Writes the value into `order`. Call [`OrderEdit::validate`] first.
+    pub fn apply(&self, order: &mut Order) {
+        match self {
+            OrderEdit::CustomerName(name) => order.customer_name = name.trim().to_string(),
+            OrderEdit::PlacedOn(date) => order.placed_on = *date,
+            OrderEdit::Channel(channel) => order.channel = *channel,
+            OrderEdit::Flagged(flagged) => order.flagged = *flagged,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::fixtures::seeded_orders;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()
+    }
+
+    #[test]
+    fn rejects_blank_names_and_future_dates() {
+        let tomorrow = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+
+        assert_eq!(
+            OrderEdit::CustomerName("  ".to_string()).validate(today()),
+            Err(EditError::BlankCustomerName)
+        );
+        assert_eq!(
+            OrderEdit::PlacedOn(tomorrow).validate(today()),
+            Err(EditError::FutureDate(today()))
+        );
+        assert_eq!(OrderEdit::PlacedOn(today()).validate(today()), Ok(()));
+        assert_eq!(OrderEdit::Flagged(true).validate(today()), Ok(()));
+    }
+
+    #[test]
+    fn applies_the_edited_field_only() {
+        let original = seeded_orders().remove(0);
+        let mut order = original.clone();
+
+        OrderEdit::CustomerName("  王小明 ".to_string()).apply(&mut order);
+        OrderEdit::Channel(SalesChannel::Wholesale).apply(&mut order);
+
+        assert_eq!(order.customer_name, "王小明");
+        assert_eq!(order.channel, SalesChannel::Wholesale);
+        assert_eq!(order.placed_on, original.placed_on);
+        assert_eq!(order.status, original.status);
+    }
+}
@@ -0,0 +1,423 @@
+//! Serializes orders into spreadsheet files. Both formats are produced on the client from the rows returned by
+//! [`super::api::export_orders`], so the export always matches the active filters and visible columns.
+
+use super::Order;
+use crate::i18n::I18n;
+use crate::time::NaiveDate;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Xlsx => "Excel (XLSX)",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn all() -> &'static [ExportFormat] {
+        &[ExportFormat::Csv, ExportFormat::Xlsx]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "csv" => Some(ExportFormat::Csv),
+            "xlsx" => Some(ExportFormat::Xlsx),
+            _ => None,
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv;charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+}
+
+/// A column of the export: the table column id it is read from and its header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportColumn {
+    pub id: String,
+    pub label: String,
+}
+
+impl ExportColumn {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportFile {
+    pub name: String,
+    pub mime_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// Leading characters that make spreadsheet apps read a CSV cell as a formula. Text starting with one is written
+/// behind a `'`, which the import drops again.
+pub(super) const FORMULA_TRIGGERS: [char; 4] = ['=', '+', '-', '@'];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Text(String),
+    Number(f64),
+}
+
+/// The value of the orders table column `id` for `order`, in the language of `i18n`. Unknown columns export as
+/// empty text.
+fn cell(order: &Order, id: &str, i18n: I18n) -> Cell {
+    let text = match id {
+        "number" => order.number.clone(),
+        "customer" => order.customer_name.clone(),
+        "email" => order.customer_email.clone(),
+        "date" => order.placed_on.format("%Y-%m-%d").to_string(),
+        "status" => order.status.label_in(i18n),
+        "payment" => order.payment_status.label_in(i18n),
+        "fulfillment" => order.fulfillment_status.label_in(i18n),
+        "channel" => order.channel.label_in(i18n),
+        "method" => order.payment_method.label_in(i18n),
+        "tags" => order.tags.join(", "),
+        "flagged" => i18n.t(if order.flagged {
+            "table.yes"
+        } else {
+            "table.no"
+        }),
+        "total" => return Cell::Number((order.total as f64 * 100.0).round() / 100.0),
+        _ => String::new(),
+    };
+    Cell::Text(text)
+}
+
+/// Builds the export file, named after the day it was produced. Values and the sheet name are written in the
+/// language of `i18n`; the import reads them back in any language.
+pub fn export(
+    orders: &[Order],
+    columns: &[ExportColumn],
+    format: ExportFormat,
+    today: NaiveDate,
+    i18n: I18n,
+) -> ExportFile {
+    let bytes = match format {
+        ExportFormat::Csv => to_csv(orders, columns, i18n),
+        ExportFormat::Xlsx => to_xlsx(orders, columns, i18n),
+    };
+    ExportFile {
+        name: format!("orders-{}.{}", today.format("%Y-%m-%d"), format.key()),
+        mime_type: format.mime_type(),
+        bytes,
+    }
+}
+
+/// RFC 4180 CSV with a UTF-8 byte order mark, which Excel needs to detect the encoding of the Chinese labels.
+/// Text that would read as a formula is defused, see [`FORMULA_TRIGGERS`].
+fn to_csv(orders: &[Order], columns: &[ExportColumn], i18n: I18n) -> Vec<u8> {
+    fn field(value: &str) -> String {
+        let value = if value.starts_with(FORMULA_TRIGGERS) {
+            format!("'{value}")
+        } else {
+            value.to_string()
+        };
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value
+        }
+    }
+
+    let mut csv = String::from("\u{feff}");
+    let header: Vec<String> = columns.iter().map(|column| field(&column.label)).collect();
+    csv.push_str(&header.join(","));
+    csv.push_str("\r\n");
+    for order in orders {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|column| match cell(order, &column.id, i18n) {
+                Cell::Text(text) => field(&text),
+                Cell::Number(number) => format!("{number:.2}"),
+            })
+            .collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv.into_bytes()
+}
+
+/// A single-sheet workbook using inline strings, so no shared string table or styles are needed.
+fn to_xlsx(orders: &[Order], columns: &[ExportColumn], i18n: I18n) -> Vec<u8> {
+    let mut rows = String::new();
+    let header = columns
+        .iter()
+        .map(|column| Cell::Text(column.label.clone()))
+        .collect::<Vec<_>>();
+    let body = orders.iter().map(|order| {
+        columns
+            .iter()
+            .map(|column| cell(order, &column.id, i18n))
+            .collect::<Vec<_>>()
+    });
+    for (index, cells) in std::iter::once(header).chain(body).enumerate() {
+        let row = index + 1;
+        rows.push_str(&format!("<row r=\"{row}\">"));
+        for (column, value) in cells.iter().enumerate() {
+            let reference = format!("{}{row}", column_name(column));
+            match value {
+                Cell::Text(text) => rows.push_str(&format!(
+                    "<c r=\"{reference}\" t=\"inlineStr\"><is><t>{}</t></is></c>",
+                    escape_xml(text)
+                )),
+                Cell::Number(number) => {
+                    rows.push_str(&format!("<c r=\"{reference}\"><v>{number}</v></c>"))
+                }
+            }
+        }
+        rows.push_str("</row>");
+    }
+
+    let sheet = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+         <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">\
+         <sheetData>{rows}</sheetData></worksheet>"
+    );
+    let workbook = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+         <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" \
+         xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+         <sheets><sheet name=\"{}\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>",
+        escape_xml(&i18n.t("orders.export.sheet"))
+    );
+    let entries: [(&str, &str); 5] = [
+        (
+            "[Content_Types].xml",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+             <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+             <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+             <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+             <Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>\
+             <Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>\
+             </Types>",
+        ),
+        (
+            "_rels/.rels",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+             <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+             <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/>\
+             </Relationships>",
+        ),
+        ("xl/workbook.xml", &workbook),
+        (
+            "xl/_rels/workbook.xml.rels",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+             <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+             <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet1.xml\"/>\
+             </Relationships>",
+        ),
+        ("xl/worksheets/sheet1.xml", &sheet),
+    ];
+    zip_stored(&entries)
+}
+
+/// Spreadsheet column name for a zero-based index: 0 → `A`, 25 → `Z`, 26 → `AA`.
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("ascii column name")
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // Control characters other than tab and newlines are not allowed in XML 1.0.
+            ch if (ch as u32) < 0x20 && !matches!(ch, '\t' | '\n' | '\r') => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Writes a zip archive whose entries are stored uncompressed, which is all an XLSX reader requires.
+fn zip_stored(entries: &[(&str, &str)]) -> Vec<u8> {
+    // 1980-01-01 00:00 in MS-DOS format, the earliest timestamp zip can represent.
+    const DOS_TIME: u16 = 0;
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    let mut archive = Vec::new();
+    let mut directory = Vec::new();
+    for (name, content) in entries {
+        let offset = archive.len() as u32;
+        let data = content.as_bytes();
+        let crc = crc32(data);
+        let size = data.len() as u32;
+
+        archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        archive.extend_from_slice(&0u16.to_le_bytes()); // flags
+        archive.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        archive.extend_from_slice(&DOS_TIME.to_le_bytes());
+        archive.extend_from_slice(&DOS_DATE.to_le_bytes());
+        archive.extend_from_slice(&crc.to_le_bytes());
+        archive.extend_from_slice(&size.to_le_bytes());
+        archive.extend_from_slice(&size.to_le_bytes());
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(data);
+
+        directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        directory.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        directory.extend_from_slice(&0u16.to_le_bytes()); // flags
+        directory.extend_from_slice(&0u16.to_le_bytes()); // method
+        directory.extend_from_slice(&DOS_TIME.to_le_bytes());
+        directory.extend_from_slice(&DOS_DATE.to_le_bytes());
+        directory.extend_from_slice(&crc.to_le_bytes());
+        directory.extend_from_slice(&size.to_le_bytes());
+        directory.extend_from_slice(&size.to_le_bytes());
+        directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&[0; 12]); // extra, comment, disk, internal and external attributes
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = archive.len() as u32;
+    archive.extend_from_slice(&directory);
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0; 4]); // disk numbers
+    archive.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    archive.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&directory_offset.to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    archive
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::fixtures::seeded_orders;
+    use crate::time::Locale;
+
+    fn columns() -> Vec<ExportColumn> {
+        vec![
+            ExportColumn::new("number", "订单号"),
+            ExportColumn::new("tags", "标签"),
+            ExportColumn::new("total", "金额"),
+        ]
+    }
+
+    #[test]
+    fn csv_starts_with_bom_and_quotes_fields() {
+        let orders = seeded_orders();
+        let file = export(
+            &orders[..2],
+            &columns(),
+            ExportFormat::Csv,
+            NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+            I18n::default(),
+        );
+        assert_eq!(file.name, "orders-2024-08-01.csv");
+
+        let text = String::from_utf8(file.bytes).unwrap();
+        assert_eq!(
+            text,
+            "\u{feff}订单号,标签,金额\r\nDX-1050,\"VIP, 加急\",1288.00\r\nDX-1049,需回访,342.00\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_keeps_names_apart_from_emails_and_defuses_formulas() {
+        let mut order = seeded_orders().remove(0);
+        order.customer_name = "=HYPERLINK(\"x\")".to_string();
+        let file = export(
+            &[order.clone()],
+            &[
+                ExportColumn::new("customer", "客户"),
+                ExportColumn::new("email", "客户邮箱"),
+            ],
+            ExportFormat::Csv,
+            NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+            I18n::default(),
+        );
+        let text = String::from_utf8(file.bytes).unwrap();
+        assert_eq!(
+            text,
+            format!(
+                "\u{feff}客户,客户邮箱\r\n\"'=HYPERLINK(\"\"x\"\")\",{}\r\n",
+                order.customer_email
+            )
+        );
+    }
+
+    #[test]
+    fn xlsx_is_a_stored_zip_with_the_sheet() {
+        let orders = seeded_orders();
+        let file = export(
+            &orders,
+            &columns(),
+            ExportFormat::Xlsx,
+            NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+            I18n::new(Locale::EnUs),
+        );
+        assert_eq!(&file.bytes[..4], b"PK\x03\x04");
+        let end = &file.bytes[file.bytes.len() - 22..];
+        assert_eq!(&end[..4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 5);
+
+        let contents = String::from_utf8_lossy(&file.bytes);
+        assert!(contents.contains("xl/worksheets/sheet1.xml"));
+        assert!(contents.contains("<c r=\"B2\" t=\"inlineStr\"><is><t>VIP, 加急</t></is></c>"));
+        assert!(contents.contains("<c r=\"C13\"><v>210</v></c>"));
+        assert!(contents.contains("<sheet name=\"Orders\""));
+    }
+
+    #[test]
+    fn helpers_match_reference_values() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(escape_xml("a<b & \"c\"\u{1}"), "a&lt;b &amp; &quot;c&quot;");
+    }
+}
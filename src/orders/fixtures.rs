@@ -0,0 +1,334 @@
+use super::{
+    FulfillmentStatus, LineItem, Order, OrderNote, OrderStatus, PaymentMethod, PaymentStatus,
+    SalesChannel, StatusChange,
+};
+use crate::time::{Duration, NaiveDate};
+
+/// The demo order set the repositories are seeded with when they start out empty.
+pub fn seeded_orders() -> Vec<Order> {
+    let orders = vec![
+        Order {
+            number: "DX-1050".to_string(),
+            placed_on: date(2024, 7, 23),
+            customer_name: "孙若水".to_string(),
+            customer_email: "ruoshui@example.com".to_string(),
+            status: OrderStatus::Processing,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Picking,
+            payment_method: PaymentMethod::CreditCard,
+            channel: SalesChannel::OnlineStore,
+            total: 1288.0,
+            tags: tags(&["VIP", "加急"]),
+            flagged: true,
+            line_items: vec![
+                item("WATCH-S2", "智能手表 S2", 1, 1088.0),
+                item("BAND-SIL", "硅胶表带", 2, 100.0),
+            ],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: vec![note(
+                "周静",
+                "客户要求周五前送达，已安排加急拣货。",
+                date(2024, 7, 23),
+            )],
+        },
+        Order {
+            number: "DX-1049".to_string(),
+            placed_on: date(2024, 7, 22),
+            customer_name: "李倩".to_string(),
+            customer_email: "lian@example.com".to_string(),
+            status: OrderStatus::PendingPayment,
+            payment_status: PaymentStatus::Pending,
+            fulfillment_status: FulfillmentStatus::Unfulfilled,
+            payment_method: PaymentMethod::WechatPay,
+            channel: SalesChannel::Marketplace,
+            total: 342.0,
+            tags: tags(&["需回访"]),
+            flagged: true,
+            line_items: vec![item("COFFEE-500", "精品咖啡豆 500g", 3, 114.0)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: vec![note("陈晨", "已短信提醒客户完成支付。", date(2024, 7, 23))],
+        },
+        Order {
+            number: "DX-1048".to_string(),
+            placed_on: date(2024, 7, 21),
+            customer_name: "Zoe Chen".to_string(),
+            customer_email: "zoe@example.com".to_string(),
+            status: OrderStatus::Processing,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Shipped,
+            payment_method: PaymentMethod::Paypal,
+            channel: SalesChannel::Marketplace,
+            total: 812.5,
+            tags: tags(&["加急"]),
+            flagged: false,
+            line_items: vec![
+                item("EARBUD-PRO", "降噪蓝牙耳机", 1, 699.0),
+                item("CASE-EAR", "耳机保护套", 1, 113.5),
+            ],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+        Order {
+            number: "DX-1047".to_string(),
+            placed_on: date(2024, 7, 20),
+            customer_name: "王宏".to_string(),
+            customer_email: "hong@example.com".to_string(),
+            status: OrderStatus::Fulfilled,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Delivered,
+            payment_method: PaymentMethod::CreditCard,
+            channel: SalesChannel::OnlineStore,
+            total: 1560.0,
+            tags: tags(&["VIP", "赠品"]),
+            flagged: false,
+            line_items: vec![item("KB-MECH87", "87 键机械键盘", 2, 780.0)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+        Order {
+            number: "DX-1046".to_string(),
+            placed_on: date(2024, 7, 18),
+            customer_name: "刘洋".to_string(),
+            customer_email: "yang@example.com".to_string(),
+            status: OrderStatus::Processing,
+            payment_status: PaymentStatus::Overdue,
+            fulfillment_status: FulfillmentStatus::Unfulfilled,
+            payment_method: PaymentMethod::BankTransfer,
+            channel: SalesChannel::Wholesale,
+            total: 2890.4,
+            tags: tags(&["缺货", "需回访"]),
+            flagged: true,
+            line_items: vec![item("BAG-CANVAS", "定制帆布袋", 40, 72.26)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: vec![
+                note(
+                    "周静",
+                    "财务确认转账尚未到账，等待客户回复。",
+                    date(2024, 7, 25),
+                ),
+                note(
+                    "陈晨",
+                    "帆布袋库存不足，需向供应商补货。",
+                    date(2024, 7, 26),
+                ),
+            ],
+        },
+        Order {
+            number: "DX-1045".to_string(),
+            placed_on: date(2024, 7, 17),
+            customer_name: "陈浩".to_string(),
+            customer_email: "hao@example.com".to_string(),
+            status: OrderStatus::Cancelled,
+            payment_status: PaymentStatus::Refunded,
+            fulfillment_status: FulfillmentStatus::Returned,
+            payment_method: PaymentMethod::CreditCard,
+            channel: SalesChannel::OnlineStore,
+            total: 420.0,
+            tags: tags(&["重复下单"]),
+            flagged: false,
+            line_items: vec![item("MUG-THERMO", "保温杯", 1, 420.0)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: vec![note(
+                "陈晨",
+                "客户重复下单，已全额退款。",
+                date(2024, 7, 20),
+            )],
+        },
+        Order {
+            number: "DX-1044".to_string(),
+            placed_on: date(2024, 7, 16),
+            customer_name: "Marvin Zhou".to_string(),
+            customer_email: "marvin@example.com".to_string(),
+            status: OrderStatus::Processing,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Shipped,
+            payment_method: PaymentMethod::CreditCard,
+            channel: SalesChannel::PopUp,
+            total: 980.0,
+            tags: tags(&["赠品"]),
+            flagged: false,
+            line_items: vec![
+                item("CANDLE-AROMA", "香薰蜡烛", 2, 240.0),
+                item("GIFT-BOX", "节日礼盒", 1, 500.0),
+            ],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+        Order {
+            number: "DX-1043".to_string(),
+            placed_on: date(2024, 7, 15),
+            customer_name: "张伟".to_string(),
+            customer_email: "zhangwei@example.com".to_string(),
+            status: OrderStatus::Fulfilled,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Delivered,
+            payment_method: PaymentMethod::WechatPay,
+            channel: SalesChannel::Subscription,
+            total: 652.0,
+            tags: tags(&["VIP"]),
+            flagged: false,
+            line_items: vec![item("COFFEE-SUB", "咖啡订阅包", 4, 163.0)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+        Order {
+            number: "DX-1042".to_string(),
+            placed_on: date(2024, 7, 13),
+            customer_name: "李雷".to_string(),
+            customer_email: "lilei@example.com".to_string(),
+            status: OrderStatus::PendingPayment,
+            payment_status: PaymentStatus::Pending,
+            fulfillment_status: FulfillmentStatus::Unfulfilled,
+            payment_method: PaymentMethod::BankTransfer,
+            channel: SalesChannel::Wholesale,
+            total: 3750.0,
+            tags: tags(&["缺货", "加急"]),
+            flagged: true,
+            line_items: vec![item("NOTE-A5", "A5 笔记本", 50, 75.0)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: vec![note(
+                "周静",
+                "批发客户申请延长付款期限至月底。",
+                date(2024, 7, 14),
+            )],
+        },
+        Order {
+            number: "DX-1041".to_string(),
+            placed_on: date(2024, 7, 11),
+            customer_name: "丁一".to_string(),
+            customer_email: "dingyi@example.com".to_string(),
+            status: OrderStatus::Processing,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Picking,
+            payment_method: PaymentMethod::Alipay,
+            channel: SalesChannel::OnlineStore,
+            total: 512.8,
+            tags: tags(&["赠品"]),
+            flagged: false,
+            line_items: vec![
+                item("CREAM-HAND", "护手霜", 2, 128.4),
+                item("SOAP-SET", "香皂礼盒", 1, 256.0),
+            ],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+        Order {
+            number: "DX-1040".to_string(),
+            placed_on: date(2024, 7, 10),
+            customer_name: "Grace Li".to_string(),
+            customer_email: "grace@example.com".to_string(),
+            status: OrderStatus::Fulfilled,
+            payment_status: PaymentStatus::Paid,
+            fulfillment_status: FulfillmentStatus::Delivered,
+            payment_method: PaymentMethod::CreditCard,
+            channel: SalesChannel::Marketplace,
+            total: 786.2,
+            tags: tags(&["VIP", "赠品"]),
+            flagged: false,
+            line_items: vec![
+                item("STAND-MON", "显示器支架", 1, 586.2),
+                item("PAD-DESK", "桌垫", 1, 200.0),
+            ],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+        Order {
+            number: "DX-1039".to_string(),
+            placed_on: date(2024, 7, 9),
+            customer_name: "赵敏".to_string(),
+            customer_email: "zhaomin@example.com".to_string(),
+            status: OrderStatus::Draft,
+            payment_status: PaymentStatus::Pending,
+            fulfillment_status: FulfillmentStatus::Unfulfilled,
+            payment_method: PaymentMethod::Cash,
+            channel: SalesChannel::PopUp,
+            total: 210.0,
+            tags: tags(&["需回访"]),
+            flagged: false,
+            line_items: vec![item("CARD-SET", "明信片套装", 3, 70.0)],
+            payment_history: Vec::new(),
+            fulfillment_history: Vec::new(),
+            notes: Vec::new(),
+        },
+    ];
+    orders.into_iter().map(with_history).collect()
+}
+
+/// Fills in a status history that walks the usual path from placement up to the order's current payment and
+/// fulfillment status.
+fn with_history(mut order: Order) -> Order {
+    let payment_path: &[(PaymentStatus, i64)] = match order.payment_status {
+        PaymentStatus::Pending => &[(PaymentStatus::Pending, 0)],
+        PaymentStatus::Paid => &[(PaymentStatus::Pending, 0), (PaymentStatus::Paid, 0)],
+        PaymentStatus::Refunded => &[
+            (PaymentStatus::Pending, 0),
+            (PaymentStatus::Paid, 0),
+            (PaymentStatus::Refunded, 3),
+        ],
+        PaymentStatus::Overdue => &[(PaymentStatus::Pending, 0), (PaymentStatus::Overdue, 7)],
+    };
+    let fulfillment_path = [
+        (FulfillmentStatus::Unfulfilled, 0),
+        (FulfillmentStatus::Picking, 1),
+        (FulfillmentStatus::Shipped, 2),
+        (FulfillmentStatus::Delivered, 4),
+        (FulfillmentStatus::Returned, 6),
+    ];
+    let reached = fulfillment_path
+        .iter()
+        .position(|(status, _)| *status == order.fulfillment_status)
+        .unwrap_or(0);
+
+    order.payment_history = payment_path
+        .iter()
+        .map(|(status, offset)| change(*status, order.placed_on, *offset))
+        .collect();
+    order.fulfillment_history = fulfillment_path[..=reached]
+        .iter()
+        .map(|(status, offset)| change(*status, order.placed_on, *offset))
+        .collect();
+    order
+}
+
+fn change<S>(status: S, placed_on: NaiveDate, offset_days: i64) -> StatusChange<S> {
+    StatusChange {
+        status,
+        on: placed_on + Duration::days(offset_days),
+    }
+}
+
+fn item(sku: &str, name: &str, quantity: u32, unit_price: f32) -> LineItem {
+    LineItem {
+        sku: sku.to_string(),
+        name: name.to_string(),
+        quantity,
+        unit_price,
+    }
+}
+
+fn note(author: &str, body: &str, created_on: NaiveDate) -> OrderNote {
+    OrderNote {
+        author: author.to_string(),
+        body: body.to_string(),
+        created_on,
+    }
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("valid mock date")
+}
+
+fn tags(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| (*value).to_string()).collect()
+}
@@ -0,0 +1,789 @@
+//! Reading orders from a CSV file. The file is parsed into raw rows, each column is mapped onto an order field
+//! (guessed from the header, adjustable by the user) and every row is validated on its own so the valid rows can
+//! be imported while the invalid ones are reported with their line number.
+
+use super::export::FORMULA_TRIGGERS;
+use super::transition::Transition;
+use super::StatusChange;
+use super::{FulfillmentStatus, Order, OrderStatus, PaymentMethod, PaymentStatus, SalesChannel};
+use crate::i18n::I18n;
+use crate::time::{Locale, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(any(feature = "server", test))]
+use super::repository::{OrderRepository, RepositoryError};
+
+/// The order fields a CSV column can be mapped onto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportField {
+    Number,
+    PlacedOn,
+    CustomerName,
+    CustomerEmail,
+    Status,
+    PaymentStatus,
+    FulfillmentStatus,
+    PaymentMethod,
+    Channel,
+    Total,
+    Tags,
+    Flagged,
+}
+
+impl ImportField {
+    /// The field name in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.field.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ImportField::Number => "number",
+            ImportField::PlacedOn => "date",
+            ImportField::CustomerName => "customer",
+            ImportField::CustomerEmail => "email",
+            ImportField::Status => "status",
+            ImportField::PaymentStatus => "payment",
+            ImportField::FulfillmentStatus => "fulfillment",
+            ImportField::PaymentMethod => "method",
+            ImportField::Channel => "channel",
+            ImportField::Total => "total",
+            ImportField::Tags => "tags",
+            ImportField::Flagged => "flagged",
+        }
+    }
+
+    pub fn all() -> &'static [ImportField] {
+        &[
+            ImportField::Number,
+            ImportField::PlacedOn,
+            ImportField::CustomerName,
+            ImportField::CustomerEmail,
+            ImportField::Status,
+            ImportField::PaymentStatus,
+            ImportField::FulfillmentStatus,
+            ImportField::PaymentMethod,
+            ImportField::Channel,
+            ImportField::Total,
+            ImportField::Tags,
+            ImportField::Flagged,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|field| field.key() == value)
+    }
+
+    /// Fields without a sensible default. Unmapped optional fields fall back to a new, unpaid draft order.
+    pub fn is_required(&self) -> bool {
+        matches!(
+            self,
+            ImportField::Number
+                | ImportField::PlacedOn
+                | ImportField::CustomerName
+                | ImportField::PaymentMethod
+                | ImportField::Channel
+                | ImportField::Total
+        )
+    }
+
+    /// Other header names the field is recognised by, including the column labels of the orders table export.
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            ImportField::Number => &["order", "order number", "单号"],
+            ImportField::PlacedOn => &["placed on", "order date", "日期"],
+            ImportField::CustomerName => &["name", "customer name", "客户"],
+            ImportField::CustomerEmail => &["customer email", "邮箱"],
+            ImportField::Status => &["order status", "状态"],
+            ImportField::PaymentStatus => &["payment status", "支付"],
+            ImportField::FulfillmentStatus => &["fulfillment status", "履约"],
+            ImportField::PaymentMethod => &["payment method"],
+            ImportField::Channel => &["sales channel", "渠道"],
+            ImportField::Total => &["amount", "总额"],
+            ImportField::Tags => &["tag"],
+            ImportField::Flagged => &["flag"],
+        }
+    }
+
+    /// Headers are recognised by the field key, the field name or the orders table column label in any language,
+    /// and the aliases.
+    fn matches_header(&self, header: &str) -> bool {
+        let header = header.trim().to_lowercase();
+        let column = format!("orders.column.{}", self.key());
+        header == self.key()
+            || in_any_language(|i18n| [self.label_in(i18n), i18n.t(&column)])
+                .any(|label| header == label.to_lowercase())
+            || self.aliases().iter().any(|alias| header == *alias)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CsvError {
+    /// A quoted field opened on `line` is never closed.
+    UnterminatedQuote { line: usize },
+    /// The file has no header row.
+    Empty,
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::UnterminatedQuote { line } => {
+                write!(f, "quoted field starting on line {line} is never closed")
+            }
+            CsvError::Empty => write!(f, "the file has no header row"),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// A parsed CSV file: the header row and the data rows below it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<CsvRow>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CsvRow {
+    /// 1-based line the row starts on, counting the header.
+    pub line: usize,
+    pub cells: Vec<String>,
+}
+
+/// Parses RFC 4180 CSV: comma separated, fields optionally quoted with `""` escaping a quote, CRLF or LF line
+/// endings. A leading byte order mark is ignored and blank lines are skipped.
+pub fn parse_csv(text: &str) -> Result<CsvTable, CsvError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records: Vec<CsvRow> = Vec::new();
+    let mut cells = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quoted_since = None;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if quoted_since.is_some() {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted_since = None,
+                '\n' => {
+                    line += 1;
+                    field.push(ch);
+                }
+                _ => field.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' if field.is_empty() => quoted_since = Some(line),
+            ',' => cells.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                cells.push(std::mem::take(&mut field));
+                records.push(CsvRow {
+                    line: record_line,
+                    cells: std::mem::take(&mut cells),
+                });
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(ch),
+        }
+    }
+    if let Some(line) = quoted_since {
+        return Err(CsvError::UnterminatedQuote { line });
+    }
+    if !field.is_empty() || !cells.is_empty() {
+        cells.push(field);
+        records.push(CsvRow {
+            line: record_line,
+            cells,
+        });
+    }
+
+    let mut records = records
+        .into_iter()
+        .filter(|row| row.cells.iter().any(|cell| !cell.trim().is_empty()));
+    let headers = records.next().ok_or(CsvError::Empty)?.cells;
+    Ok(CsvTable {
+        headers,
+        rows: records.collect(),
+    })
+}
+
+/// Which field each CSV column feeds, by column index. `None` ignores the column.
+pub type ColumnMapping = Vec<Option<ImportField>>;
+
+/// Maps every header that names a field onto it. Each field is used at most once, for its first matching column.
+pub fn guess_mapping(headers: &[String]) -> ColumnMapping {
+    let mut mapping: ColumnMapping = Vec::with_capacity(headers.len());
+    for header in headers {
+        let field = ImportField::all()
+            .iter()
+            .copied()
+            .find(|field| field.matches_header(header) && !mapping.contains(&Some(*field)));
+        mapping.push(field);
+    }
+    mapping
+}
+
+/// Required fields no column is mapped onto.
+pub fn missing_fields(mapping: &[Option<ImportField>]) -> Vec<ImportField> {
+    ImportField::all()
+        .iter()
+        .copied()
+        .filter(|field| field.is_required() && !mapping.contains(&Some(*field)))
+        .collect()
+}
+
+/// Why a single row cannot be imported.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RowError {
+    /// A required field is empty.
+    Missing(ImportField),
+    /// The value cannot be read as the field's type.
+    Invalid { field: ImportField, value: String },
+    /// An earlier row in the file already uses the order number.
+    DuplicateNumber(String),
+    /// The order, payment and fulfillment status cannot occur together, e.g. a cancelled order that was delivered.
+    Inconsistent(Transition),
+}
+
+impl Display for RowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RowError::Missing(field) => write!(f, "{} is required", field.key()),
+            RowError::Invalid { field, value } => {
+                write!(f, "{value:?} is not a valid {}", field.key())
+            }
+            RowError::DuplicateNumber(number) => write!(f, "order {number} appears twice"),
+            RowError::Inconsistent(statuses) => write!(
+                f,
+                "an order cannot be {}/{}/{}",
+                statuses.status.key(),
+                statuses.payment_status.key(),
+                statuses.fulfillment_status.key()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RowError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportRow {
+    pub line: usize,
+    pub cells: Vec<String>,
+    pub result: Result<Order, Vec<RowError>>,
+}
+
+impl ImportRow {
+    /// The value of the column mapped onto `field`, if any, without the `'` exports put before text that would
+    /// read as a formula.
+    pub fn value<'a>(&'a self, mapping: &[Option<ImportField>], field: ImportField) -> &'a str {
+        let value = mapping
+            .iter()
+            .position(|mapped| *mapped == Some(field))
+            .and_then(|index| self.cells.get(index))
+            .map(|value| value.trim())
+            .unwrap_or("");
+        match value.strip_prefix('\'') {
+            Some(text) if text.starts_with(FORMULA_TRIGGERS) => text,
+            _ => value,
+        }
+    }
+}
+
+/// Validates every data row against `mapping`. Rows are independent: one invalid row never affects another,
+/// except that an order number may only appear once.
+pub fn validate(table: &CsvTable, mapping: &[Option<ImportField>]) -> Vec<ImportRow> {
+    let mut seen: Vec<String> = Vec::new();
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let mut import = ImportRow {
+                line: row.line,
+                cells: row.cells.clone(),
+                result: Err(Vec::new()),
+            };
+            let mut result = build_order(&import, mapping);
+            if let Ok(order) = &result {
+                if seen.contains(&order.number) {
+                    result = Err(vec![RowError::DuplicateNumber(order.number.clone())]);
+                } else {
+                    seen.push(order.number.clone());
+                }
+            }
+            import.result = result;
+            import
+        })
+        .collect()
+}
+
+/// The texts `texts` gives for every language of the app.
+fn in_any_language<const N: usize>(
+    texts: impl Fn(I18n) -> [String; N],
+) -> impl Iterator<Item = String> {
+    Locale::all()
+        .iter()
+        .flat_map(move |locale| texts(I18n::new(*locale)))
+}
+
+/// Reads a label in any language or a `from_key` key, so both exported files and hand-written keys are accepted.
+fn choice<T: Copy>(
+    value: &str,
+    all: &[T],
+    label_in: fn(&T, I18n) -> String,
+    from_key: fn(&str) -> Option<T>,
+) -> Option<T> {
+    from_key(&value.to_lowercase()).or_else(|| {
+        all.iter()
+            .copied()
+            .find(|item| in_any_language(|i18n| [label_in(item, i18n)]).any(|label| label == value))
+    })
+}
+
+/// Accepts `YYYY-MM-DD` and `YYYY/MM/DD`.
+pub(crate) fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y/%m/%d"))
+        .ok()
+}
+
+fn parse_total(value: &str) -> Option<f32> {
+    let cleaned: String = value
+        .chars()
+        .filter(|ch| !matches!(ch, '¥' | '￥' | '$' | ',' | ' '))
+        .collect();
+    cleaned
+        .parse::<f32>()
+        .ok()
+        .filter(|total| total.is_finite() && *total >= 0.0)
+}
+
+/// Reads `true` / `false`, `1` / `0`, `y` / `n` or the yes and no of any language.
+fn parse_flag(value: &str) -> Option<bool> {
+    let value = value.to_lowercase();
+    let said =
+        |key: &str| in_any_language(|i18n| [i18n.t(key).to_lowercase()]).any(|word| word == value);
+    match value.as_str() {
+        "" | "0" | "false" | "n" => Some(false),
+        "1" | "true" | "y" => Some(true),
+        _ if said("table.no") => Some(false),
+        _ if said("table.yes") => Some(true),
+        _ => None,
+    }
+}
+
+fn build_order(row: &ImportRow, mapping: &[Option<ImportField>]) -> Result<Order, Vec<RowError>> {
+    let mut errors = Vec::new();
+    let mut field = |field: ImportField| {
+        let value = row.value(mapping, field);
+        if value.is_empty() && field.is_required() {
+            errors.push(RowError::Missing(field));
+        }
+        value
+    };
+
+    let number = field(ImportField::Number).to_string();
+    let customer_name = field(ImportField::CustomerName).to_string();
+    let customer_email = field(ImportField::CustomerEmail).to_string();
+    let placed_on = field(ImportField::PlacedOn);
+    let status = field(ImportField::Status);
+    let payment_status = field(ImportField::PaymentStatus);
+    let fulfillment_status = field(ImportField::FulfillmentStatus);
+    let payment_method = field(ImportField::PaymentMethod);
+    let channel = field(ImportField::Channel);
+    let total = field(ImportField::Total);
+    let tags = field(ImportField::Tags);
+    let flagged = field(ImportField::Flagged);
+
+    // Empty optional values take their default; empty required values were reported above.
+    fn parsed<T>(
+        errors: &mut Vec<RowError>,
+        field: ImportField,
+        value: &str,
+        default: Option<T>,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> Option<T> {
+        if value.is_empty() {
+            return default;
+        }
+        let parsed = parse(value);
+        if parsed.is_none() {
+            errors.push(RowError::Invalid {
+                field,
+                value: value.to_string(),
+            });
+        }
+        parsed
+    }
+
+    let placed_on = parsed(
+        &mut errors,
+        ImportField::PlacedOn,
+        placed_on,
+        None,
+        parse_date,
+    );
+    let status = parsed(
+        &mut errors,
+        ImportField::Status,
+        status,
+        Some(OrderStatus::Draft),
+        |value| {
+            choice(
+                value,
+                OrderStatus::all(),
+                OrderStatus::label_in,
+                OrderStatus::from_key,
+            )
+        },
+    );
+    let payment_status = parsed(
+        &mut errors,
+        ImportField::PaymentStatus,
+        payment_status,
+        Some(PaymentStatus::Pending),
+        |value| {
+            choice(
+                value,
+                PaymentStatus::all(),
+                PaymentStatus::label_in,
+                PaymentStatus::from_key,
+            )
+        },
+    );
+    let fulfillment_status = parsed(
+        &mut errors,
+        ImportField::FulfillmentStatus,
+        fulfillment_status,
+        Some(FulfillmentStatus::Unfulfilled),
+        |value| {
+            choice(
+                value,
+                FulfillmentStatus::all(),
+                FulfillmentStatus::label_in,
+                FulfillmentStatus::from_key,
+            )
+        },
+    );
+    let payment_method = parsed(
+        &mut errors,
+        ImportField::PaymentMethod,
+        payment_method,
+        None,
+        |value| {
+            choice(
+                value,
+                PaymentMethod::all(),
+                PaymentMethod::label_in,
+                PaymentMethod::from_key,
+            )
+        },
+    );
+    let channel = parsed(&mut errors, ImportField::Channel, channel, None, |value| {
+        choice(
+            value,
+            SalesChannel::all(),
+            SalesChannel::label_in,
+            SalesChannel::from_key,
+        )
+    });
+    let total = parsed(&mut errors, ImportField::Total, total, None, parse_total);
+    let flagged = parsed(
+        &mut errors,
+        ImportField::Flagged,
+        flagged,
+        Some(false),
+        parse_flag,
+    );
+
+    let (
+        Some(placed_on),
+        Some(status),
+        Some(payment_status),
+        Some(fulfillment_status),
+        Some(payment_method),
+        Some(channel),
+        Some(total),
+        Some(flagged),
+    ) = (
+        placed_on,
+        status,
+        payment_status,
+        fulfillment_status,
+        payment_method,
+        channel,
+        total,
+        flagged,
+    )
+    else {
+        return Err(errors);
+    };
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let statuses = Transition {
+        status,
+        payment_status,
+        fulfillment_status,
+    };
+    if !statuses.is_consistent() {
+        return Err(vec![RowError::Inconsistent(statuses)]);
+    }
+
+    let mut imported: Vec<String> = Vec::new();
+    for tag in tags.split([',', '，', '、', ';']) {
+        let tag = tag.trim();
+        if !tag.is_empty() && !imported.iter().any(|existing| existing == tag) {
+            imported.push(tag.to_string());
+        }
+    }
+
+    Ok(Order {
+        number,
+        placed_on,
+        customer_name,
+        customer_email,
+        status,
+        payment_status,
+        fulfillment_status,
+        payment_method,
+        channel,
+        total,
+        tags: imported,
+        flagged,
+        line_items: Vec::new(),
+        // The file only carries the current statuses, so the history starts with them on the order date.
+        payment_history: vec![StatusChange {
+            status: payment_status,
+            on: placed_on,
+        }],
+        fulfillment_history: vec![StatusChange {
+            status: fulfillment_status,
+            on: placed_on,
+        }],
+        notes: Vec::new(),
+    })
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImportOutcome {
+    pub imported: Vec<String>,
+    /// Numbers that already belong to a stored order. Existing orders are never overwritten by an import.
+    pub skipped: Vec<String>,
+}
+
+/// Saves every order whose number is not taken yet. Numbers taken in the meantime, e.g. by a concurrent import,
+/// are skipped as well.
+#[cfg(any(feature = "server", test))]
+pub fn import(
+    repository: &dyn OrderRepository,
+    orders: &[Order],
+) -> Result<ImportOutcome, RepositoryError> {
+    let mut outcome = ImportOutcome::default();
+    for order in orders {
+        if repository.insert_new(order)? {
+            outcome.imported.push(order.number.clone());
+        } else {
+            outcome.skipped.push(order.number.clone());
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::repository::InMemoryOrderRepository;
+
+    const SAMPLE: &str = "\u{feff}订单号,客户,下单日期,支付方式,渠道,金额,状态,标签\r\n\
+        DX-2001,\"Li, Wei\",2024-07-01,alipay,官网商城,\"1,280.50\",待支付,\"VIP, 加急\"\r\n\
+        DX-2002,王芳,2024-02-30,cash,store,99,draft,\r\n\
+        DX-2003,赵敏,2024-07-02,bitcoin,store,-5,shipped,\r\n\
+        \r\n\
+        DX-2001,重复,2024-07-03,cash,store,10,,\r\n";
+
+    fn table() -> CsvTable {
+        parse_csv(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parses_quoted_fields_and_skips_blank_lines() {
+        let table = parse_csv("a,b\n\"x \"\"y\"\"\",\"multi\nline\"\n\n1,\n").unwrap();
+        assert_eq!(table.headers, ["a", "b"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0].cells, ["x \"y\"", "multi\nline"]);
+        assert_eq!(table.rows[1].cells, ["1", ""]);
+        assert_eq!(table.rows[1].line, 5);
+
+        assert_eq!(
+            parse_csv("a\n\"open").unwrap_err(),
+            CsvError::UnterminatedQuote { line: 2 }
+        );
+        assert_eq!(parse_csv("\u{feff}\r\n").unwrap_err(), CsvError::Empty);
+    }
+
+    #[test]
+    fn guesses_mapping_from_headers() {
+        let mapping = guess_mapping(&table().headers);
+        assert_eq!(
+            mapping,
+            [
+                Some(ImportField::Number),
+                Some(ImportField::CustomerName),
+                Some(ImportField::PlacedOn),
+                Some(ImportField::PaymentMethod),
+                Some(ImportField::Channel),
+                Some(ImportField::Total),
+                Some(ImportField::Status),
+                Some(ImportField::Tags),
+            ]
+        );
+        assert!(missing_fields(&mapping).is_empty());
+        assert_eq!(
+            missing_fields(&mapping[1..]),
+            [ImportField::Number],
+            "dropping the first column leaves the order number unmapped"
+        );
+    }
+
+    #[test]
+    fn validates_each_row_independently() {
+        let table = table();
+        let rows = validate(&table, &guess_mapping(&table.headers));
+        assert_eq!(
+            rows.iter().map(|row| row.line).collect::<Vec<_>>(),
+            [2, 3, 4, 6]
+        );
+
+        let order = rows[0].result.as_ref().unwrap();
+        assert_eq!(order.customer_name, "Li, Wei");
+        assert_eq!(
+            order.placed_on,
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()
+        );
+        assert_eq!(order.status, OrderStatus::PendingPayment);
+        assert_eq!(order.payment_status, PaymentStatus::Pending);
+        assert_eq!(order.payment_method, PaymentMethod::Alipay);
+        assert_eq!(order.channel, SalesChannel::OnlineStore);
+        assert_eq!(order.total, 1280.5);
+        assert_eq!(order.tags, ["VIP", "加急"]);
+
+        assert_eq!(
+            rows[1].result,
+            Err(vec![RowError::Invalid {
+                field: ImportField::PlacedOn,
+                value: "2024-02-30".to_string(),
+            }])
+        );
+        let errors = rows[2].result.as_ref().unwrap_err();
+        let fields: Vec<_> = errors
+            .iter()
+            .map(|error| match error {
+                RowError::Invalid { field, .. } => *field,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ImportField::Status,
+                ImportField::PaymentMethod,
+                ImportField::Total
+            ]
+        );
+        assert_eq!(
+            rows[3].result,
+            Err(vec![RowError::DuplicateNumber("DX-2001".to_string())])
+        );
+    }
+
+    #[test]
+    fn reads_files_exported_in_english() {
+        let table = parse_csv(
+            "Order,Customer,Date,Payment method,Channel,Amount,Status,Flagged\n\
+             DX-2101,'=Ann,2024-07-01,Alipay,Online store,12,Awaiting payment,Yes\n",
+        )
+        .unwrap();
+        let rows = validate(&table, &guess_mapping(&table.headers));
+        let order = rows[0].result.as_ref().unwrap();
+        // The `'` the export puts before text starting like a formula is dropped again.
+        assert_eq!(order.customer_name, "=Ann");
+        assert_eq!(order.payment_method, PaymentMethod::Alipay);
+        assert_eq!(order.channel, SalesChannel::OnlineStore);
+        assert_eq!(order.status, OrderStatus::PendingPayment);
+        assert!(order.flagged);
+    }
+
+    #[test]
+    fn reports_missing_required_values() {
+        let table =
+            parse_csv("number,customer,date,method,channel,total\n,张三,,card,store,1\n").unwrap();
+        let rows = validate(&table, &guess_mapping(&table.headers));
+        assert_eq!(
+            rows[0].result,
+            Err(vec![
+                RowError::Missing(ImportField::Number),
+                RowError::Missing(ImportField::PlacedOn),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_status_combinations_the_workflow_cannot_reach() {
+        let table = parse_csv(
+            "number,customer,date,method,channel,total,status,payment,fulfillment\n\
+             DX-2201,张三,2024-07-01,card,store,1,cancelled,refunded,delivered\n\
+             DX-2202,李四,2024-07-01,card,store,1,fulfilled,paid,delivered\n",
+        )
+        .unwrap();
+        let rows = validate(&table, &guess_mapping(&table.headers));
+        assert_eq!(
+            rows[0].result,
+            Err(vec![RowError::Inconsistent(Transition {
+                status: OrderStatus::Cancelled,
+                payment_status: PaymentStatus::Refunded,
+                fulfillment_status: FulfillmentStatus::Delivered,
+            })])
+        );
+        assert!(rows[1].result.is_ok());
+    }
+
+    #[test]
+    fn imports_only_new_orders() {
+        let repository = InMemoryOrderRepository::seeded();
+        let table = table();
+        let mut orders: Vec<Order> = validate(&table, &guess_mapping(&table.headers))
+            .into_iter()
+            .filter_map(|row| row.result.ok())
+            .collect();
+        let mut existing = orders[0].clone();
+        existing.number = "DX-1050".to_string();
+        orders.push(existing);
+
+        let outcome = import(&repository, &orders).unwrap();
+        assert_eq!(outcome.imported, ["DX-2001"]);
+        assert_eq!(outcome.skipped, ["DX-1050"]);
+        assert_eq!(
+            repository.find("DX-2001").unwrap().unwrap().customer_name,
+            "Li, Wei"
+        );
+        assert_ne!(
+            repository.find("DX-1050").unwrap().unwrap().customer_name,
+            "Li, Wei"
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! The orders module holds the order domain model shared by the client and the server, together with the
+//! repository abstraction the server functions in [`api`] are backed by.
+//!
+//! The repository implementations are only compiled for the server (and for tests), so the web client never
+//! ships the seeded fixtures or the SQLite driver.
+
+pub mod api;
+pub mod bulk;
+pub mod edit;
+pub mod export;
+#[cfg(any(feature = "server", test))]
+mod fixtures;
+pub mod import;
+pub mod query;
+mod query_string;
+#[cfg(any(feature = "server", test))]
+pub mod repository;
+#[cfg(feature = "server")]
+pub mod sqlite;
+pub mod transition;
+
+use crate::i18n::I18n;
+use crate::time::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Tags operators can attach to an order from the list and detail views.
+pub const AVAILABLE_TAGS: &[&str] = &["加急", "赠品", "VIP", "缺货", "重复下单", "需回访"];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub number: String,
+    pub placed_on: NaiveDate,
+    pub customer_name: String,
+    pub customer_email: String,
+    pub status: OrderStatus,
+    pub payment_status: PaymentStatus,
+    pub fulfillment_status: FulfillmentStatus,
+    pub payment_method: PaymentMethod,
+    pub channel: SalesChannel,
+    pub total: f32,
+    pub tags: Vec<String>,
+    pub flagged: bool,
+    #[serde(default)]
+    pub line_items: Vec<LineItem>,
+    /// Every payment status the order went through, oldest first.
+    #[serde(default)]
+    pub payment_history: Vec<StatusChange<PaymentStatus>>,
+    /// Every fulfillment status the order went through, oldest first.
+    #[serde(default)]
+    pub fulfillment_history: Vec<StatusChange<FulfillmentStatus>>,
+    /// Internal notes left by operators, oldest first. They are never shown to the customer.
+    #[serde(default)]
+    pub notes: Vec<OrderNote>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LineItem {
+    pub sku: String,
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price: f32,
+}
+
+impl LineItem {
+    pub fn subtotal(&self) -> f32 {
+        self.unit_price * self.quantity as f32
+    }
+}
+
+/// A status an order entered and the day it happened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange<S> {
+    pub status: S,
+    pub on: NaiveDate,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrderNote {
+    pub author: String,
+    pub body: String,
+    pub created_on: NaiveDate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    Draft,
+    PendingPayment,
+    Processing,
+    Fulfilled,
+    Cancelled,
+}
+
+impl OrderStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderStatus::Draft => "草稿",
+            OrderStatus::PendingPayment => "待支付",
+            OrderStatus::Processing => "处理中",
+            OrderStatus::Fulfilled => "已完成",
+            OrderStatus::Cancelled => "已取消",
+        }
+    }
+
+    /// [`Self::label`] in the language of `i18n`. `label` itself stays the Chinese text database sorting
+    /// and search rely on.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.status.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            OrderStatus::Draft => "draft",
+            OrderStatus::PendingPayment => "pending",
+            OrderStatus::Processing => "processing",
+            OrderStatus::Fulfilled => "fulfilled",
+            OrderStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn all() -> &'static [OrderStatus] {
+        &[
+            OrderStatus::Draft,
+            OrderStatus::PendingPayment,
+            OrderStatus::Processing,
+            OrderStatus::Fulfilled,
+            OrderStatus::Cancelled,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(OrderStatus::Draft),
+            "pending" => Some(OrderStatus::PendingPayment),
+            "processing" => Some(OrderStatus::Processing),
+            "fulfilled" => Some(OrderStatus::Fulfilled),
+            "cancelled" => Some(OrderStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentStatus {
+    Pending,
+    Paid,
+    Refunded,
+    Overdue,
+}
+
+impl PaymentStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "待入账",
+            PaymentStatus::Paid => "已支付",
+            PaymentStatus::Refunded => "已退款",
+            PaymentStatus::Overdue => "逾期",
+        }
+    }
+
+    /// [`Self::label`] in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.payment.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Paid => "paid",
+            PaymentStatus::Refunded => "refunded",
+            PaymentStatus::Overdue => "overdue",
+        }
+    }
+
+    pub fn all() -> &'static [PaymentStatus] {
+        &[
+            PaymentStatus::Pending,
+            PaymentStatus::Paid,
+            PaymentStatus::Refunded,
+            PaymentStatus::Overdue,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(PaymentStatus::Pending),
+            "paid" => Some(PaymentStatus::Paid),
+            "refunded" => Some(PaymentStatus::Refunded),
+            "overdue" => Some(PaymentStatus::Overdue),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FulfillmentStatus {
+    Unfulfilled,
+    Picking,
+    Shipped,
+    Delivered,
+    Returned,
+}
+
+impl FulfillmentStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FulfillmentStatus::Unfulfilled => "待打包",
+            FulfillmentStatus::Picking => "拣货中",
+            FulfillmentStatus::Shipped => "运输中",
+            FulfillmentStatus::Delivered => "已签收",
+            FulfillmentStatus::Returned => "已退回",
+        }
+    }
+
+    /// [`Self::label`] in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.fulfillment.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            FulfillmentStatus::Unfulfilled => "unfulfilled",
+            FulfillmentStatus::Picking => "picking",
+            FulfillmentStatus::Shipped => "shipped",
+            FulfillmentStatus::Delivered => "delivered",
+            FulfillmentStatus::Returned => "returned",
+        }
+    }
+
+    pub fn all() -> &'static [FulfillmentStatus] {
+        &[
+            FulfillmentStatus::Unfulfilled,
+            FulfillmentStatus::Picking,
+            FulfillmentStatus::Shipped,
+            FulfillmentStatus::Delivered,
+            FulfillmentStatus::Returned,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "unfulfilled" => Some(FulfillmentStatus::Unfulfilled),
+            "picking" => Some(FulfillmentStatus::Picking),
+            "shipped" => Some(FulfillmentStatus::Shipped),
+            "delivered" => Some(FulfillmentStatus::Delivered),
+            "returned" => Some(FulfillmentStatus::Returned),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SalesChannel {
+    OnlineStore,
+    Marketplace,
+    Wholesale,
+    PopUp,
+    Subscription,
+}
+
+impl SalesChannel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SalesChannel::OnlineStore => "官网商城",
+            SalesChannel::Marketplace => "第三方平台",
+            SalesChannel::Wholesale => "批发",
+            SalesChannel::PopUp => "快闪店",
+            SalesChannel::Subscription => "订阅",
+        }
+    }
+
+    /// [`Self::label`] in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.channel.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            SalesChannel::OnlineStore => "store",
+            SalesChannel::Marketplace => "marketplace",
+            SalesChannel::Wholesale => "wholesale",
+            SalesChannel::PopUp => "popup",
+            SalesChannel::Subscription => "subscription",
+        }
+    }
+
+    pub fn all() -> &'static [SalesChannel] {
+        &[
+            SalesChannel::OnlineStore,
+            SalesChannel::Marketplace,
+            SalesChannel::Wholesale,
+            SalesChannel::PopUp,
+            SalesChannel::Subscription,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "store" => Some(SalesChannel::OnlineStore),
+            "marketplace" => Some(SalesChannel::Marketplace),
+            "wholesale" => Some(SalesChannel::Wholesale),
+            "popup" => Some(SalesChannel::PopUp),
+            "subscription" => Some(SalesChannel::Subscription),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentMethod {
+    CreditCard,
+    BankTransfer,
+    Cash,
+    Paypal,
+    WechatPay,
+    Alipay,
+}
+
+impl PaymentMethod {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaymentMethod::CreditCard => "信用卡",
+            PaymentMethod::BankTransfer => "银行转账",
+            PaymentMethod::Cash => "现金",
+            PaymentMethod::Paypal => "PayPal",
+            PaymentMethod::WechatPay => "微信支付",
+            PaymentMethod::Alipay => "支付宝",
+        }
+    }
+
+    /// [`Self::label`] in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.method.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            PaymentMethod::CreditCard => "card",
+            PaymentMethod::BankTransfer => "transfer",
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::Paypal => "paypal",
+            PaymentMethod::WechatPay => "wechat",
+            PaymentMethod::Alipay => "alipay",
+        }
+    }
+
+    pub fn all() -> &'static [PaymentMethod] {
+        &[
+            PaymentMethod::CreditCard,
+            PaymentMethod::BankTransfer,
+            PaymentMethod::Cash,
+            PaymentMethod::Paypal,
+            PaymentMethod::WechatPay,
+            PaymentMethod::Alipay,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "card" => Some(PaymentMethod::CreditCard),
+            "transfer" => Some(PaymentMethod::BankTransfer),
+            "cash" => Some(PaymentMethod::Cash),
+            "paypal" => Some(PaymentMethod::Paypal),
+            "wechat" => Some(PaymentMethod::WechatPay),
+            "alipay" => Some(PaymentMethod::Alipay),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,626 @@
+use super::{FulfillmentStatus, Order, OrderStatus, PaymentMethod, PaymentStatus, SalesChannel};
+use crate::i18n::I18n;
+use crate::time::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Tag that marks an order as belonging to a VIP customer.
+#[cfg(any(feature = "server", test))]
+pub const VIP_TAG: &str = "VIP";
+
+/// Predefined work queues layered on top of the regular filters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderPipeline {
+    #[default]
+    All,
+    AwaitingFulfillment,
+    Overdue,
+    Vip,
+}
+
+impl OrderPipeline {
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "all" => Some(OrderPipeline::All),
+            "awaiting_fulfillment" => Some(OrderPipeline::AwaitingFulfillment),
+            "overdue" => Some(OrderPipeline::Overdue),
+            "vip" => Some(OrderPipeline::Vip),
+            _ => None,
+        }
+    }
+    pub fn key(&self) -> &'static str {
+        match self {
+            OrderPipeline::All => "all",
+            OrderPipeline::AwaitingFulfillment => "awaiting_fulfillment",
+            OrderPipeline::Overdue => "overdue",
+            OrderPipeline::Vip => "vip",
+        }
+    }
+}
+
+/// Field the order list can be grouped by. Groups are aggregated over every matching order, not only one page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderGroupField {
+    Channel,
+    Status,
+    PaymentMethod,
+}
+
+impl OrderGroupField {
+    pub fn all() -> &'static [OrderGroupField] {
+        &[
+            OrderGroupField::Channel,
+            OrderGroupField::Status,
+            OrderGroupField::PaymentMethod,
+        ]
+    }
+
+    /// Also the id of the orders table column showing the field.
+    pub fn key(&self) -> &'static str {
+        match self {
+            OrderGroupField::Channel => "channel",
+            OrderGroupField::Status => "status",
+            OrderGroupField::PaymentMethod => "method",
+        }
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|field| field.key() == value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderSortField {
+    PlacedOn,
+    Number,
+    Customer,
+    Total,
+}
+
+impl OrderSortField {
+    /// The name shown in the UI, in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(&format!("order.sort.{}", self.key()))
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            OrderSortField::PlacedOn => "placed_on",
+            OrderSortField::Number => "number",
+            OrderSortField::Customer => "customer",
+            OrderSortField::Total => "total",
+        }
+    }
+
+    pub fn all() -> &'static [OrderSortField] {
+        &[
+            OrderSortField::PlacedOn,
+            OrderSortField::Number,
+            OrderSortField::Customer,
+            OrderSortField::Total,
+        ]
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "placed_on" => Some(OrderSortField::PlacedOn),
+            "number" => Some(OrderSortField::Number),
+            "customer" => Some(OrderSortField::Customer),
+            "total" => Some(OrderSortField::Total),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn key(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        }
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        match value {
+            "asc" => Some(SortDirection::Ascending),
+            "desc" => Some(SortDirection::Descending),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderSort {
+    pub field: OrderSortField,
+    pub direction: SortDirection,
+}
+
+impl OrderSort {
+    pub fn new(field: OrderSortField, direction: SortDirection) -> Self {
+        Self { field, direction }
+    }
+
+    /// `field:direction`, e.g. `total:desc`.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.field.key(), self.direction.key())
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        let (field, direction) = value.split_once(':')?;
+        Some(Self::new(
+            OrderSortField::from_key(field)?,
+            SortDirection::from_key(direction)?,
+        ))
+    }
+}
+
+impl Default for OrderSort {
+    fn default() -> Self {
+        Self::new(OrderSortField::PlacedOn, SortDirection::Descending)
+    }
+}
+
+/// Every criterion the orders page can filter, sort and paginate by. Repositories evaluate it so clients
+/// only ever receive a single page.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrderQuery {
+    /// Case-insensitive substring matched against number, customer, email, channel label and tags.
+    pub search: String,
+    pub status: Option<OrderStatus>,
+    pub payment_status: Option<PaymentStatus>,
+    pub fulfillment_status: Option<FulfillmentStatus>,
+    pub channel: Option<SalesChannel>,
+    pub payment_method: Option<PaymentMethod>,
+    /// Orders must carry every listed tag.
+    pub tags: Vec<String>,
+    /// Inclusive lower bound of the placement date.
+    pub placed_from: Option<NaiveDate>,
+    /// Inclusive upper bound of the placement date.
+    pub placed_to: Option<NaiveDate>,
+    /// Ignored unless positive.
+    pub min_total: f32,
+    pub flagged_only: bool,
+    pub pipeline: OrderPipeline,
+    /// Applied in order; ties fall back to the order number so pages are stable.
+    pub sort: Vec<OrderSort>,
+    /// Asks for [`OrderPage::groups`]. Does not change which orders match or how they are sorted.
+    pub group_by: Option<OrderGroupField>,
+    /// 1-based; clamped to the last page by the repository.
+    pub page: usize,
+    /// Clamped to `1..=200` by the repository, so one request cannot ask for every order.
+    pub page_size: usize,
+}
+
+impl Default for OrderQuery {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            status: None,
+            payment_status: None,
+            fulfillment_status: None,
+            channel: None,
+            payment_method: None,
+            tags: Vec::new(),
+            placed_from: None,
+            placed_to: None,
+            min_total: 0.0,
+            flagged_only: false,
+            pipeline: OrderPipeline::All,
+            sort: vec![OrderSort::default()],
+            group_by: None,
+            page: 1,
+            page_size: 20,
+        }
+    }
+}
+
+/// Aggregates over every order matching a query, not only the returned page.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderMetrics {
+    pub gross_revenue: f32,
+    pub average_order_value: f32,
+    /// Orders whose payment is pending or overdue.
+    pub outstanding_payments: usize,
+    /// Orders that are neither delivered nor returned.
+    pub fulfillment_queue: usize,
+    pub flagged: usize,
+}
+
+/// The matching orders sharing one value of [`OrderQuery::group_by`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderGroup {
+    /// The canonical label of the value, which is also the orders table's cell text for it.
+    pub key: String,
+    pub count: usize,
+    pub gross_revenue: f32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderPage {
+    pub orders: Vec<Order>,
+    /// Number of orders matching the query.
+    pub total: usize,
+    /// Number of orders in the repository regardless of the query.
+    pub unfiltered_total: usize,
+    /// The page actually returned after clamping.
+    pub page: usize,
+    pub page_count: usize,
+    pub metrics: OrderMetrics,
+    /// One entry per value of [`OrderQuery::group_by`] that matching orders have, in the order the values are
+    /// declared. Empty when the query does not group.
+    pub groups: Vec<OrderGroup>,
+}
+
+/// In-memory evaluation of queries. Only the server (and the tests) need it; the client receives finished pages.
+#[cfg(any(feature = "server", test))]
+mod evaluate {
+    use super::*;
+    use std::cmp::Ordering;
+
+    impl OrderPipeline {
+        fn matches(&self, order: &Order) -> bool {
+            match self {
+                OrderPipeline::All => true,
+                OrderPipeline::AwaitingFulfillment => order.fulfillment_status.is_open(),
+                OrderPipeline::Overdue => order.payment_status == PaymentStatus::Overdue,
+                OrderPipeline::Vip => order.tags.iter().any(|tag| tag == VIP_TAG),
+            }
+        }
+    }
+
+    impl OrderSort {
+        fn compare(&self, a: &Order, b: &Order) -> Ordering {
+            let ordering = match self.field {
+                OrderSortField::PlacedOn => a.placed_on.cmp(&b.placed_on),
+                OrderSortField::Number => a.number.cmp(&b.number),
+                OrderSortField::Customer => a.customer_name.cmp(&b.customer_name),
+                OrderSortField::Total => a.total.total_cmp(&b.total),
+            };
+            match self.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        }
+    }
+
+    impl OrderQuery {
+        pub fn matches(&self, order: &Order) -> bool {
+            let search_term = self.search.trim().to_lowercase();
+            let matches_search =
+                search_term.is_empty() || search_text(order).contains(&search_term);
+
+            matches_search
+                && self.status.is_none_or(|expected| order.status == expected)
+                && self
+                    .payment_status
+                    .is_none_or(|expected| order.payment_status == expected)
+                && self
+                    .fulfillment_status
+                    .is_none_or(|expected| order.fulfillment_status == expected)
+                && self
+                    .channel
+                    .is_none_or(|expected| order.channel == expected)
+                && self
+                    .payment_method
+                    .is_none_or(|expected| order.payment_method == expected)
+                && self
+                    .tags
+                    .iter()
+                    .all(|tag| order.tags.iter().any(|candidate| candidate == tag))
+                && self.placed_from.is_none_or(|from| order.placed_on >= from)
+                && self.placed_to.is_none_or(|to| order.placed_on <= to)
+                && (self.min_total <= 0.0 || order.total >= self.min_total)
+                && (!self.flagged_only || order.flagged)
+                && self.pipeline.matches(order)
+        }
+
+        pub fn compare(&self, a: &Order, b: &Order) -> Ordering {
+            self.sort
+                .iter()
+                .map(|sort| sort.compare(a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.number.cmp(&b.number))
+        }
+
+        /// The number of pages needed for `total` matches; never zero so an empty result still has page 1.
+        pub fn page_count(&self, total: usize) -> usize {
+            total.div_ceil(self.effective_page_size()).max(1)
+        }
+
+        /// The most orders one page can hold.
+        pub const MAX_PAGE_SIZE: usize = 200;
+
+        pub fn effective_page_size(&self) -> usize {
+            self.page_size.clamp(1, Self::MAX_PAGE_SIZE)
+        }
+
+        pub fn effective_page(&self, total: usize) -> usize {
+            self.page.clamp(1, self.page_count(total))
+        }
+
+        /// The orders matching the filters, sorted, without pagination.
+        pub fn select(&self, orders: Vec<Order>) -> Vec<Order> {
+            let mut matching: Vec<Order> = orders
+                .into_iter()
+                .filter(|order| self.matches(order))
+                .collect();
+            matching.sort_by(|a, b| self.compare(a, b));
+            matching
+        }
+
+        pub fn evaluate(&self, orders: Vec<Order>) -> OrderPage {
+            let unfiltered_total = orders.len();
+            let matching = self.select(orders);
+
+            let total = matching.len();
+            let metrics = OrderMetrics::from_orders(&matching);
+            let groups = self
+                .group_by
+                .map(|field| field.groups(&matching))
+                .unwrap_or_default();
+            let page = self.effective_page(total);
+            let page_size = self.effective_page_size();
+            let orders = matching
+                .into_iter()
+                .skip((page - 1) * page_size)
+                .take(page_size)
+                .collect();
+
+            OrderPage {
+                orders,
+                total,
+                unfiltered_total,
+                page,
+                page_count: self.page_count(total),
+                metrics,
+                groups,
+            }
+        }
+    }
+
+    impl OrderGroupField {
+        /// The stored key and canonical label of every value of the field, in declaration order.
+        pub(crate) fn values(&self) -> Vec<(&'static str, &'static str)> {
+            match self {
+                OrderGroupField::Channel => SalesChannel::all()
+                    .iter()
+                    .map(|channel| (channel.key(), channel.label()))
+                    .collect(),
+                OrderGroupField::Status => OrderStatus::all()
+                    .iter()
+                    .map(|status| (status.key(), status.label()))
+                    .collect(),
+                OrderGroupField::PaymentMethod => PaymentMethod::all()
+                    .iter()
+                    .map(|method| (method.key(), method.label()))
+                    .collect(),
+            }
+        }
+
+        fn label_of(&self, order: &Order) -> &'static str {
+            match self {
+                OrderGroupField::Channel => order.channel.label(),
+                OrderGroupField::Status => order.status.label(),
+                OrderGroupField::PaymentMethod => order.payment_method.label(),
+            }
+        }
+
+        /// Counts and sums `orders` per value of the field, leaving out values no order has.
+        fn groups(&self, orders: &[Order]) -> Vec<OrderGroup> {
+            self.values()
+                .into_iter()
+                .filter_map(|(_, label)| {
+                    let members = orders.iter().filter(|order| self.label_of(order) == label);
+                    let (count, gross_revenue) = members.fold((0, 0.0), |(count, sum), order| {
+                        (count + 1, sum + order.total)
+                    });
+                    (count > 0).then(|| OrderGroup {
+                        key: label.to_string(),
+                        count,
+                        gross_revenue,
+                    })
+                })
+                .collect()
+        }
+    }
+
+    impl OrderMetrics {
+        pub fn from_orders(orders: &[Order]) -> Self {
+            let gross_revenue = orders.iter().map(|order| order.total).sum::<f32>();
+            Self {
+                gross_revenue,
+                average_order_value: Self::average(gross_revenue, orders.len()),
+                outstanding_payments: orders
+                    .iter()
+                    .filter(|order| order.payment_status.is_outstanding())
+                    .count(),
+                fulfillment_queue: orders
+                    .iter()
+                    .filter(|order| order.fulfillment_status.is_open())
+                    .count(),
+                flagged: orders.iter().filter(|order| order.flagged).count(),
+            }
+        }
+
+        pub(crate) fn average(gross_revenue: f32, count: usize) -> f32 {
+            if count > 0 {
+                gross_revenue / count as f32
+            } else {
+                0.0
+            }
+        }
+    }
+
+    impl PaymentStatus {
+        /// Payments that still have to be collected.
+        pub(crate) fn is_outstanding(&self) -> bool {
+            matches!(self, PaymentStatus::Pending | PaymentStatus::Overdue)
+        }
+    }
+
+    impl FulfillmentStatus {
+        /// Orders still sitting in the fulfillment queue.
+        pub(crate) fn is_open(&self) -> bool {
+            !matches!(
+                self,
+                FulfillmentStatus::Delivered | FulfillmentStatus::Returned
+            )
+        }
+    }
+
+    fn search_text(order: &Order) -> String {
+        format!(
+            "{} {} {} {} {}",
+            order.number,
+            order.customer_name,
+            order.customer_email,
+            order.channel.label(),
+            order.tags.join(" ")
+        )
+        .to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::fixtures::seeded_orders;
+
+    fn query() -> OrderQuery {
+        OrderQuery {
+            page_size: 5,
+            ..OrderQuery::default()
+        }
+    }
+
+    #[test]
+    fn paginates_and_clamps_pages() {
+        let page = query().evaluate(seeded_orders());
+        assert_eq!(page.total, 12);
+        assert_eq!(page.unfiltered_total, 12);
+        assert_eq!(page.page_count, 3);
+        assert_eq!(page.orders.len(), 5);
+        assert_eq!(page.orders[0].number, "DX-1050");
+
+        let last = OrderQuery { page: 9, ..query() }.evaluate(seeded_orders());
+        assert_eq!(last.page, 3);
+        assert_eq!(last.orders.len(), 2);
+
+        let empty = OrderQuery {
+            page_size: 0,
+            ..query()
+        };
+        assert_eq!(empty.evaluate(seeded_orders()).orders.len(), 1);
+        let huge = OrderQuery {
+            page_size: 10_000,
+            ..query()
+        };
+        assert_eq!(huge.effective_page_size(), OrderQuery::MAX_PAGE_SIZE);
+        assert_eq!(huge.page_count(450), 3);
+    }
+
+    #[test]
+    fn combines_filters() {
+        let page = OrderQuery {
+            search: "example.com".to_string(),
+            tags: vec!["VIP".to_string()],
+            min_total: 700.0,
+            ..query()
+        }
+        .evaluate(seeded_orders());
+        let numbers: Vec<_> = page
+            .orders
+            .iter()
+            .map(|order| order.number.as_str())
+            .collect();
+        assert_eq!(numbers, ["DX-1050", "DX-1047", "DX-1040"]);
+        assert_eq!(page.metrics.gross_revenue, 1288.0 + 1560.0 + 786.2);
+        assert_eq!(page.metrics.flagged, 1);
+    }
+
+    #[test]
+    fn pipelines_and_date_bounds() {
+        let overdue = OrderQuery {
+            pipeline: OrderPipeline::Overdue,
+            ..query()
+        }
+        .evaluate(seeded_orders());
+        assert_eq!(overdue.total, 1);
+        assert_eq!(overdue.orders[0].number, "DX-1046");
+
+        let bounded = OrderQuery {
+            placed_from: NaiveDate::from_ymd_opt(2024, 7, 15),
+            placed_to: NaiveDate::from_ymd_opt(2024, 7, 17),
+            ..query()
+        }
+        .evaluate(seeded_orders());
+        assert_eq!(bounded.total, 3);
+    }
+
+    #[test]
+    fn groups_every_matching_order_not_just_the_page() {
+        let page = OrderQuery {
+            group_by: Some(OrderGroupField::Status),
+            ..query()
+        }
+        .evaluate(seeded_orders());
+        assert_eq!(page.orders.len(), 5);
+        assert_eq!(
+            page.groups.iter().map(|group| group.count).sum::<usize>(),
+            page.total
+        );
+        let revenue: f32 = page.groups.iter().map(|group| group.gross_revenue).sum();
+        assert!((revenue - page.metrics.gross_revenue).abs() < 0.01);
+        let keys: Vec<_> = page.groups.iter().map(|group| group.key.as_str()).collect();
+        let declared: Vec<_> = OrderStatus::all()
+            .iter()
+            .map(OrderStatus::label)
+            .filter(|label| keys.contains(label))
+            .collect();
+        assert_eq!(keys, declared);
+
+        let processing = page
+            .groups
+            .iter()
+            .find(|group| group.key == OrderStatus::Processing.label())
+            .unwrap();
+        let expected = seeded_orders()
+            .iter()
+            .filter(|order| order.status == OrderStatus::Processing)
+            .count();
+        assert_eq!(processing.count, expected);
+        assert!(query().evaluate(seeded_orders()).groups.is_empty());
+    }
+
+    #[test]
+    fn sorts_by_multiple_keys() {
+        let page = OrderQuery {
+            sort: vec![
+                OrderSort::new(OrderSortField::Total, SortDirection::Descending),
+                OrderSort::new(OrderSortField::Number, SortDirection::Ascending),
+            ],
+            ..query()
+        }
+        .evaluate(seeded_orders());
+        assert_eq!(page.orders[0].number, "DX-1042");
+        assert!(page
+            .orders
+            .windows(2)
+            .all(|pair| pair[0].total >= pair[1].total));
+        assert_eq!(
+            OrderSort::from_key("total:desc"),
+            Some(OrderSort::new(
+                OrderSortField::Total,
+                SortDirection::Descending
+            ))
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! The URL form of an [`OrderQuery`], used as the query string of the orders route so a filtered list can be
+//! shared as a link, e.g. `/orders?pipeline=vip&status=pending&from=2024-06-01&to=2024-06-07&page=2`.
+//!
+//! Only criteria that differ from [`OrderQuery::default`] are written, and unknown keys or invalid values are
+//! ignored when parsing, so hand-edited links degrade to the default filters instead of failing. The page size is
+//! not part of the link.
+
+use super::import::parse_date;
+use super::query::{OrderGroupField, OrderPipeline, OrderQuery, OrderSort};
+use super::{FulfillmentStatus, OrderStatus, PaymentMethod, PaymentStatus, SalesChannel};
+use std::fmt::{self, Display, Formatter};
+
+/// Characters that would be read as structure inside a value.
+const RESERVED: &[char] = &['%', '&', '=', ','];
+
+/// The router percent-decodes the whole query string once before parsing it, so reserved characters are escaped
+/// twice: `&` is written as `%2526`, which reaches [`OrderQuery::from`] as `%26`.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if RESERVED.contains(&ch) {
+            escaped.push_str(&format!("%25{:02X}", ch as u32));
+        } else {
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+impl Display for OrderQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let defaults = OrderQuery::default();
+        let mut pairs: Vec<(&str, String)> = Vec::new();
+        if !self.search.is_empty() {
+            pairs.push(("q", escape(&self.search)));
+        }
+        if let Some(status) = self.status {
+            pairs.push(("status", status.key().to_string()));
+        }
+        if let Some(status) = self.payment_status {
+            pairs.push(("payment", status.key().to_string()));
+        }
+        if let Some(status) = self.fulfillment_status {
+            pairs.push(("fulfillment", status.key().to_string()));
+        }
+        if let Some(channel) = self.channel {
+            pairs.push(("channel", channel.key().to_string()));
+        }
+        if let Some(method) = self.payment_method {
+            pairs.push(("method", method.key().to_string()));
+        }
+        if !self.tags.is_empty() {
+            let tags = self.tags.iter().map(|tag| escape(tag)).collect::<Vec<_>>();
+            pairs.push(("tags", tags.join(",")));
+        }
+        if let Some(date) = self.placed_from {
+            pairs.push(("from", date.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(date) = self.placed_to {
+            pairs.push(("to", date.format("%Y-%m-%d").to_string()));
+        }
+        if self.min_total > 0.0 {
+            pairs.push(("min_total", self.min_total.to_string()));
+        }
+        if self.flagged_only {
+            pairs.push(("flagged", "1".to_string()));
+        }
+        if self.pipeline != defaults.pipeline {
+            pairs.push(("pipeline", self.pipeline.key().to_string()));
+        }
+        if self.sort != defaults.sort {
+            let sort = self.sort.iter().map(OrderSort::key).collect::<Vec<_>>();
+            pairs.push(("sort", sort.join(",")));
+        }
+        if let Some(field) = self.group_by {
+            pairs.push(("group", field.key().to_string()));
+        }
+        if self.page > 1 {
+            pairs.push(("page", self.page.to_string()));
+        }
+
+        for (index, (key, value)) in pairs.iter().enumerate() {
+            if index > 0 {
+                f.write_str("&")?;
+            }
+            write!(f, "{key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for OrderQuery {
+    fn from(query: &str) -> Self {
+        let mut parsed = OrderQuery::default();
+        for pair in query.split('&') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            match key {
+                "q" => parsed.search = unescape(value),
+                "status" => parsed.status = OrderStatus::from_key(value),
+                "payment" => parsed.payment_status = PaymentStatus::from_key(value),
+                "fulfillment" => parsed.fulfillment_status = FulfillmentStatus::from_key(value),
+                "channel" => parsed.channel = SalesChannel::from_key(value),
+                "method" => parsed.payment_method = PaymentMethod::from_key(value),
+                "tags" => {
+                    parsed.tags = value
+                        .split(',')
+                        .map(unescape)
+                        .filter(|tag| !tag.is_empty())
+                        .collect()
+                }
+                "from" => parsed.placed_from = parse_date(value),
+                "to" => parsed.placed_to = parse_date(value),
+                "min_total" => {
+                    parsed.min_total = value
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|total| total.is_finite() && *total > 0.0)
+                        .unwrap_or_default()
+                }
+                "flagged" => parsed.flagged_only = value == "1",
+                "pipeline" => parsed.pipeline = OrderPipeline::from_key(value).unwrap_or_default(),
+                "sort" => {
+                    let sort: Vec<OrderSort> =
+                        value.split(',').filter_map(OrderSort::from_key).collect();
+                    if !sort.is_empty() {
+                        parsed.sort = sort;
+                    }
+                }
+                "group" => parsed.group_by = OrderGroupField::from_key(value),
+                "page" => parsed.page = value.parse::<usize>().unwrap_or(1).max(1),
+                _ => {}
+            }
+        }
+        parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::query::{OrderSortField, SortDirection};
+    use crate::time::NaiveDate;
+    use crate::Route;
+
+    /// Writes the orders route as a URL and parses it back, the way a shared link is opened.
+    fn through_router(query: &OrderQuery) -> OrderQuery {
+        let url = Route::Orders {
+            query: query.clone(),
+        }
+        .to_string();
+        match url.parse::<Route>() {
+            Ok(Route::Orders { query }) => query,
+            other => panic!("{url} parsed as {other:?}"),
+        }
+    }
+
+    #[test]
+    fn default_query_has_an_empty_query_string() {
+        assert_eq!(OrderQuery::default().to_string(), "");
+        assert_eq!(OrderQuery::from(""), OrderQuery::default());
+    }
+
+    #[test]
+    fn filters_sort_grouping_and_page_survive_a_round_trip() {
+        let query = OrderQuery {
+            search: "a&b=c, 100%".to_string(),
+            status: Some(OrderStatus::PendingPayment),
+            payment_status: Some(PaymentStatus::Paid),
+            channel: Some(SalesChannel::Marketplace),
+            tags: vec!["VIP".to_string(), "加急".to_string()],
+            placed_from: NaiveDate::from_ymd_opt(2024, 6, 1),
+            placed_to: NaiveDate::from_ymd_opt(2024, 6, 7),
+            min_total: 150.5,
+            flagged_only: true,
+            pipeline: OrderPipeline::Overdue,
+            sort: vec![
+                OrderSort::new(OrderSortField::Total, SortDirection::Descending),
+                OrderSort::new(OrderSortField::Customer, SortDirection::Ascending),
+            ],
+            group_by: Some(OrderGroupField::PaymentMethod),
+            page: 3,
+            ..OrderQuery::default()
+        };
+
+        assert_eq!(through_router(&query), query);
+        assert!(query
+            .to_string()
+            .starts_with("q=a%2526b%253Dc%252C 100%2525&status=pending"));
+    }
+
+    #[test]
+    fn unknown_keys_and_invalid_values_fall_back_to_defaults() {
+        let parsed =
+            OrderQuery::from("status=lost&page=0&sort=bogus&min_total=-5&from=2024-13-01&utm=x&q");
+        assert_eq!(parsed, OrderQuery::default());
+    }
+}
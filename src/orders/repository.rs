@@ -0,0 +1,268 @@
+use super::query::{OrderPage, OrderQuery};
+use super::transition::TransitionError;
+use super::{fixtures::seeded_orders, Order};
+use std::cmp::Reverse;
+use std::fmt::{self, Display, Formatter};
+use std::sync::RwLock;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepositoryError {
+    /// The storage backend failed, e.g. the database could not be opened or a record could not be decoded.
+    Storage(String),
+    /// No order with the given number exists.
+    NotFound(String),
+    /// The requested change is not a legal status transition for the stored order.
+    Transition(TransitionError),
+    /// The stored order no longer is what the change was based on, e.g. it was edited after a bulk update that
+    /// is being undone.
+    Conflict(String),
+}
+
+impl Display for RepositoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::Storage(message) => write!(f, "order storage failed: {message}"),
+            RepositoryError::NotFound(number) => write!(f, "order {number} does not exist"),
+            RepositoryError::Transition(err) => err.fmt(f),
+            RepositoryError::Conflict(number) => {
+                write!(f, "order {number} changed in the meantime")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {}
+
+impl From<TransitionError> for RepositoryError {
+    fn from(err: TransitionError) -> Self {
+        RepositoryError::Transition(err)
+    }
+}
+
+/// Storage for orders. Implementations must be shareable across the server's request handlers.
+pub trait OrderRepository: Send + Sync {
+    /// Every stored order, newest first.
+    fn list(&self) -> Result<Vec<Order>, RepositoryError>;
+
+    /// The requested page of orders matching `query`. The default implementation evaluates the query in memory
+    /// over [`OrderRepository::list`]; backends that can filter natively should override it.
+    fn query(&self, query: &OrderQuery) -> Result<OrderPage, RepositoryError> {
+        Ok(query.evaluate(self.list()?))
+    }
+
+    /// Every order matching `query`'s filters in its sort order, ignoring pagination.
+    fn matching(&self, query: &OrderQuery) -> Result<Vec<Order>, RepositoryError> {
+        Ok(query.select(self.list()?))
+    }
+
+    /// The order with the given number, if there is one.
+    fn find(&self, number: &str) -> Result<Option<Order>, RepositoryError>;
+
+    /// Stores `order`, replacing the stored order with the same number.
+    #[allow(dead_code)]
+    fn save(&self, order: &Order) -> Result<(), RepositoryError>;
+
+    /// Stores `order` unless its number is already taken, and returns whether it was stored. Checking and storing
+    /// are one step, so of two concurrent inserts with the same number only one succeeds and the stored order is
+    /// never overwritten.
+    fn insert_new(&self, order: &Order) -> Result<bool, RepositoryError>;
+
+    /// Loads the order, applies `change` and saves the result, which is also returned. Nothing is saved when
+    /// `change` fails. The whole read-modify-write is atomic: concurrent updates to the same order run one after
+    /// the other, so neither change is lost.
+    fn update(
+        &self,
+        number: &str,
+        change: &mut dyn FnMut(&mut Order) -> Result<(), RepositoryError>,
+    ) -> Result<Order, RepositoryError>;
+}
+
+/// Keeps orders in process memory. Used when no database is configured and in tests.
+pub struct InMemoryOrderRepository {
+    orders: RwLock<Vec<Order>>,
+}
+
+impl InMemoryOrderRepository {
+    pub fn new(orders: Vec<Order>) -> Self {
+        Self {
+            orders: RwLock::new(orders),
+        }
+    }
+
+    pub fn seeded() -> Self {
+        Self::new(seeded_orders())
+    }
+}
+
+impl OrderRepository for InMemoryOrderRepository {
+    fn list(&self) -> Result<Vec<Order>, RepositoryError> {
+        let orders = self
+            .orders
+            .read()
+            .map_err(|err| RepositoryError::Storage(err.to_string()))?;
+        let mut listed = orders.clone();
+        listed.sort_by_key(|order| Reverse(order.placed_on));
+        Ok(listed)
+    }
+
+    fn find(&self, number: &str) -> Result<Option<Order>, RepositoryError> {
+        let orders = self
+            .orders
+            .read()
+            .map_err(|err| RepositoryError::Storage(err.to_string()))?;
+        Ok(orders.iter().find(|order| order.number == number).cloned())
+    }
+
+    fn save(&self, order: &Order) -> Result<(), RepositoryError> {
+        let mut orders = self
+            .orders
+            .write()
+            .map_err(|err| RepositoryError::Storage(err.to_string()))?;
+        match orders
+            .iter_mut()
+            .find(|stored| stored.number == order.number)
+        {
+            Some(stored) => *stored = order.clone(),
+            None => orders.push(order.clone()),
+        }
+        Ok(())
+    }
+
+    fn insert_new(&self, order: &Order) -> Result<bool, RepositoryError> {
+        let mut orders = self
+            .orders
+            .write()
+            .map_err(|err| RepositoryError::Storage(err.to_string()))?;
+        if orders.iter().any(|stored| stored.number == order.number) {
+            return Ok(false);
+        }
+        orders.push(order.clone());
+        Ok(true)
+    }
+
+    fn update(
+        &self,
+        number: &str,
+        change: &mut dyn FnMut(&mut Order) -> Result<(), RepositoryError>,
+    ) -> Result<Order, RepositoryError> {
+        let mut orders = self
+            .orders
+            .write()
+            .map_err(|err| RepositoryError::Storage(err.to_string()))?;
+        let stored = orders
+            .iter_mut()
+            .find(|stored| stored.number == number)
+            .ok_or_else(|| RepositoryError::NotFound(number.to_string()))?;
+        let mut order = stored.clone();
+        change(&mut order)?;
+        *stored = order.clone();
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::transition::OrderAction;
+
+    #[test]
+    fn seeded_repository_lists_newest_first() {
+        let repository = InMemoryOrderRepository::seeded();
+        let orders = repository.list().unwrap();
+        assert_eq!(orders.len(), seeded_orders().len());
+        assert!(orders
+            .windows(2)
+            .all(|pair| pair[0].placed_on >= pair[1].placed_on));
+        assert_eq!(orders[0].number, "DX-1050");
+    }
+
+    #[test]
+    fn default_query_evaluates_over_listed_orders() {
+        let repository = InMemoryOrderRepository::seeded();
+        let query = OrderQuery {
+            flagged_only: true,
+            page_size: 2,
+            ..OrderQuery::default()
+        };
+        let page = repository.query(&query).unwrap();
+        assert_eq!(page.total, 4);
+        assert_eq!(page.page_count, 2);
+        assert_eq!(page.metrics.flagged, 4);
+        assert_eq!(page.orders.len(), 2);
+
+        let matching = repository.matching(&query).unwrap();
+        assert_eq!(matching.len(), 4);
+        assert_eq!(matching[..2], page.orders[..]);
+    }
+
+    #[test]
+    fn insert_new_keeps_existing_orders() {
+        let repository = InMemoryOrderRepository::seeded();
+        let mut order = repository.find("DX-1050").unwrap().unwrap();
+        order.customer_name = "覆盖".to_string();
+        assert!(!repository.insert_new(&order).unwrap());
+        assert_ne!(
+            repository.find("DX-1050").unwrap().unwrap().customer_name,
+            "覆盖"
+        );
+
+        order.number = "DX-3001".to_string();
+        assert!(repository.insert_new(&order).unwrap());
+        assert!(!repository.insert_new(&order).unwrap());
+        assert_eq!(repository.list().unwrap().len(), seeded_orders().len() + 1);
+    }
+
+    #[test]
+    fn update_saves_changes_and_reports_missing_orders() {
+        let repository = InMemoryOrderRepository::seeded();
+        let updated = repository
+            .update("DX-1049", &mut |order| {
+                order.tags.push("VIP".to_string());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(updated.tags, ["需回访", "VIP"]);
+        assert_eq!(repository.find("DX-1049").unwrap(), Some(updated));
+
+        assert_eq!(
+            repository.update("DX-0000", &mut |order| {
+                order.flagged = true;
+                Ok(())
+            }),
+            Err(RepositoryError::NotFound("DX-0000".to_string()))
+        );
+
+        let delivered = repository.find("DX-1047").unwrap();
+        let result = repository.update("DX-1047", &mut |order| {
+            order.apply(OrderAction::Cancel, order.placed_on)?;
+            Ok(())
+        });
+        assert!(matches!(result, Err(RepositoryError::Transition(_))));
+        assert_eq!(repository.find("DX-1047").unwrap(), delivered);
+        assert_eq!(repository.list().unwrap().len(), seeded_orders().len());
+    }
+
+    #[test]
+    fn overlapping_updates_to_one_order_all_survive() {
+        let repository = InMemoryOrderRepository::seeded();
+        std::thread::scope(|scope| {
+            for tag in ["甲", "乙", "丙", "丁"] {
+                let repository = &repository;
+                scope.spawn(move || {
+                    repository
+                        .update("DX-1048", &mut |order| {
+                            // Widens the window between reading and writing the order.
+                            std::thread::sleep(std::time::Duration::from_millis(5));
+                            order.tags.push(tag.to_string());
+                            Ok(())
+                        })
+                        .unwrap();
+                });
+            }
+        });
+        let tags = repository.find("DX-1048").unwrap().unwrap().tags;
+        for tag in ["甲", "乙", "丙", "丁"] {
+            assert!(tags.iter().any(|existing| existing == tag), "{tag} lost");
+        }
+    }
+}
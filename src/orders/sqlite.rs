@@ -0,0 +1,807 @@
+use super::fixtures::seeded_orders;
+use super::query::{
+    OrderGroup, OrderGroupField, OrderMetrics, OrderPage, OrderPipeline, OrderQuery,
+    OrderSortField, SortDirection, VIP_TAG,
+};
+use super::repository::{OrderRepository, RepositoryError};
+use super::{
+    FulfillmentStatus, LineItem, Order, OrderNote, OrderStatus, PaymentMethod, PaymentStatus,
+    SalesChannel, StatusChange,
+};
+use crate::time::NaiveDate;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::{FromSql, Type, Value, ValueRef};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS orders (
+    number TEXT PRIMARY KEY NOT NULL,
+    placed_on INTEGER NOT NULL,
+    customer_name TEXT NOT NULL,
+    customer_email TEXT NOT NULL,
+    status TEXT NOT NULL,
+    payment_status TEXT NOT NULL,
+    fulfillment_status TEXT NOT NULL,
+    payment_method TEXT NOT NULL,
+    channel TEXT NOT NULL,
+    total REAL NOT NULL,
+    tags TEXT NOT NULL,
+    flagged INTEGER NOT NULL,
+    details TEXT NOT NULL DEFAULT '{}'
+);
+CREATE INDEX IF NOT EXISTS orders_placed_on ON orders (placed_on);
+";
+
+const SELECT_COLUMNS: &str = "number, placed_on, customer_name, customer_email, status, \
+    payment_status, fulfillment_status, payment_method, channel, total, tags, flagged, details";
+
+/// Line items, status history and notes are only ever read together with their order, so they are stored as
+/// one JSON document instead of in child tables.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct OrderDetails {
+    line_items: Vec<LineItem>,
+    payment_history: Vec<StatusChange<PaymentStatus>>,
+    fulfillment_history: Vec<StatusChange<FulfillmentStatus>>,
+    notes: Vec<OrderNote>,
+}
+
+/// Persists orders in a SQLite database. Dates are stored as days since the Unix epoch and tags as a JSON
+/// array so they stay comparable and searchable from SQL.
+pub struct SqliteOrderRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteOrderRepository {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        let connection = Connection::open(path).map_err(storage_error)?;
+        Self::from_connection(connection)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, RepositoryError> {
+        // Writers from other processes sharing the file wait for each other instead of failing.
+        connection
+            .busy_timeout(Duration::from_secs(5))
+            .map_err(storage_error)?;
+        // SQLite's `lower()` only folds ASCII letters; search folds case with Rust's rules, as the in-memory
+        // repository does, so `Élodie` is found by `élodie` in both.
+        connection
+            .create_scalar_function(
+                "fold_case",
+                1,
+                FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+                |context| Ok(context.get::<String>(0)?.to_lowercase()),
+            )
+            .map_err(storage_error)?;
+        connection.execute_batch(SCHEMA).map_err(storage_error)?;
+        // Databases created before order details were tracked lack the column.
+        if connection.prepare("SELECT details FROM orders").is_err() {
+            connection
+                .execute_batch("ALTER TABLE orders ADD COLUMN details TEXT NOT NULL DEFAULT '{}'")
+                .map_err(storage_error)?;
+        }
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Inserts the demo fixtures when the database does not contain any order yet.
+    pub fn seed_if_empty(&self) -> Result<(), RepositoryError> {
+        let mut connection = self.lock()?;
+        let count: i64 = connection
+            .query_row("SELECT COUNT(*) FROM orders", [], |row| row.get(0))
+            .map_err(storage_error)?;
+        if count > 0 {
+            return Ok(());
+        }
+
+        let transaction = connection.transaction().map_err(storage_error)?;
+        for order in seeded_orders() {
+            insert_order(&transaction, &order, true)?;
+        }
+        transaction.commit().map_err(storage_error)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, RepositoryError> {
+        self.connection
+            .lock()
+            .map_err(|err| RepositoryError::Storage(err.to_string()))
+    }
+}
+
+impl OrderRepository for SqliteOrderRepository {
+    fn list(&self) -> Result<Vec<Order>, RepositoryError> {
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {SELECT_COLUMNS} FROM orders ORDER BY placed_on DESC, number DESC"
+            ))
+            .map_err(storage_error)?;
+        let rows = statement.query_map([], read_order).map_err(storage_error)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(storage_error)
+    }
+
+    fn query(&self, query: &OrderQuery) -> Result<OrderPage, RepositoryError> {
+        let (clause, mut values) = where_clause(query);
+        let connection = self.lock()?;
+
+        let unfiltered_total: i64 = connection
+            .query_row("SELECT COUNT(*) FROM orders", [], |row| row.get(0))
+            .map_err(storage_error)?;
+        let outstanding = key_list(
+            PaymentStatus::all()
+                .iter()
+                .filter(|status| status.is_outstanding())
+                .map(PaymentStatus::key),
+        );
+        let open = key_list(
+            FulfillmentStatus::all()
+                .iter()
+                .filter(|status| status.is_open())
+                .map(FulfillmentStatus::key),
+        );
+        let (total, gross_revenue, outstanding_payments, fulfillment_queue, flagged) = connection
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*), COALESCE(SUM(total), 0), \
+                     COALESCE(SUM(payment_status IN ({outstanding})), 0), \
+                     COALESCE(SUM(fulfillment_status IN ({open})), 0), \
+                     COALESCE(SUM(flagged), 0) \
+                     FROM orders WHERE {clause}"
+                ),
+                params_from_iter(values.iter()),
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)? as usize,
+                        row.get::<_, f64>(1)? as f32,
+                        row.get::<_, i64>(2)? as usize,
+                        row.get::<_, i64>(3)? as usize,
+                        row.get::<_, i64>(4)? as usize,
+                    ))
+                },
+            )
+            .map_err(storage_error)?;
+        let groups = match query.group_by {
+            Some(field) => query_groups(&connection, field, &clause, &values)?,
+            None => Vec::new(),
+        };
+
+        let page = query.effective_page(total);
+        let page_size = query.effective_page_size();
+        values.push(Value::Integer(page_size as i64));
+        values.push(Value::Integer(((page - 1) * page_size) as i64));
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {SELECT_COLUMNS} FROM orders WHERE {clause} ORDER BY {} LIMIT ? OFFSET ?",
+                order_by(query)
+            ))
+            .map_err(storage_error)?;
+        let orders = statement
+            .query_map(params_from_iter(values.iter()), read_order)
+            .map_err(storage_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(storage_error)?;
+
+        Ok(OrderPage {
+            orders,
+            total,
+            unfiltered_total: unfiltered_total as usize,
+            page,
+            page_count: query.page_count(total),
+            metrics: OrderMetrics {
+                gross_revenue,
+                average_order_value: OrderMetrics::average(gross_revenue, total),
+                outstanding_payments,
+                fulfillment_queue,
+                flagged,
+            },
+            groups,
+        })
+    }
+
+    fn matching(&self, query: &OrderQuery) -> Result<Vec<Order>, RepositoryError> {
+        let (clause, values) = where_clause(query);
+        let connection = self.lock()?;
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {SELECT_COLUMNS} FROM orders WHERE {clause} ORDER BY {}",
+                order_by(query)
+            ))
+            .map_err(storage_error)?;
+        let orders = statement
+            .query_map(params_from_iter(values.iter()), read_order)
+            .map_err(storage_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(storage_error)?;
+        Ok(orders)
+    }
+
+    fn find(&self, number: &str) -> Result<Option<Order>, RepositoryError> {
+        let connection = self.lock()?;
+        connection
+            .query_row(
+                &format!("SELECT {SELECT_COLUMNS} FROM orders WHERE number = ?1"),
+                [number],
+                read_order,
+            )
+            .optional()
+            .map_err(storage_error)
+    }
+
+    fn save(&self, order: &Order) -> Result<(), RepositoryError> {
+        let connection = self.lock()?;
+        insert_order(&connection, order, true)?;
+        Ok(())
+    }
+
+    /// A plain `INSERT`: the primary key on `number` makes SQLite refuse the row when the number is taken, also
+    /// by another connection to the same file.
+    fn insert_new(&self, order: &Order) -> Result<bool, RepositoryError> {
+        let connection = self.lock()?;
+        insert_order(&connection, order, false)
+    }
+
+    /// Runs in one `BEGIN IMMEDIATE` transaction, which takes the write lock before reading, so other
+    /// connections to the same file cannot slip a write in between.
+    fn update(
+        &self,
+        number: &str,
+        change: &mut dyn FnMut(&mut Order) -> Result<(), RepositoryError>,
+    ) -> Result<Order, RepositoryError> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(storage_error)?;
+        let mut order = transaction
+            .query_row(
+                &format!("SELECT {SELECT_COLUMNS} FROM orders WHERE number = ?1"),
+                [number],
+                read_order,
+            )
+            .optional()
+            .map_err(storage_error)?
+            .ok_or_else(|| RepositoryError::NotFound(number.to_string()))?;
+        change(&mut order)?;
+        insert_order(&transaction, &order, true)?;
+        transaction.commit().map_err(storage_error)?;
+        Ok(order)
+    }
+}
+
+fn where_clause(query: &OrderQuery) -> (String, Vec<Value>) {
+    let mut conditions = vec!["1 = 1".to_string()];
+    let mut values = Vec::new();
+
+    let search_term = query.search.trim().to_lowercase();
+    if !search_term.is_empty() {
+        let channel_labels = SalesChannel::all()
+            .iter()
+            .map(|channel| format!("WHEN '{}' THEN '{}'", channel.key(), channel.label()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        conditions.push(format!(
+            "instr(fold_case(number || ' ' || customer_name || ' ' || customer_email || ' ' || \
+             (CASE channel {channel_labels} ELSE '' END) || ' ' || \
+             COALESCE((SELECT group_concat(value, ' ') FROM json_each(orders.tags)), '')), ?) > 0"
+        ));
+        values.push(Value::Text(search_term));
+    }
+
+    let keyed = [
+        ("status", query.status.map(|status| status.key())),
+        (
+            "payment_status",
+            query.payment_status.map(|status| status.key()),
+        ),
+        (
+            "fulfillment_status",
+            query.fulfillment_status.map(|status| status.key()),
+        ),
+        ("channel", query.channel.map(|channel| channel.key())),
+        (
+            "payment_method",
+            query.payment_method.map(|method| method.key()),
+        ),
+    ];
+    for (column, key) in keyed {
+        if let Some(key) = key {
+            conditions.push(format!("{column} = ?"));
+            values.push(Value::Text(key.to_string()));
+        }
+    }
+
+    let pipeline_tag = (query.pipeline == OrderPipeline::Vip).then_some(VIP_TAG);
+    for tag in query.tags.iter().map(String::as_str).chain(pipeline_tag) {
+        conditions
+            .push("EXISTS (SELECT 1 FROM json_each(orders.tags) WHERE value = ?)".to_string());
+        values.push(Value::Text(tag.to_string()));
+    }
+
+    if let Some(from) = query.placed_from {
+        conditions.push("placed_on >= ?".to_string());
+        values.push(Value::Integer(epoch_days(from)));
+    }
+    if let Some(to) = query.placed_to {
+        conditions.push("placed_on <= ?".to_string());
+        values.push(Value::Integer(epoch_days(to)));
+    }
+    if query.min_total > 0.0 {
+        conditions.push("total >= ?".to_string());
+        values.push(Value::Real(query.min_total as f64));
+    }
+    if query.flagged_only {
+        conditions.push("flagged = 1".to_string());
+    }
+    match query.pipeline {
+        OrderPipeline::AwaitingFulfillment => {
+            let open = key_list(
+                FulfillmentStatus::all()
+                    .iter()
+                    .filter(|status| status.is_open())
+                    .map(FulfillmentStatus::key),
+            );
+            conditions.push(format!("fulfillment_status IN ({open})"));
+        }
+        OrderPipeline::Overdue => {
+            conditions.push(format!(
+                "payment_status = '{}'",
+                PaymentStatus::Overdue.key()
+            ));
+        }
+        OrderPipeline::All | OrderPipeline::Vip => {}
+    }
+
+    (conditions.join(" AND "), values)
+}
+
+fn order_by(query: &OrderQuery) -> String {
+    query
+        .sort
+        .iter()
+        .map(|sort| {
+            let column = match sort.field {
+                OrderSortField::PlacedOn => "placed_on",
+                OrderSortField::Number => "number",
+                OrderSortField::Customer => "customer_name",
+                OrderSortField::Total => "total",
+            };
+            let direction = match sort.direction {
+                SortDirection::Ascending => "ASC",
+                SortDirection::Descending => "DESC",
+            };
+            format!("{column} {direction}")
+        })
+        .chain(std::iter::once("number ASC".to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders static enum keys as a SQL list literal, e.g. `'pending', 'overdue'`.
+/// Counts and sums the orders matching `clause` per value of `field`, in the order [`OrderQuery::evaluate`] lists
+/// its groups.
+fn query_groups(
+    connection: &Connection,
+    field: OrderGroupField,
+    clause: &str,
+    values: &[Value],
+) -> Result<Vec<OrderGroup>, RepositoryError> {
+    let column = match field {
+        OrderGroupField::Channel => "channel",
+        OrderGroupField::Status => "status",
+        OrderGroupField::PaymentMethod => "payment_method",
+    };
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT {column}, COUNT(*), COALESCE(SUM(total), 0) FROM orders WHERE {clause} GROUP BY {column}"
+        ))
+        .map_err(storage_error)?;
+    let stored = statement
+        .query_map(params_from_iter(values.iter()), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)? as usize,
+                row.get::<_, f64>(2)? as f32,
+            ))
+        })
+        .map_err(storage_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(storage_error)?;
+    Ok(field
+        .values()
+        .into_iter()
+        .filter_map(|(key, label)| {
+            stored
+                .iter()
+                .find(|(stored_key, ..)| stored_key == key)
+                .map(|(_, count, gross_revenue)| OrderGroup {
+                    key: label.to_string(),
+                    count: *count,
+                    gross_revenue: *gross_revenue,
+                })
+        })
+        .collect())
+}
+
+fn key_list<'a>(keys: impl Iterator<Item = &'a str>) -> String {
+    keys.map(|key| format!("'{key}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Writes `order` and returns whether it was written. With `replace` the stored order with the same number is
+/// overwritten; without it nothing is written when the number is taken.
+fn insert_order(
+    connection: &Connection,
+    order: &Order,
+    replace: bool,
+) -> Result<bool, RepositoryError> {
+    let tags = serde_json::to_string(&order.tags).map_err(storage_error)?;
+    let details = serde_json::to_string(&OrderDetails {
+        line_items: order.line_items.clone(),
+        payment_history: order.payment_history.clone(),
+        fulfillment_history: order.fulfillment_history.clone(),
+        notes: order.notes.clone(),
+    })
+    .map_err(storage_error)?;
+    let verb = if replace {
+        "INSERT OR REPLACE"
+    } else {
+        "INSERT"
+    };
+    let inserted = connection.execute(
+        &format!(
+            "{verb} INTO orders ({SELECT_COLUMNS}) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+        ),
+        params![
+            order.number,
+            epoch_days(order.placed_on),
+            order.customer_name,
+            order.customer_email,
+            order.status.key(),
+            order.payment_status.key(),
+            order.fulfillment_status.key(),
+            order.payment_method.key(),
+            order.channel.key(),
+            order.total as f64,
+            tags,
+            order.flagged,
+            details,
+        ],
+    );
+    match inserted {
+        Ok(_) => Ok(true),
+        Err(rusqlite::Error::SqliteFailure(err, _))
+            if !replace && err.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY =>
+        {
+            Ok(false)
+        }
+        Err(err) => Err(storage_error(err)),
+    }
+}
+
+/// Decodes a row selected with [`SELECT_COLUMNS`].
+fn read_order(row: &Row<'_>) -> rusqlite::Result<Order> {
+    let tags: String = row.get(10)?;
+    let details: String = row.get(12)?;
+    let details: OrderDetails = serde_json::from_str(&details)
+        .map_err(|err| rusqlite::Error::FromSqlConversionFailure(12, Type::Text, Box::new(err)))?;
+    Ok(Order {
+        number: row.get(0)?,
+        placed_on: decode(row, 1, NaiveDate::from_days_since_epoch)?,
+        customer_name: row.get(2)?,
+        customer_email: row.get(3)?,
+        status: decode(row, 4, |key: String| OrderStatus::from_key(&key))?,
+        payment_status: decode(row, 5, |key: String| PaymentStatus::from_key(&key))?,
+        fulfillment_status: decode(row, 6, |key: String| FulfillmentStatus::from_key(&key))?,
+        payment_method: decode(row, 7, |key: String| PaymentMethod::from_key(&key))?,
+        channel: decode(row, 8, |key: String| SalesChannel::from_key(&key))?,
+        total: row.get::<_, f64>(9)? as f32,
+        tags: serde_json::from_str(&tags).map_err(|err| {
+            rusqlite::Error::FromSqlConversionFailure(10, Type::Text, Box::new(err))
+        })?,
+        flagged: row.get(11)?,
+        line_items: details.line_items,
+        payment_history: details.payment_history,
+        fulfillment_history: details.fulfillment_history,
+        notes: details.notes,
+    })
+}
+
+/// Reads column `index` and converts it with `parse`, reporting values `parse` rejects as conversion failures.
+fn decode<S, T>(
+    row: &Row<'_>,
+    index: usize,
+    parse: impl FnOnce(S) -> Option<T>,
+) -> rusqlite::Result<T>
+where
+    S: FromSql,
+{
+    let raw: Value = row.get(index)?;
+    let data_type = raw.data_type();
+    let value = S::column_result(ValueRef::from(&raw)).map_err(|err| {
+        rusqlite::Error::FromSqlConversionFailure(index, data_type, Box::new(err))
+    })?;
+    parse(value).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            data_type,
+            format!("unexpected value {raw:?}").into(),
+        )
+    })
+}
+
+fn epoch_days(date: NaiveDate) -> i64 {
+    let epoch = NaiveDate::from_days_since_epoch(0).expect("unix epoch");
+    (date - epoch).num_days()
+}
+
+fn storage_error(err: impl std::fmt::Display) -> RepositoryError {
+    RepositoryError::Storage(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::query::OrderSort;
+
+    fn seeded_repository() -> SqliteOrderRepository {
+        let repository = SqliteOrderRepository::from_connection(
+            Connection::open_in_memory().expect("in-memory database"),
+        )
+        .unwrap();
+        repository.seed_if_empty().unwrap();
+        repository
+    }
+
+    #[test]
+    fn seeds_once_and_roundtrips_orders() {
+        let repository = seeded_repository();
+        repository.seed_if_empty().unwrap();
+
+        let mut expected = seeded_orders();
+        expected.sort_by_key(|order| std::cmp::Reverse(order.placed_on));
+        assert_eq!(repository.list().unwrap(), expected);
+    }
+
+    #[test]
+    fn saves_and_finds_orders_by_number() {
+        let repository = seeded_repository();
+        assert_eq!(repository.find("DX-0000").unwrap(), None);
+
+        let updated = repository
+            .update("DX-1046", &mut |order| {
+                order.tags.clear();
+                order.notes.push(OrderNote {
+                    author: "周静".to_string(),
+                    body: "已补货".to_string(),
+                    created_on: order.placed_on,
+                });
+                Ok(())
+            })
+            .unwrap();
+        let found = repository.find("DX-1046").unwrap().unwrap();
+        assert_eq!(found, updated);
+        assert!(found.tags.is_empty());
+        assert_eq!(found.notes.len(), 3);
+        assert_eq!(found.line_items.len(), 1);
+    }
+
+    #[test]
+    fn overlapping_updates_from_two_connections_both_survive() {
+        let path = std::env::temp_dir().join(format!("orders-update-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let first = SqliteOrderRepository::open(&path).unwrap();
+        first.seed_if_empty().unwrap();
+        let second = SqliteOrderRepository::open(&path).unwrap();
+
+        std::thread::scope(|scope| {
+            for (repository, tag) in [(&first, "甲"), (&second, "乙")] {
+                scope.spawn(move || {
+                    repository
+                        .update("DX-1048", &mut |order| {
+                            // Widens the window between reading and writing the order.
+                            std::thread::sleep(Duration::from_millis(20));
+                            order.tags.push(tag.to_string());
+                            Ok(())
+                        })
+                        .unwrap();
+                });
+            }
+        });
+        let tags = first.find("DX-1048").unwrap().unwrap().tags;
+        drop((first, second));
+        let _ = std::fs::remove_file(&path);
+        assert!(tags.iter().any(|tag| tag == "甲") && tags.iter().any(|tag| tag == "乙"));
+    }
+
+    #[test]
+    fn inserts_new_orders_once_across_connections() {
+        let path = std::env::temp_dir().join(format!("orders-insert-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let first = SqliteOrderRepository::open(&path).unwrap();
+        first.seed_if_empty().unwrap();
+        let second = SqliteOrderRepository::open(&path).unwrap();
+        let mut order = seeded_orders().remove(0);
+        order.number = "DX-3001".to_string();
+
+        let inserted = std::thread::scope(|scope| {
+            let handles = [(&first, "甲"), (&second, "乙")].map(|(repository, name)| {
+                let mut order = order.clone();
+                order.customer_name = name.to_string();
+                scope.spawn(move || repository.insert_new(&order).unwrap())
+            });
+            handles.map(|handle| handle.join().unwrap())
+        });
+        let existing = first.insert_new(&seeded_orders()[1]).unwrap();
+        let stored = first.find("DX-3001").unwrap().unwrap();
+        drop((first, second));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(inserted.iter().filter(|inserted| **inserted).count(), 1);
+        let winner = if inserted[0] { "甲" } else { "乙" };
+        assert_eq!(stored.customer_name, winner);
+        assert!(!existing);
+    }
+
+    #[test]
+    fn adds_details_column_to_existing_databases() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE orders (number TEXT PRIMARY KEY NOT NULL, placed_on INTEGER NOT NULL, \
+                 customer_name TEXT NOT NULL, customer_email TEXT NOT NULL, status TEXT NOT NULL, \
+                 payment_status TEXT NOT NULL, fulfillment_status TEXT NOT NULL, \
+                 payment_method TEXT NOT NULL, channel TEXT NOT NULL, total REAL NOT NULL, \
+                 tags TEXT NOT NULL, flagged INTEGER NOT NULL);
+                 INSERT INTO orders VALUES ('DX-1', 19900, '王宏', 'hong@example.com', 'draft', \
+                 'pending', 'unfulfilled', 'cash', 'popup', 10.0, '[]', 0);",
+            )
+            .unwrap();
+
+        let repository = SqliteOrderRepository::from_connection(connection).unwrap();
+        let order = repository.find("DX-1").unwrap().unwrap();
+        assert!(order.line_items.is_empty());
+        assert!(order.payment_history.is_empty());
+        assert!(order.notes.is_empty());
+    }
+
+    #[test]
+    fn sql_queries_match_in_memory_evaluation() {
+        let repository = seeded_repository();
+        let queries = [
+            OrderQuery {
+                page_size: 5,
+                page: 2,
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                search: "EXAMPLE.com".to_string(),
+                tags: vec!["VIP".to_string()],
+                min_total: 700.0,
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                search: "第三方".to_string(),
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                search: "需回访".to_string(),
+                payment_status: Some(PaymentStatus::Pending),
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                pipeline: OrderPipeline::AwaitingFulfillment,
+                flagged_only: true,
+                sort: vec![OrderSort::new(
+                    OrderSortField::Total,
+                    SortDirection::Ascending,
+                )],
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                pipeline: OrderPipeline::Vip,
+                placed_from: NaiveDate::from_ymd_opt(2024, 7, 15),
+                placed_to: NaiveDate::from_ymd_opt(2024, 7, 21),
+                sort: vec![OrderSort::new(
+                    OrderSortField::Customer,
+                    SortDirection::Descending,
+                )],
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                channel: Some(SalesChannel::Wholesale),
+                payment_method: Some(PaymentMethod::BankTransfer),
+                status: Some(OrderStatus::PendingPayment),
+                fulfillment_status: Some(FulfillmentStatus::Unfulfilled),
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                page_size: 3,
+                group_by: Some(OrderGroupField::Channel),
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                search: "example.com".to_string(),
+                page_size: 3,
+                group_by: Some(OrderGroupField::PaymentMethod),
+                ..OrderQuery::default()
+            },
+            OrderQuery {
+                page_size: 3,
+                group_by: Some(OrderGroupField::Status),
+                ..OrderQuery::default()
+            },
+        ];
+
+        for query in queries {
+            let expected = query.evaluate(seeded_orders());
+            let actual = repository.query(&query).unwrap();
+            assert_eq!(actual.orders, expected.orders, "{query:?}");
+            assert_eq!(
+                repository.matching(&query).unwrap(),
+                query.select(seeded_orders())
+            );
+            assert_eq!(
+                (
+                    actual.total,
+                    actual.unfiltered_total,
+                    actual.page,
+                    actual.page_count
+                ),
+                (
+                    expected.total,
+                    expected.unfiltered_total,
+                    expected.page,
+                    expected.page_count
+                ),
+            );
+            assert_eq!(
+                actual.metrics.outstanding_payments,
+                expected.metrics.outstanding_payments
+            );
+            assert_eq!(
+                actual.metrics.fulfillment_queue,
+                expected.metrics.fulfillment_queue
+            );
+            assert_eq!(actual.metrics.flagged, expected.metrics.flagged);
+            assert!((actual.metrics.gross_revenue - expected.metrics.gross_revenue).abs() < 0.01);
+            let counts = |page: &OrderPage| {
+                page.groups
+                    .iter()
+                    .map(|group| (group.key.clone(), group.count))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(counts(&actual), counts(&expected), "{query:?}");
+            for (actual, expected) in actual.groups.iter().zip(&expected.groups) {
+                assert!((actual.gross_revenue - expected.gross_revenue).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn search_folds_non_ascii_case_like_in_memory() {
+        let repository = seeded_repository();
+        let mut orders = seeded_orders();
+        let mut accented = orders[0].clone();
+        accented.number = "DX-2001".to_string();
+        accented.customer_name = "ÉLODIE Müller".to_string();
+        accented.customer_email = "ÉLODIE@EXAMPLE.FR".to_string();
+        repository.save(&accented).unwrap();
+        orders.push(accented);
+
+        for search in ["élodie", "MÜLLER", "Élodie@example.fr"] {
+            let query = OrderQuery {
+                search: search.to_string(),
+                ..OrderQuery::default()
+            };
+            let found = repository.query(&query).unwrap().orders;
+            assert_eq!(found, query.evaluate(orders.clone()).orders, "{search}");
+            assert_eq!(found.len(), 1, "{search}");
+        }
+    }
+}
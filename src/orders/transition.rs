@@ -0,0 +1,409 @@
+//! Legal status changes for an order. Order, payment and fulfillment status only move together through an
+//! [`OrderAction`], which keeps combinations such as a cancelled order that is still being delivered from ever
+//! being stored.
+
+use super::{FulfillmentStatus, Order, OrderStatus, PaymentStatus};
+use crate::i18n::I18n;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(any(feature = "server", test))]
+use super::StatusChange;
+#[cfg(any(feature = "server", test))]
+use crate::time::NaiveDate;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderAction {
+    /// Draft → pending payment.
+    Submit,
+    /// Records the payment and moves unpaid orders into processing.
+    ConfirmPayment,
+    MarkOverdue,
+    StartPicking,
+    Ship,
+    /// Records the delivery and completes the order.
+    Deliver,
+    /// Takes back shipped or delivered goods. The order is cancelled and a captured payment is refunded.
+    Return,
+    /// Cancels an order that has not shipped yet. A captured payment is refunded and picking is reset.
+    Cancel,
+}
+
+impl OrderAction {
+    /// The name shown in the UI, in the language of `i18n`.
+    pub fn label_in(&self, i18n: I18n) -> String {
+        i18n.t(match self {
+            OrderAction::Submit => "order.action.submit",
+            OrderAction::ConfirmPayment => "order.action.confirm_payment",
+            OrderAction::MarkOverdue => "order.action.mark_overdue",
+            OrderAction::StartPicking => "order.action.start_picking",
+            OrderAction::Ship => "order.action.ship",
+            OrderAction::Deliver => "order.action.deliver",
+            OrderAction::Return => "order.action.return",
+            OrderAction::Cancel => "order.action.cancel",
+        })
+    }
+
+    pub fn all() -> &'static [OrderAction] {
+        &[
+            OrderAction::Submit,
+            OrderAction::ConfirmPayment,
+            OrderAction::MarkOverdue,
+            OrderAction::StartPicking,
+            OrderAction::Ship,
+            OrderAction::Deliver,
+            OrderAction::Return,
+            OrderAction::Cancel,
+        ]
+    }
+
+    /// Whether the action undoes work already done and should be confirmed in the UI.
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, OrderAction::Return | OrderAction::Cancel)
+    }
+}
+
+/// The statuses an order ends up in after an action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+    pub status: OrderStatus,
+    pub payment_status: PaymentStatus,
+    pub fulfillment_status: FulfillmentStatus,
+}
+
+impl Transition {
+    /// The statuses of a new order: a draft that is neither paid nor picked.
+    pub const NEW: Transition = Transition {
+        status: OrderStatus::Draft,
+        payment_status: PaymentStatus::Pending,
+        fulfillment_status: FulfillmentStatus::Unfulfilled,
+    };
+
+    /// An order taken into processing before it is paid for, e.g. on a bank transfer invoiced later. Payment is
+    /// confirmed or marked overdue from there.
+    pub const ON_ACCOUNT: Transition = Transition {
+        status: OrderStatus::Processing,
+        ..Transition::NEW
+    };
+
+    /// The statuses `action` leads to from these.
+    pub fn after(self, action: OrderAction) -> Result<Transition, TransitionError> {
+        use FulfillmentStatus as F;
+        use OrderStatus as O;
+        use PaymentStatus as P;
+
+        let from = self;
+        let refunded = |payment| match payment {
+            P::Paid => P::Refunded,
+            other => other,
+        };
+        let to = match (
+            action,
+            from.status,
+            from.payment_status,
+            from.fulfillment_status,
+        ) {
+            (OrderAction::Submit, O::Draft, _, _) => Transition {
+                status: O::PendingPayment,
+                ..from
+            },
+            (
+                OrderAction::ConfirmPayment,
+                O::Draft | O::PendingPayment,
+                P::Pending | P::Overdue,
+                _,
+            ) => Transition {
+                status: O::Processing,
+                payment_status: P::Paid,
+                ..from
+            },
+            (OrderAction::ConfirmPayment, O::Processing, P::Pending | P::Overdue, _) => {
+                Transition {
+                    payment_status: P::Paid,
+                    ..from
+                }
+            }
+            (OrderAction::MarkOverdue, O::PendingPayment | O::Processing, P::Pending, _) => {
+                Transition {
+                    payment_status: P::Overdue,
+                    ..from
+                }
+            }
+            (OrderAction::StartPicking, O::Processing, _, F::Unfulfilled) => Transition {
+                fulfillment_status: F::Picking,
+                ..from
+            },
+            (OrderAction::Ship, O::Processing, _, F::Picking) => Transition {
+                fulfillment_status: F::Shipped,
+                ..from
+            },
+            (OrderAction::Deliver, O::Processing, _, F::Shipped) => Transition {
+                status: O::Fulfilled,
+                fulfillment_status: F::Delivered,
+                ..from
+            },
+            (
+                OrderAction::Return,
+                O::Processing | O::Fulfilled,
+                payment,
+                F::Shipped | F::Delivered,
+            ) => Transition {
+                status: O::Cancelled,
+                payment_status: refunded(payment),
+                fulfillment_status: F::Returned,
+            },
+            (
+                OrderAction::Cancel,
+                O::Draft | O::PendingPayment | O::Processing,
+                payment,
+                F::Unfulfilled | F::Picking,
+            ) => Transition {
+                status: O::Cancelled,
+                payment_status: refunded(payment),
+                fulfillment_status: F::Unfulfilled,
+            },
+            _ => return Err(TransitionError::NotAllowed { action, from }),
+        };
+        Ok(to)
+    }
+
+    /// Whether an order starting out as [`Transition::NEW`] or [`Transition::ON_ACCOUNT`] can end up in these
+    /// statuses through [`OrderAction`]s. Orders from outside the workflow, e.g. imported ones, are checked with
+    /// this before they are stored.
+    pub fn is_consistent(self) -> bool {
+        let mut reached = vec![Transition::NEW, Transition::ON_ACCOUNT];
+        let mut next = 0;
+        while let Some(&from) = reached.get(next) {
+            if from == self {
+                return true;
+            }
+            for action in OrderAction::all() {
+                if let Ok(to) = from.after(*action) {
+                    if !reached.contains(&to) {
+                        reached.push(to);
+                    }
+                }
+            }
+            next += 1;
+        }
+        false
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransitionError {
+    /// The action does not apply to an order in the given statuses.
+    NotAllowed {
+        action: OrderAction,
+        from: Transition,
+    },
+}
+
+impl Display for TransitionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TransitionError::NotAllowed { action, from } => write!(
+                f,
+                "cannot {action:?} an order that is {}/{}/{}",
+                from.status.key(),
+                from.payment_status.key(),
+                from.fulfillment_status.key()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+impl Order {
+    fn statuses(&self) -> Transition {
+        Transition {
+            status: self.status,
+            payment_status: self.payment_status,
+            fulfillment_status: self.fulfillment_status,
+        }
+    }
+
+    /// The statuses `action` would lead to, without changing the order.
+    pub fn plan(&self, action: OrderAction) -> Result<Transition, TransitionError> {
+        self.statuses().after(action)
+    }
+
+    /// Every action that is legal for the order right now, in workflow order.
+    pub fn available_actions(&self) -> Vec<OrderAction> {
+        OrderAction::all()
+            .iter()
+            .copied()
+            .filter(|action| self.plan(*action).is_ok())
+            .collect()
+    }
+
+    /// Performs `action`, recording payment and fulfillment changes in the order's history as happening `on`.
+    #[cfg(any(feature = "server", test))]
+    pub fn apply(&mut self, action: OrderAction, on: NaiveDate) -> Result<(), TransitionError> {
+        let to = self.plan(action)?;
+        if to.payment_status != self.payment_status {
+            self.payment_history.push(StatusChange {
+                status: to.payment_status,
+                on,
+            });
+        }
+        if to.fulfillment_status != self.fulfillment_status {
+            self.fulfillment_history.push(StatusChange {
+                status: to.fulfillment_status,
+                on,
+            });
+        }
+        self.status = to.status;
+        self.payment_status = to.payment_status;
+        self.fulfillment_status = to.fulfillment_status;
+        Ok(())
+    }
+
+    /// Runs whichever of picking, shipping and delivery are still outstanding. The order is left unchanged when it
+    /// cannot be delivered, e.g. because it has not been paid for yet.
+    #[cfg(any(feature = "server", test))]
+    pub fn fulfill(&mut self, on: NaiveDate) -> Result<(), TransitionError> {
+        if self.fulfillment_status == FulfillmentStatus::Delivered {
+            return Ok(());
+        }
+        let mut fulfilled = self.clone();
+        for action in [
+            OrderAction::StartPicking,
+            OrderAction::Ship,
+            OrderAction::Deliver,
+        ] {
+            if fulfilled.fulfillment_status == FulfillmentStatus::Delivered {
+                break;
+            }
+            if fulfilled.plan(action).is_ok() {
+                fulfilled.apply(action, on)?;
+            }
+        }
+        if fulfilled.fulfillment_status != FulfillmentStatus::Delivered {
+            return Err(TransitionError::NotAllowed {
+                action: OrderAction::Deliver,
+                from: self.statuses(),
+            });
+        }
+        *self = fulfilled;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orders::fixtures::seeded_orders;
+
+    fn order(number: &str) -> Order {
+        seeded_orders()
+            .into_iter()
+            .find(|order| order.number == number)
+            .expect("fixture order")
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()
+    }
+
+    #[test]
+    fn offers_only_legal_actions() {
+        // Draft, payment pending, nothing picked yet.
+        assert_eq!(
+            order("DX-1039").available_actions(),
+            [
+                OrderAction::Submit,
+                OrderAction::ConfirmPayment,
+                OrderAction::Cancel
+            ]
+        );
+        // Delivered orders can only be returned.
+        assert_eq!(order("DX-1047").available_actions(), [OrderAction::Return]);
+        // Cancelled orders are final.
+        assert!(order("DX-1045").available_actions().is_empty());
+    }
+
+    #[test]
+    fn only_reachable_statuses_are_consistent() {
+        assert!(Transition::NEW.is_consistent());
+        for order in seeded_orders() {
+            assert!(order.statuses().is_consistent(), "{}", order.number);
+        }
+        let delivered_but_cancelled = Transition {
+            status: OrderStatus::Cancelled,
+            payment_status: PaymentStatus::Refunded,
+            fulfillment_status: FulfillmentStatus::Delivered,
+        };
+        assert!(!delivered_but_cancelled.is_consistent());
+        let shipped_draft = Transition {
+            fulfillment_status: FulfillmentStatus::Shipped,
+            ..Transition::NEW
+        };
+        assert!(!shipped_draft.is_consistent());
+    }
+
+    #[test]
+    fn walks_an_order_through_fulfillment() {
+        let mut order = order("DX-1049");
+        for action in [
+            OrderAction::ConfirmPayment,
+            OrderAction::StartPicking,
+            OrderAction::Ship,
+            OrderAction::Deliver,
+        ] {
+            order.apply(action, today()).unwrap();
+        }
+        assert_eq!(order.status, OrderStatus::Fulfilled);
+        assert_eq!(order.payment_status, PaymentStatus::Paid);
+        assert_eq!(order.fulfillment_status, FulfillmentStatus::Delivered);
+        let last = order.fulfillment_history.last().unwrap();
+        assert_eq!(
+            (last.status, last.on),
+            (FulfillmentStatus::Delivered, today())
+        );
+        assert_eq!(order.fulfillment_history.len(), 4);
+        assert_eq!(order.payment_history.len(), 2);
+    }
+
+    #[test]
+    fn cancelling_refunds_and_resets_picking() {
+        let mut order = order("DX-1050");
+        order.apply(OrderAction::Cancel, today()).unwrap();
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(order.payment_status, PaymentStatus::Refunded);
+        assert_eq!(order.fulfillment_status, FulfillmentStatus::Unfulfilled);
+        assert_eq!(
+            order.payment_history.last().unwrap().status,
+            PaymentStatus::Refunded
+        );
+    }
+
+    #[test]
+    fn returning_refunds_and_cancels() {
+        let mut order = order("DX-1047");
+        order.apply(OrderAction::Return, today()).unwrap();
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(order.payment_status, PaymentStatus::Refunded);
+        assert_eq!(order.fulfillment_status, FulfillmentStatus::Returned);
+    }
+
+    #[test]
+    fn rejects_illegal_transitions_without_changing_the_order() {
+        let mut order = order("DX-1048");
+        let before = order.clone();
+        let err = order.apply(OrderAction::Cancel, today()).unwrap_err();
+        assert_eq!(
+            err,
+            TransitionError::NotAllowed {
+                action: OrderAction::Cancel,
+                from: Transition {
+                    status: OrderStatus::Processing,
+                    payment_status: PaymentStatus::Paid,
+                    fulfillment_status: FulfillmentStatus::Shipped,
+                },
+            }
+        );
+        assert_eq!(order, before);
+    }
+}
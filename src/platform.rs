@@ -0,0 +1,133 @@
+//! Small platform integrations that differ between the web build and the desktop build.
+
+/// Hands a generated file to the user and returns where it went. On web the browser downloads it; on desktop it
+/// is written straight into the Downloads folder (or the working directory when there is none) without a dialog.
+#[cfg(not(feature = "desktop"))]
+pub async fn save_download(name: &str, mime_type: &str, bytes: Vec<u8>) -> Result<String, String> {
+    use dioxus::prelude::document;
+
+    const SCRIPT: &str = r#"
+        const [name, mimeType, bytes] = await dioxus.recv();
+        const url = URL.createObjectURL(new Blob([new Uint8Array(bytes)], { type: mimeType }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = name;
+        document.body.appendChild(link);
+        link.click();
+        link.remove();
+        setTimeout(() => URL.revokeObjectURL(url), 0);
+        return true;
+    "#;
+
+    let eval = document::eval(SCRIPT);
+    eval.send((name, mime_type, bytes))
+        .map_err(|err| err.to_string())?;
+    eval.await.map_err(|err| err.to_string())?;
+    Ok(name.to_string())
+}
+
+/// Hands a generated file to the user and returns where it went. On web the browser downloads it; on desktop it
+/// is written straight into the Downloads folder (or the working directory when there is none) without a dialog.
+#[cfg(feature = "desktop")]
+pub async fn save_download(name: &str, _mime_type: &str, bytes: Vec<u8>) -> Result<String, String> {
+    use std::path::PathBuf;
+
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    let directory = home
+        .map(|home| PathBuf::from(home).join("Downloads"))
+        .filter(|path| path.is_dir())
+        .or_else(|| std::env::current_dir().ok())
+        .ok_or_else(|| "no directory to save into".to_string())?;
+
+    // Keep earlier exports: `orders.csv` becomes `orders (1).csv`, `orders (2).csv`, …
+    let (stem, extension) = name.rsplit_once('.').unwrap_or((name, ""));
+    let mut path = directory.join(name);
+    let mut copy = 1;
+    while path.exists() {
+        path = directory.join(format!("{stem} ({copy}).{extension}"));
+        copy += 1;
+    }
+
+    std::fs::write(&path, bytes).map_err(|err| err.to_string())?;
+    Ok(path.display().to_string())
+}
+
+/// Reads a value stored with [`save_setting`]. Missing values and values that no longer decode read as `None`.
+/// On web settings live in `localStorage`; on desktop each one is a JSON file in the user's config directory.
+#[cfg(not(feature = "desktop"))]
+pub async fn load_setting<T: serde::de::DeserializeOwned>(key: &str) -> Option<T> {
+    use dioxus::prelude::document;
+
+    const SCRIPT: &str = r#"
+        const [key] = await dioxus.recv();
+        try {
+            const raw = window.localStorage.getItem(key);
+            return raw === null ? null : JSON.parse(raw);
+        } catch (_) {
+            return null;
+        }
+    "#;
+
+    let eval = document::eval(SCRIPT);
+    eval.send((setting_name(key),)).ok()?;
+    eval.join::<Option<T>>().await.ok().flatten()
+}
+
+/// Stores `value` under `key` so [`load_setting`] finds it after a reload.
+#[cfg(not(feature = "desktop"))]
+pub async fn save_setting<T: serde::Serialize>(key: &str, value: &T) -> Result<(), String> {
+    use dioxus::prelude::document;
+
+    const SCRIPT: &str = r#"
+        const [key, value] = await dioxus.recv();
+        window.localStorage.setItem(key, JSON.stringify(value));
+        return true;
+    "#;
+
+    let eval = document::eval(SCRIPT);
+    eval.send((setting_name(key), value))
+        .map_err(|err| err.to_string())?;
+    eval.await.map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Reads a value stored with [`save_setting`]. Missing values and values that no longer decode read as `None`.
+/// On web settings live in `localStorage`; on desktop each one is a JSON file in the user's config directory.
+#[cfg(feature = "desktop")]
+pub async fn load_setting<T: serde::de::DeserializeOwned>(key: &str) -> Option<T> {
+    let raw = std::fs::read(settings_directory()?.join(format!("{key}.json"))).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+/// Stores `value` under `key` so [`load_setting`] finds it after a restart.
+#[cfg(feature = "desktop")]
+pub async fn save_setting<T: serde::Serialize>(key: &str, value: &T) -> Result<(), String> {
+    let directory = settings_directory().ok_or_else(|| "no config directory".to_string())?;
+    std::fs::create_dir_all(&directory).map_err(|err| err.to_string())?;
+    let json = serde_json::to_vec_pretty(value).map_err(|err| err.to_string())?;
+    std::fs::write(directory.join(format!("{key}.json")), json).map_err(|err| err.to_string())
+}
+
+/// Settings share `localStorage` with everything else served from the same origin, so their keys are prefixed.
+#[cfg(not(feature = "desktop"))]
+fn setting_name(key: &str) -> String {
+    format!("dx-admin-template.{key}")
+}
+
+/// `dx-admin-template` inside the platform's per-user config directory.
+#[cfg(feature = "desktop")]
+fn settings_directory() -> Option<std::path::PathBuf> {
+    use std::env::var_os;
+    use std::path::PathBuf;
+
+    let base = if cfg!(target_os = "windows") {
+        var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|base| base.join("dx-admin-template"))
+}
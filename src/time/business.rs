@@ -0,0 +1,42 @@
+//! The business time zone: the zone "today", quick date ranges and the dates on screen are read in, so everyone
+//! sees the same day wherever their browser or server runs.
+//!
+//! It defaults to [`DEFAULT_ZONE`]. Native builds, the server included, can override it with the
+//! `BUSINESS_TIME_ZONE` environment variable; the app switches it from the navbar and stores the choice as a
+//! setting.
+
+use super::zone::{DateTime, Tz};
+use std::sync::RwLock;
+
+pub const DEFAULT_ZONE: &str = "Asia/Shanghai";
+
+static ZONE: RwLock<Option<Tz>> = RwLock::new(None);
+
+pub fn business_zone() -> Tz {
+    if let Some(zone) = *ZONE.read().unwrap_or_else(|err| err.into_inner()) {
+        return zone;
+    }
+    let zone = configured_zone()
+        .unwrap_or_else(|| Tz::from_key(DEFAULT_ZONE).expect("default zone in table"));
+    set_business_zone(zone);
+    zone
+}
+
+pub fn set_business_zone(zone: Tz) {
+    *ZONE.write().unwrap_or_else(|err| err.into_inner()) = Some(zone);
+}
+
+/// The current time in the business time zone.
+pub fn business_now() -> DateTime<Tz> {
+    DateTime::now(business_zone())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn configured_zone() -> Option<Tz> {
+    Tz::from_key(std::env::var("BUSINESS_TIME_ZONE").ok()?.trim())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn configured_zone() -> Option<Tz> {
+    None
+}
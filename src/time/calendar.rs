@@ -0,0 +1,297 @@
+//! Calendar arithmetic on [`NaiveDate`]: months and years, ISO weeks, quarters, days of the year and business days.
+
+use super::{days_in_month, Duration, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+/// A week of an ISO 8601 week-based year. Weeks start on Monday and week 1 is the one with the year's first
+/// Thursday, so the first days of January can belong to the last week of the previous year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoWeek {
+    year: i32,
+    week: u32,
+}
+
+#[allow(dead_code)]
+impl IsoWeek {
+    /// The week-based year, which differs from the calendar year around New Year.
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// 1 to 52, or 53 in long years.
+    pub fn week(self) -> u32 {
+        self.week
+    }
+}
+
+/// `2024-W26`.
+impl Display for IsoWeek {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-W{:02}", self.year, self.week)
+    }
+}
+
+#[allow(dead_code)]
+impl NaiveDate {
+    pub fn first_day_of_month(self) -> Self {
+        self.with_day(1).expect("every month has a first day")
+    }
+
+    pub fn last_day_of_month(self) -> Self {
+        self.with_day(self.days_in_month())
+            .expect("day within month")
+    }
+
+    pub fn days_in_month(self) -> u32 {
+        days_in_month(self.year(), self.month())
+    }
+
+    /// Moves by whole months, keeping the day of the month where it exists and clamping it to the month's last
+    /// day where it does not: 31 January plus one month is 29 February in a leap year.
+    pub fn add_months(self, months: i32) -> Self {
+        let (year, month, day) = self.components();
+        let index = year as i64 * 12 + (month as i64 - 1) + months as i64;
+        let year = i32::try_from(index.div_euclid(12)).expect("date overflow");
+        let month = index.rem_euclid(12) as u32 + 1;
+        Self::from_ymd_opt(year, month, day.min(days_in_month(year, month))).expect("date overflow")
+    }
+
+    /// Moves by whole years; 29 February becomes 28 February in common years.
+    pub fn add_years(self, years: i32) -> Self {
+        self.add_months(years.checked_mul(12).expect("date overflow"))
+    }
+
+    /// Day of the year, 1 for 1 January.
+    pub fn ordinal(self) -> u32 {
+        let new_year = Self::from_ymd_opt(self.year(), 1, 1).expect("every year has a first day");
+        (self - new_year).num_days() as u32 + 1
+    }
+
+    pub fn iso_week(self) -> IsoWeek {
+        // The week belongs to the year its Thursday is in.
+        let thursday = self + Duration::days(3 - self.weekday().num_days_from_monday() as i64);
+        IsoWeek {
+            year: thursday.year(),
+            week: (thursday.ordinal() - 1) / 7 + 1,
+        }
+    }
+
+    /// The day of ISO week `week` of week-based year `year`; `None` when the year has no such week.
+    pub fn from_isoywd_opt(year: i32, week: u32, weekday: Weekday) -> Option<Self> {
+        if week == 0 {
+            return None;
+        }
+        let january_fourth = Self::from_ymd_opt(year, 1, 4)?;
+        let first_monday =
+            january_fourth - Duration::days(january_fourth.weekday().num_days_from_monday() as i64);
+        let days = (week as i64 - 1) * 7 + weekday.num_days_from_monday() as i64;
+        let date = first_monday + Duration::days(days);
+        (date.iso_week().year == year).then_some(date)
+    }
+
+    /// 1 to 4.
+    pub fn quarter(self) -> u32 {
+        (self.month() - 1) / 3 + 1
+    }
+
+    pub fn first_day_of_quarter(self) -> Self {
+        let month = (self.quarter() - 1) * 3 + 1;
+        Self::from_ymd_opt(self.year(), month, 1).expect("quarter starts a month")
+    }
+
+    pub fn last_day_of_quarter(self) -> Self {
+        self.first_day_of_quarter()
+            .add_months(2)
+            .last_day_of_month()
+    }
+}
+
+/// Working days: Monday to Friday, minus holidays, plus weekend days that are worked instead (such as the
+/// make-up days around Chinese public holidays).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BusinessDays {
+    holidays: BTreeSet<NaiveDate>,
+    workdays: BTreeSet<NaiveDate>,
+}
+
+#[allow(dead_code)]
+impl BusinessDays {
+    pub fn new(holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        Self {
+            holidays: holidays.into_iter().collect(),
+            workdays: BTreeSet::new(),
+        }
+    }
+
+    /// Weekend days that are worked.
+    pub fn with_workdays(mut self, workdays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.workdays.extend(workdays);
+        self
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        if self.workdays.contains(&date) {
+            return true;
+        }
+        let weekend = matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday);
+        !weekend && !self.holidays.contains(&date)
+    }
+
+    /// The business day `days` business days after `date`, or before it when `days` is negative. Zero days
+    /// returns `date` itself even when it is not a business day.
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> NaiveDate {
+        let step = Duration::days(days.signum());
+        let mut remaining = days.abs();
+        let mut cursor = date;
+        while remaining > 0 {
+            cursor += step;
+            if self.is_business_day(cursor) {
+                remaining -= 1;
+            }
+        }
+        cursor
+    }
+
+    /// Business days from `start` up to but not including `end`; negative when `end` comes first.
+    pub fn business_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        let (from, to, sign) = if start <= end {
+            (start, end, 1)
+        } else {
+            (end, start, -1)
+        };
+        let mut count = 0;
+        let mut cursor = from;
+        while cursor < to {
+            if self.is_business_day(cursor) {
+                count += 1;
+            }
+            cursor += Duration::days(1);
+        }
+        count * sign
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Dates between roughly 6200 BC and 10200 AD.
+    fn any_date() -> impl Strategy<Value = NaiveDate> {
+        (-3_000_000i64..3_000_000).prop_map(|days| NaiveDate::from_days_since_epoch(days).unwrap())
+    }
+
+    #[test]
+    fn months_and_years_clamp_to_the_end_of_the_month() {
+        assert_eq!(date(2024, 1, 31).add_months(1), date(2024, 2, 29));
+        assert_eq!(date(2023, 1, 31).add_months(1), date(2023, 2, 28));
+        assert_eq!(date(2024, 3, 31).add_months(-13), date(2023, 2, 28));
+        assert_eq!(date(2024, 2, 29).add_years(1), date(2025, 2, 28));
+        assert_eq!(date(2024, 2, 10).last_day_of_month(), date(2024, 2, 29));
+        assert_eq!(date(2024, 12, 10).first_day_of_month(), date(2024, 12, 1));
+    }
+
+    #[test]
+    fn iso_weeks_quarters_and_ordinals() {
+        // 2021-01-03 is a Sunday in the last week of 2020; 2024-12-30 is a Monday in week 1 of 2025.
+        assert_eq!(date(2021, 1, 3).iso_week().to_string(), "2020-W53");
+        assert_eq!(date(2024, 12, 30).iso_week().to_string(), "2025-W01");
+        assert_eq!(date(2024, 6, 30).iso_week().week(), 26);
+        assert_eq!(
+            NaiveDate::from_isoywd_opt(2020, 53, Weekday::Sunday),
+            Some(date(2021, 1, 3))
+        );
+        assert_eq!(NaiveDate::from_isoywd_opt(2021, 53, Weekday::Monday), None);
+
+        assert_eq!(date(2024, 8, 15).quarter(), 3);
+        assert_eq!(date(2024, 8, 15).first_day_of_quarter(), date(2024, 7, 1));
+        assert_eq!(date(2024, 8, 15).last_day_of_quarter(), date(2024, 9, 30));
+        assert_eq!(date(2024, 12, 31).ordinal(), 366);
+    }
+
+    #[test]
+    fn business_days_skip_weekends_and_holidays() {
+        // National Day 2024: 1 to 7 October off, with Sunday 29 September and Saturday 12 October worked.
+        let holidays = (1..=7).map(|day| date(2024, 10, day));
+        let calendar =
+            BusinessDays::new(holidays).with_workdays([date(2024, 9, 29), date(2024, 10, 12)]);
+
+        assert!(calendar.is_business_day(date(2024, 9, 29)));
+        assert!(!calendar.is_business_day(date(2024, 10, 2)));
+        assert_eq!(
+            calendar.add_business_days(date(2024, 9, 27), 1),
+            date(2024, 9, 29)
+        );
+        assert_eq!(
+            calendar.add_business_days(date(2024, 9, 30), 1),
+            date(2024, 10, 8)
+        );
+        assert_eq!(
+            calendar.add_business_days(date(2024, 10, 8), -2),
+            date(2024, 9, 29)
+        );
+        assert_eq!(
+            calendar.business_days_between(date(2024, 9, 30), date(2024, 10, 14)),
+            6
+        );
+        assert_eq!(
+            calendar.business_days_between(date(2024, 10, 14), date(2024, 9, 30)),
+            -6
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn adding_months_keeps_or_clamps_the_day(date in any_date(), months in -2_400i32..2_400) {
+            let moved = date.add_months(months);
+            let month_index = |date: NaiveDate| date.year() as i64 * 12 + date.month() as i64;
+            prop_assert_eq!(month_index(moved) - month_index(date), months as i64);
+            prop_assert_eq!(moved.day(), date.day().min(moved.days_in_month()));
+            if date.day() <= 28 {
+                prop_assert_eq!(moved.add_months(-months), date);
+            }
+        }
+
+        #[test]
+        fn iso_weeks_round_trip(date in any_date()) {
+            let week = date.iso_week();
+            prop_assert!((1..=53).contains(&week.week()));
+            prop_assert!((week.year() - date.year()).abs() <= 1);
+            prop_assert_eq!(NaiveDate::from_isoywd_opt(week.year(), week.week(), date.weekday()), Some(date));
+            let next = date + Duration::days(1);
+            if next.weekday() != Weekday::Monday {
+                prop_assert_eq!(next.iso_week(), week);
+            }
+        }
+
+        #[test]
+        fn ordinals_and_quarters_contain_the_date(date in any_date()) {
+            let new_year = NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap();
+            prop_assert_eq!(new_year + Duration::days(date.ordinal() as i64 - 1), date);
+            prop_assert!(date.first_day_of_quarter() <= date && date <= date.last_day_of_quarter());
+            prop_assert_eq!(date.last_day_of_quarter() + Duration::days(1), date.first_day_of_quarter().add_months(3));
+        }
+
+        #[test]
+        fn business_days_count_back_what_was_added(
+            date in any_date(),
+            days in -60i64..60,
+            holidays in proptest::collection::vec(0i64..120, 0..20),
+        ) {
+            let start = date - Duration::days(60);
+            let calendar = BusinessDays::new(holidays.iter().map(|offset| start + Duration::days(*offset)));
+            let moved = calendar.add_business_days(date, days);
+            if days != 0 {
+                prop_assert!(calendar.is_business_day(moved));
+            }
+            if calendar.is_business_day(date) || days == 0 {
+                prop_assert_eq!(calendar.business_days_between(date, moved), days);
+            }
+        }
+    }
+}
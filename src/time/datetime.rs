@@ -0,0 +1,350 @@
+use super::{
+    Duration, FormattedDate, Locale, NaiveDate, MILLIS_PER_DAY, MILLIS_PER_HOUR, MILLIS_PER_MINUTE,
+    MILLIS_PER_SECOND,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A time of day with millisecond precision and no time zone.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct NaiveTime {
+    millis_of_day: u32,
+}
+
+#[allow(dead_code)]
+impl NaiveTime {
+    pub const MIDNIGHT: NaiveTime = NaiveTime { millis_of_day: 0 };
+
+    pub fn from_hms_opt(hour: u32, minute: u32, second: u32) -> Option<Self> {
+        Self::from_hms_milli_opt(hour, minute, second, 0)
+    }
+
+    pub fn from_hms_milli_opt(hour: u32, minute: u32, second: u32, milli: u32) -> Option<Self> {
+        if hour >= 24 || minute >= 60 || second >= 60 || milli >= 1_000 {
+            return None;
+        }
+        Some(Self {
+            millis_of_day: ((hour * 60 + minute) * 60 + second) * 1_000 + milli,
+        })
+    }
+
+    pub fn hour(self) -> u32 {
+        self.millis_of_day / MILLIS_PER_HOUR as u32
+    }
+
+    /// Whether the time is after noon, and the hour on a 12-hour clock (12, 1, …, 11).
+    pub fn hour12(self) -> (bool, u32) {
+        let hour = self.hour();
+        (hour >= 12, (hour + 11) % 12 + 1)
+    }
+
+    pub fn minute(self) -> u32 {
+        self.millis_of_day / MILLIS_PER_MINUTE as u32 % 60
+    }
+
+    pub fn second(self) -> u32 {
+        self.millis_of_day / MILLIS_PER_SECOND as u32 % 60
+    }
+
+    pub fn millisecond(self) -> u32 {
+        self.millis_of_day % 1_000
+    }
+
+    pub fn num_seconds_from_midnight(self) -> u32 {
+        self.millis_of_day / MILLIS_PER_SECOND as u32
+    }
+
+    /// Formats with `%H %I %p %M %S` and English day periods; date specifiers such as `%Y` are left as they are.
+    pub fn format<'a>(self, pattern: &'a str) -> FormattedDate<'a> {
+        FormattedDate {
+            date: None,
+            time: Some(self),
+            pattern,
+            locale: Locale::EnUs,
+        }
+    }
+
+    /// Like [`NaiveTime::format`] with the day periods of `locale`.
+    pub fn format_localized<'a>(self, pattern: &'a str, locale: Locale) -> FormattedDate<'a> {
+        FormattedDate {
+            locale,
+            ..self.format(pattern)
+        }
+    }
+
+    /// Adds `rhs`, wrapping around midnight, and also returns how many days were crossed (negative going back).
+    pub fn overflowing_add(self, rhs: Duration) -> (Self, i64) {
+        let total = self.millis_of_day as i64 + rhs.num_milliseconds();
+        let time = Self {
+            millis_of_day: total.rem_euclid(MILLIS_PER_DAY) as u32,
+        };
+        (time, total.div_euclid(MILLIS_PER_DAY))
+    }
+}
+
+/// Wraps around midnight; see [`NaiveTime::overflowing_add`] for the days crossed.
+impl Add<Duration> for NaiveTime {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.overflowing_add(rhs).0
+    }
+}
+
+impl Sub<Duration> for NaiveTime {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.overflowing_add(-rhs).0
+    }
+}
+
+impl Sub for NaiveTime {
+    type Output = Duration;
+
+    fn sub(self, rhs: NaiveTime) -> Self::Output {
+        Duration::milliseconds(self.millis_of_day as i64 - rhs.millis_of_day as i64)
+    }
+}
+
+/// `HH:MM:SS`, with `.mmm` appended when there are milliseconds.
+impl Display for NaiveTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format("%H:%M:%S"))?;
+        match self.millisecond() {
+            0 => Ok(()),
+            milli => write!(f, ".{milli:03}"),
+        }
+    }
+}
+
+/// A date and time of day with millisecond precision and no time zone. Timestamps, system times and JS dates are
+/// read as UTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NaiveDateTime {
+    date: NaiveDate,
+    time: NaiveTime,
+}
+
+#[allow(dead_code)]
+impl NaiveDateTime {
+    pub fn new(date: NaiveDate, time: NaiveTime) -> Self {
+        Self { date, time }
+    }
+
+    pub fn date(self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn time(self) -> NaiveTime {
+        self.time
+    }
+
+    /// From whole seconds since the Unix epoch.
+    pub fn from_timestamp_opt(seconds: i64) -> Option<Self> {
+        Self::from_timestamp_millis(seconds.checked_mul(MILLIS_PER_SECOND)?)
+    }
+
+    /// From milliseconds since the Unix epoch.
+    pub fn from_timestamp_millis(millis: i64) -> Option<Self> {
+        let date = NaiveDate::from_days_since_epoch(millis.div_euclid(MILLIS_PER_DAY))?;
+        let time = NaiveTime {
+            millis_of_day: millis.rem_euclid(MILLIS_PER_DAY) as u32,
+        };
+        Some(Self { date, time })
+    }
+
+    /// Whole seconds since the Unix epoch, rounded down.
+    pub fn timestamp(self) -> i64 {
+        self.timestamp_millis().div_euclid(MILLIS_PER_SECOND)
+    }
+
+    pub fn timestamp_millis(self) -> i64 {
+        self.date.days_since_epoch as i64 * MILLIS_PER_DAY + self.time.millis_of_day as i64
+    }
+
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        let millis = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => i64::try_from(after.as_millis()).ok()?,
+            // Round toward the past so an instant just before the epoch lands on 1969-12-31.
+            Err(err) => {
+                let before = err.duration();
+                let millis = i64::try_from(before.as_millis()).ok()?;
+                -millis - i64::from(before.subsec_nanos() % 1_000_000 != 0)
+            }
+        };
+        Self::from_timestamp_millis(millis)
+    }
+
+    /// The instant of a JS `Date`; `None` for an invalid date.
+    #[cfg(target_arch = "wasm32")]
+    pub fn from_js_date(date: &js_sys::Date) -> Option<Self> {
+        let millis = date.get_time();
+        if !millis.is_finite() {
+            return None;
+        }
+        Self::from_timestamp_millis(millis.floor() as i64)
+    }
+
+    /// The current UTC date and time: the browser's clock on web, the system clock elsewhere.
+    #[cfg(target_arch = "wasm32")]
+    pub fn now_utc() -> Self {
+        Self::from_timestamp_millis(js_sys::Date::now().floor() as i64)
+            .unwrap_or_else(|| Self::from_timestamp_millis(0).expect("unix epoch"))
+    }
+
+    /// The current UTC date and time: the browser's clock on web, the system clock elsewhere.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now_utc() -> Self {
+        Self::from_system_time(SystemTime::now()).expect("system time within supported range")
+    }
+
+    /// Formats with `%Y %m %d %B %b %A %a %H %I %p %M %S` and English names.
+    pub fn format<'a>(self, pattern: &'a str) -> FormattedDate<'a> {
+        FormattedDate {
+            date: Some(self.date),
+            time: Some(self.time),
+            pattern,
+            locale: Locale::EnUs,
+        }
+    }
+
+    /// Like [`NaiveDateTime::format`] with month and weekday names in `locale`.
+    pub fn format_localized<'a>(self, pattern: &'a str, locale: Locale) -> FormattedDate<'a> {
+        FormattedDate {
+            locale,
+            ..self.format(pattern)
+        }
+    }
+}
+
+impl Add<Duration> for NaiveDateTime {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        let (time, days) = self.time.overflowing_add(rhs);
+        Self {
+            date: self.date + Duration::days(days),
+            time,
+        }
+    }
+}
+
+impl AddAssign<Duration> for NaiveDateTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for NaiveDateTime {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Duration> for NaiveDateTime {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for NaiveDateTime {
+    type Output = Duration;
+
+    fn sub(self, rhs: NaiveDateTime) -> Self::Output {
+        Duration::milliseconds(self.timestamp_millis() - rhs.timestamp_millis())
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SS`, with `.mmm` appended when there are milliseconds.
+impl Display for NaiveDateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.date.format("%Y-%m-%d"), self.time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(
+        (year, month, day): (i32, u32, u32),
+        (hour, minute, second): (u32, u32, u32),
+    ) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .unwrap()
+    }
+
+    #[test]
+    fn times_validate_and_wrap_around_midnight() {
+        assert_eq!(NaiveTime::from_hms_opt(24, 0, 0), None);
+        assert_eq!(NaiveTime::from_hms_milli_opt(23, 59, 59, 1_000), None);
+
+        let late = NaiveTime::from_hms_milli_opt(23, 30, 15, 250).unwrap();
+        assert_eq!(
+            (
+                late.hour(),
+                late.minute(),
+                late.second(),
+                late.millisecond()
+            ),
+            (23, 30, 15, 250)
+        );
+        let (next, days) = late.overflowing_add(Duration::hours(1));
+        assert_eq!((next.to_string(), days), ("00:30:15.250".to_string(), 1));
+        assert_eq!(
+            NaiveTime::MIDNIGHT - Duration::seconds(1),
+            NaiveTime::from_hms_opt(23, 59, 59).unwrap()
+        );
+        assert_eq!(late - next, Duration::hours(23));
+    }
+
+    #[test]
+    fn datetimes_carry_days_and_convert_to_timestamps() {
+        let placed = at((2024, 2, 28), (22, 15, 0));
+        let due = placed + Duration::hours(26);
+        assert_eq!(due, at((2024, 3, 1), (0, 15, 0)));
+        assert_eq!(due - placed, Duration::hours(26));
+        assert!(due > placed);
+        assert_eq!(
+            due.format("%Y/%m/%d %H:%M:%S").to_string(),
+            "2024/03/01 00:15:00"
+        );
+
+        assert_eq!(at((1970, 1, 1), (0, 0, 0)).timestamp(), 0);
+        assert_eq!(placed.timestamp(), 1_709_158_500);
+        assert_eq!(
+            NaiveDateTime::from_timestamp_opt(1_709_158_500),
+            Some(placed)
+        );
+        let before_epoch = NaiveDateTime::from_timestamp_millis(-1).unwrap();
+        assert_eq!(before_epoch.to_string(), "1969-12-31 23:59:59.999");
+        assert_eq!(before_epoch.timestamp(), -1);
+    }
+
+    #[test]
+    fn system_times_round_toward_the_past() {
+        let after = UNIX_EPOCH + std::time::Duration::from_millis(1_709_158_500_123);
+        assert_eq!(
+            NaiveDateTime::from_system_time(after).map(|time| time.timestamp_millis()),
+            Some(1_709_158_500_123)
+        );
+        let before = UNIX_EPOCH - std::time::Duration::from_micros(1_500);
+        assert_eq!(
+            NaiveDateTime::from_system_time(before).map(|time| time.timestamp_millis()),
+            Some(-2)
+        );
+        assert_eq!(
+            NaiveDate::from_system_time(before),
+            NaiveDate::from_ymd_opt(1969, 12, 31)
+        );
+    }
+}
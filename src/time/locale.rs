@@ -0,0 +1,262 @@
+//! Month and weekday names, default patterns and the first day of the week for the languages the app ships in.
+
+use super::Weekday;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    ZhCn,
+    EnUs,
+}
+
+/// Default patterns a [`Locale`] provides, see [`Locale::pattern`].
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateStyle {
+    /// `2024年6月30日` / `June 30, 2024`.
+    Long,
+    /// `2024年6月30日 星期日` / `Sunday, June 30, 2024`.
+    Full,
+    /// `2024年6月` / `June 2024`, for calendar titles.
+    YearMonth,
+    /// `6月30日` / `Jun 30`, for the start of a range within one year.
+    MonthDay,
+    /// `2024年6月30日 09:30` / `June 30, 2024 09:30`.
+    DateTime,
+}
+
+/// How a clock counts the hours of a day, see [`Locale::hour_cycle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HourCycle {
+    /// `00`–`23`.
+    #[default]
+    H24,
+    /// `12`, `1`–`11` with AM / PM.
+    H12,
+}
+
+#[allow(dead_code)]
+impl Locale {
+    /// BCP 47 tag, e.g. `zh-CN`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|locale| locale.key().eq_ignore_ascii_case(key))
+    }
+
+    /// The language's own name for itself.
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "简体中文",
+            Locale::EnUs => "English",
+        }
+    }
+
+    pub fn all() -> &'static [Locale] {
+        &[Locale::ZhCn, Locale::EnUs]
+    }
+
+    pub fn pattern(self, style: DateStyle) -> &'static str {
+        match (self, style) {
+            (Locale::ZhCn, DateStyle::Long) => "%Y年%-m月%-d日",
+            (Locale::ZhCn, DateStyle::Full) => "%Y年%-m月%-d日 %A",
+            (Locale::ZhCn, DateStyle::YearMonth) => "%Y年%-m月",
+            (Locale::ZhCn, DateStyle::MonthDay) => "%-m月%-d日",
+            (Locale::ZhCn, DateStyle::DateTime) => "%Y年%-m月%-d日 %H:%M",
+            (Locale::EnUs, DateStyle::Long) => "%B %-d, %Y",
+            (Locale::EnUs, DateStyle::Full) => "%A, %B %-d, %Y",
+            (Locale::EnUs, DateStyle::YearMonth) => "%B %Y",
+            (Locale::EnUs, DateStyle::MonthDay) => "%b %-d",
+            (Locale::EnUs, DateStyle::DateTime) => "%B %-d, %Y %H:%M",
+        }
+    }
+
+    /// Times of day such as `15:30` / `下午3:30` / `3:30 PM`, with or without seconds.
+    pub fn time_pattern(self, cycle: HourCycle, seconds: bool) -> &'static str {
+        match (self, cycle, seconds) {
+            (_, HourCycle::H24, false) => "%H:%M",
+            (_, HourCycle::H24, true) => "%H:%M:%S",
+            (Locale::ZhCn, HourCycle::H12, false) => "%p%-I:%M",
+            (Locale::ZhCn, HourCycle::H12, true) => "%p%-I:%M:%S",
+            (Locale::EnUs, HourCycle::H12, false) => "%-I:%M %p",
+            (Locale::EnUs, HourCycle::H12, true) => "%-I:%M:%S %p",
+        }
+    }
+
+    /// The 24-hour clock in China, the 12-hour clock in the United States.
+    pub fn hour_cycle(self) -> HourCycle {
+        match self {
+            Locale::ZhCn => HourCycle::H24,
+            Locale::EnUs => HourCycle::H12,
+        }
+    }
+
+    /// `%p`: before or after noon.
+    pub fn day_period(self, pm: bool) -> &'static str {
+        match (self, pm) {
+            (Locale::ZhCn, false) => "上午",
+            (Locale::ZhCn, true) => "下午",
+            (Locale::EnUs, false) => "AM",
+            (Locale::EnUs, true) => "PM",
+        }
+    }
+
+    /// Monday in China, Sunday in the United States.
+    pub fn first_day_of_week(self) -> Weekday {
+        match self {
+            Locale::ZhCn => Weekday::Monday,
+            Locale::EnUs => Weekday::Sunday,
+        }
+    }
+
+    /// `%B`.
+    pub fn month_name(self, month: u32) -> &'static str {
+        const ZH: [&str; 12] = [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ];
+        const EN: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        let names = match self {
+            Locale::ZhCn => &ZH,
+            Locale::EnUs => &EN,
+        };
+        names.get(month as usize - 1).copied().unwrap_or("Unknown")
+    }
+
+    /// `%b`.
+    pub fn short_month_name(self, month: u32) -> &'static str {
+        const ZH: [&str; 12] = [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ];
+        const EN: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        let names = match self {
+            Locale::ZhCn => &ZH,
+            Locale::EnUs => &EN,
+        };
+        names.get(month as usize - 1).copied().unwrap_or("Unk")
+    }
+
+    /// `%A`.
+    pub fn weekday_name(self, weekday: Weekday) -> &'static str {
+        const ZH: [&str; 7] = [
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+            "星期日",
+        ];
+        const EN: [&str; 7] = [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ];
+        match self {
+            Locale::ZhCn => ZH[weekday.num_days_from_monday() as usize],
+            Locale::EnUs => EN[weekday.num_days_from_monday() as usize],
+        }
+    }
+
+    /// `%a`, also used as the column headers of calendars.
+    pub fn short_weekday_name(self, weekday: Weekday) -> &'static str {
+        const ZH: [&str; 7] = ["周一", "周二", "周三", "周四", "周五", "周六", "周日"];
+        const EN: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        match self {
+            Locale::ZhCn => ZH[weekday.num_days_from_monday() as usize],
+            Locale::EnUs => EN[weekday.num_days_from_monday() as usize],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::NaiveDate;
+
+    #[test]
+    fn default_patterns_read_naturally_in_each_language() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let long = |locale: Locale| {
+            date.format_localized(locale.pattern(DateStyle::Long), locale)
+                .to_string()
+        };
+        assert_eq!(long(Locale::ZhCn), "2024年6月3日");
+        assert_eq!(long(Locale::EnUs), "June 3, 2024");
+
+        let full = |locale: Locale| {
+            date.format_localized(locale.pattern(DateStyle::Full), locale)
+                .to_string()
+        };
+        assert_eq!(full(Locale::ZhCn), "2024年6月3日 星期一");
+        assert_eq!(full(Locale::EnUs), "Monday, June 3, 2024");
+        assert_eq!(
+            date.format_localized("%a %b", Locale::ZhCn).to_string(),
+            "周一 6月"
+        );
+        assert_eq!(date.format("%a %-d %B").to_string(), "Mon 3 June");
+    }
+
+    #[test]
+    fn times_follow_the_hour_cycle() {
+        let time = crate::time::NaiveTime::from_hms_opt(15, 30, 0).unwrap();
+        let format = |locale: Locale, cycle: HourCycle| {
+            time.format_localized(locale.time_pattern(cycle, false), locale)
+                .to_string()
+        };
+        assert_eq!(format(Locale::ZhCn, Locale::ZhCn.hour_cycle()), "15:30");
+        assert_eq!(format(Locale::ZhCn, HourCycle::H12), "下午3:30");
+        assert_eq!(format(Locale::EnUs, Locale::EnUs.hour_cycle()), "3:30 PM");
+        assert_eq!(
+            crate::time::NaiveTime::MIDNIGHT.format("%I %p").to_string(),
+            "12 AM"
+        );
+    }
+
+    #[test]
+    fn long_dates_parse_back() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let pattern = Locale::ZhCn.pattern(DateStyle::Long);
+        let text = date.format_localized(pattern, Locale::ZhCn).to_string();
+        assert_eq!(NaiveDate::parse_from_str(&text, pattern), Ok(date));
+        assert_eq!(Locale::from_key("en-us"), Some(Locale::EnUs));
+    }
+}
@@ -0,0 +1,567 @@
+//! Calendar dates, times of day, durations and time zones, small enough to ship to the browser.
+
+mod business;
+mod calendar;
+mod datetime;
+mod locale;
+mod parse;
+mod zone;
+
+pub use business::{business_now, business_zone, set_business_zone};
+#[allow(unused_imports)]
+pub use calendar::{BusinessDays, IsoWeek};
+pub use datetime::{NaiveDateTime, NaiveTime};
+pub use locale::{DateStyle, HourCycle, Locale};
+#[allow(unused_imports)]
+pub use parse::{Expected, Field, ParseError, ParseErrorKind};
+pub use zone::{DateTime, FixedOffset, TimeZone, Tz};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter, Write};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::time::SystemTime;
+
+const MILLIS_PER_SECOND: i64 = 1_000;
+const MILLIS_PER_MINUTE: i64 = 60 * MILLIS_PER_SECOND;
+const MILLIS_PER_HOUR: i64 = 60 * MILLIS_PER_MINUTE;
+const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NaiveDate {
+    days_since_epoch: i32,
+}
+
+/// A signed span of time with millisecond precision.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration {
+    millis: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weekday {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2,
+    Thursday = 3,
+    Friday = 4,
+    Saturday = 5,
+    Sunday = 6,
+}
+
+impl Weekday {
+    /// Monday first.
+    pub fn all() -> &'static [Weekday] {
+        &[
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ]
+    }
+
+    pub fn num_days_from_monday(self) -> u8 {
+        self as u8
+    }
+
+    /// Days since `first`, the day a week starts on: 0 for `first` itself, 6 for the day before it.
+    pub fn num_days_from(self, first: Weekday) -> u8 {
+        (self as u8 + 7 - first as u8) % 7
+    }
+
+    /// The seven days of a week that starts on `first`.
+    pub fn week_from(first: Weekday) -> impl Iterator<Item = Weekday> {
+        (0..7).map(move |offset| Self::all()[(first as usize + offset) % 7])
+    }
+}
+
+#[allow(dead_code)]
+impl Duration {
+    pub const ZERO: Duration = Duration { millis: 0 };
+
+    pub fn days(days: i64) -> Self {
+        Self::milliseconds(days * MILLIS_PER_DAY)
+    }
+
+    pub fn hours(hours: i64) -> Self {
+        Self::milliseconds(hours * MILLIS_PER_HOUR)
+    }
+
+    pub fn minutes(minutes: i64) -> Self {
+        Self::milliseconds(minutes * MILLIS_PER_MINUTE)
+    }
+
+    pub fn seconds(seconds: i64) -> Self {
+        Self::milliseconds(seconds * MILLIS_PER_SECOND)
+    }
+
+    pub fn milliseconds(millis: i64) -> Self {
+        Self { millis }
+    }
+
+    /// Whole days, truncated toward zero like the other `num_*` accessors.
+    pub fn num_days(self) -> i64 {
+        self.millis / MILLIS_PER_DAY
+    }
+
+    pub fn num_hours(self) -> i64 {
+        self.millis / MILLIS_PER_HOUR
+    }
+
+    pub fn num_minutes(self) -> i64 {
+        self.millis / MILLIS_PER_MINUTE
+    }
+
+    pub fn num_seconds(self) -> i64 {
+        self.millis / MILLIS_PER_SECOND
+    }
+
+    pub fn num_milliseconds(self) -> i64 {
+        self.millis
+    }
+
+    pub fn abs(self) -> Self {
+        Self::milliseconds(self.millis.abs())
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self::milliseconds(self.millis + rhs.millis)
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self::milliseconds(self.millis - rhs.millis)
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Duration {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::milliseconds(-self.millis)
+    }
+}
+
+impl Mul<i64> for Duration {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::milliseconds(self.millis * rhs)
+    }
+}
+
+/// Largest units first, zero units left out: `1d 2h 30m`, `45s`, `1s 250ms`, `-5m`, `0s`.
+impl Display for Duration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.millis == 0 {
+            return f.write_str("0s");
+        }
+        if self.millis < 0 {
+            f.write_str("-")?;
+        }
+        let mut rest = self.millis.unsigned_abs();
+        let mut first = true;
+        for (size, unit) in [
+            (MILLIS_PER_DAY, "d"),
+            (MILLIS_PER_HOUR, "h"),
+            (MILLIS_PER_MINUTE, "m"),
+            (MILLIS_PER_SECOND, "s"),
+            (1, "ms"),
+        ] {
+            let count = rest / size as u64;
+            rest %= size as u64;
+            if count > 0 {
+                if !first {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{count}{unit}")?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A date, time of day or both being formatted; see [`NaiveDate::format`].
+#[derive(Clone, Copy)]
+pub struct FormattedDate<'a> {
+    date: Option<NaiveDate>,
+    time: Option<NaiveTime>,
+    pattern: &'a str,
+    locale: Locale,
+}
+
+impl<'a> Display for FormattedDate<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_pattern(
+            self.pattern,
+            self.date,
+            self.time,
+            self.locale,
+        ))
+    }
+}
+
+#[allow(dead_code)]
+impl NaiveDate {
+    pub fn from_ymd_opt(year: i32, month: u32, day: u32) -> Option<Self> {
+        if month == 0 || month > 12 {
+            return None;
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self {
+            days_since_epoch: days_from_civil(year, month, day)?,
+        })
+    }
+
+    pub fn with_day(self, day: u32) -> Option<Self> {
+        Self::from_ymd_opt(self.year(), self.month(), day)
+    }
+
+    pub fn year(self) -> i32 {
+        self.components().0
+    }
+
+    pub fn month(self) -> u32 {
+        self.components().1
+    }
+
+    pub fn day(self) -> u32 {
+        self.components().2
+    }
+
+    pub fn weekday(self) -> Weekday {
+        let idx = (self.days_since_epoch as i64 + 3).rem_euclid(7) as u8;
+        match idx {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    /// Formats with `%Y %m %d %B %b %A %a` and English names; time specifiers such as `%H` are left as they are.
+    pub fn format<'a>(self, pattern: &'a str) -> FormattedDate<'a> {
+        FormattedDate {
+            date: Some(self),
+            time: None,
+            pattern,
+            locale: Locale::EnUs,
+        }
+    }
+
+    /// Like [`NaiveDate::format`] with month and weekday names in `locale`; see [`Locale::pattern`] for patterns
+    /// that suit it.
+    pub fn format_localized<'a>(self, pattern: &'a str, locale: Locale) -> FormattedDate<'a> {
+        FormattedDate {
+            locale,
+            ..self.format(pattern)
+        }
+    }
+
+    pub fn and_time(self, time: NaiveTime) -> NaiveDateTime {
+        NaiveDateTime::new(self, time)
+    }
+
+    pub fn and_hms_opt(self, hour: u32, minute: u32, second: u32) -> Option<NaiveDateTime> {
+        NaiveTime::from_hms_opt(hour, minute, second).map(|time| self.and_time(time))
+    }
+
+    /// The UTC date of `time`.
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        NaiveDateTime::from_system_time(time).map(NaiveDateTime::date)
+    }
+
+    /// The current date in the business time zone, see [`business_zone`].
+    pub fn today() -> Self {
+        business_now().date_naive()
+    }
+
+    /// The current date in `zone`. The server dates requests with the zone the client picked, since
+    /// [`set_business_zone`] only switches it in the browser.
+    pub fn today_in(zone: Tz) -> Self {
+        DateTime::now(zone).date_naive()
+    }
+
+    pub fn from_days_since_epoch(days: i64) -> Option<Self> {
+        let value: i32 = days.try_into().ok()?;
+        Some(Self {
+            days_since_epoch: value,
+        })
+    }
+
+    fn components(self) -> (i32, u32, u32) {
+        civil_from_days(self.days_since_epoch)
+    }
+}
+
+/// Adds the whole days of `rhs`; any remainder shorter than a day is dropped.
+impl Add<Duration> for NaiveDate {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        let total = self.days_since_epoch as i64 + rhs.num_days();
+        Self::from_days_since_epoch(total).expect("date overflow")
+    }
+}
+
+impl AddAssign<Duration> for NaiveDate {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for NaiveDate {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        let total = self.days_since_epoch as i64 - rhs.num_days();
+        Self::from_days_since_epoch(total).expect("date overflow")
+    }
+}
+
+impl SubAssign<Duration> for NaiveDate {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for NaiveDate {
+    type Output = Duration;
+
+    fn sub(self, rhs: NaiveDate) -> Self::Output {
+        Duration::days(self.days_since_epoch as i64 - rhs.days_since_epoch as i64)
+    }
+}
+
+/// Writes `pattern` with `%Y %m %d %B %b %A %a` taken from `date` and `%H %I %p %M %S` from `time`, naming months,
+/// weekdays and day periods in `locale`. A `-` after the `%` drops the zero padding of a number, as in `%-d`. Specifiers whose part
+/// is missing, and unknown ones, are copied as they are; `%%` is a literal `%`.
+fn format_pattern(
+    pattern: &str,
+    date: Option<NaiveDate>,
+    time: Option<NaiveTime>,
+    locale: Locale,
+) -> String {
+    let ymd = date.map(NaiveDate::components);
+    let mut output = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            output.push(ch);
+            continue;
+        }
+        let padded = chars.next_if_eq(&'-').is_none();
+        let Some(spec) = chars.next() else {
+            break;
+        };
+        let number = |output: &mut String, value: u32| match padded {
+            true => write!(output, "{value:02}"),
+            false => write!(output, "{value}"),
+        };
+        let _ = match (spec, ymd, time) {
+            ('Y', Some((year, _, _)), _) => write!(output, "{year:04}"),
+            ('m', Some((_, month, _)), _) => number(&mut output, month),
+            ('d', Some((_, _, day)), _) => number(&mut output, day),
+            ('B', Some((_, month, _)), _) => output.write_str(locale.month_name(month)),
+            ('b', Some((_, month, _)), _) => output.write_str(locale.short_month_name(month)),
+            ('A', Some(_), _) => {
+                output.write_str(locale.weekday_name(date.expect("date").weekday()))
+            }
+            ('a', Some(_), _) => {
+                output.write_str(locale.short_weekday_name(date.expect("date").weekday()))
+            }
+            ('H', _, Some(time)) => number(&mut output, time.hour()),
+            ('I', _, Some(time)) => number(&mut output, time.hour12().1),
+            ('p', _, Some(time)) => output.write_str(locale.day_period(time.hour12().0)),
+            ('M', _, Some(time)) => number(&mut output, time.minute()),
+            ('S', _, Some(time)) => number(&mut output, time.second()),
+            ('%', _, _) => output.write_char('%'),
+            (other, _, _) if padded => write!(output, "%{other}"),
+            (other, _, _) => write!(output, "%-{other}"),
+        };
+    }
+    output
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_from_civil(year: i32, month: u32, day: u32) -> Option<i32> {
+    let y = year - if month <= 2 { 1 } else { 0 };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = month as i32 + if month > 2 { -3 } else { 9 };
+    let doy = (153 * mp + 2) / 5 + day as i32 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719_468;
+    Some(days)
+}
+
+fn civil_from_days(days: i32) -> (i32, u32, u32) {
+    let days = days as i64 + 719_468;
+    let era = if days >= 0 {
+        days / 146_097
+    } else {
+        (days - 146_096) / 146_097
+    };
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let mut y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = mp + if mp < 10 { 3 } else { -9 };
+    y += if month <= 2 { 1 } else { 0 };
+    (y as i32, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn roundtrip_ymd() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        assert_eq!(date.year(), 2024);
+        assert_eq!(date.month(), 6);
+        assert_eq!(date.day(), 30);
+        assert_eq!(date.weekday().num_days_from_monday(), 6);
+    }
+
+    #[test]
+    fn today_in_follows_the_given_zone() {
+        // Kiritimati runs exactly a day ahead of Honolulu, so the two dates differ at any instant.
+        let honolulu = Tz::from_key("Pacific/Honolulu").unwrap();
+        let kiritimati = Tz::from_key("Pacific/Kiritimati").unwrap();
+        assert_eq!(
+            NaiveDate::today_in(kiritimati),
+            NaiveDate::today_in(honolulu) + Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn add_days() {
+        let mut date = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        date += Duration::days(1);
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 1));
+    }
+
+    #[test]
+    fn add_hours_counts_whole_days_only() {
+        let mut date = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        date += Duration::hours(47);
+        assert_eq!((date.month(), date.day()), (2, 2));
+    }
+
+    #[test]
+    fn durations_convert_compare_and_display() {
+        let sla = Duration::hours(26) + Duration::minutes(30);
+        assert_eq!(sla.num_days(), 1);
+        assert_eq!(sla.num_minutes(), 26 * 60 + 30);
+        assert_eq!((-sla).num_hours(), -26);
+        assert!(Duration::minutes(90) > Duration::hours(1));
+        assert_eq!(Duration::seconds(90) * 2, Duration::minutes(3));
+        assert_eq!(sla.to_string(), "1d 2h 30m");
+        assert_eq!(
+            (Duration::seconds(-1) - Duration::milliseconds(250)).to_string(),
+            "-1s 250ms"
+        );
+        assert_eq!(Duration::ZERO.to_string(), "0s");
+    }
+
+    #[test]
+    fn format_leaves_specifiers_without_a_value() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        assert_eq!(
+            date.format("%d %b %Y %H:%M 100%%").to_string(),
+            "30 Jun 2024 %H:%M 100%"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn civil_conversions_round_trip(days in -3_000_000i32..3_000_000) {
+            let (year, month, day) = civil_from_days(days);
+            prop_assert!((1..=12).contains(&month));
+            prop_assert!((1..=days_in_month(year, month)).contains(&day));
+            prop_assert_eq!(days_from_civil(year, month, day), Some(days));
+        }
+
+        #[test]
+        fn consecutive_days_step_through_the_calendar(days in -3_000_000i32..3_000_000) {
+            let (year, month, day) = civil_from_days(days);
+            let next = civil_from_days(days + 1);
+            let expected = if day < days_in_month(year, month) {
+                (year, month, day + 1)
+            } else if month < 12 {
+                (year, month + 1, 1)
+            } else {
+                (year + 1, 1, 1)
+            };
+            prop_assert_eq!(next, expected);
+
+            let date = NaiveDate { days_since_epoch: days };
+            let tomorrow = NaiveDate { days_since_epoch: days + 1 };
+            prop_assert_eq!(
+                tomorrow.weekday().num_days_from_monday(),
+                (date.weekday().num_days_from_monday() + 1) % 7
+            );
+        }
+
+        #[test]
+        fn only_real_days_make_dates(year in -9_999i32..9_999, month in 0u32..14, day in 0u32..33) {
+            let valid = (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day);
+            let date = NaiveDate::from_ymd_opt(year, month, day);
+            prop_assert_eq!(date.is_some(), valid);
+            if let Some(date) = date {
+                prop_assert_eq!((date.year(), date.month(), date.day()), (year, month, day));
+            }
+        }
+
+        #[test]
+        fn iso_dates_parse_back(days in -719_162i32..2_932_896) {
+            let date = NaiveDate { days_since_epoch: days };
+            prop_assert_eq!(date.format("%Y-%m-%d").to_string().parse::<NaiveDate>(), Ok(date));
+        }
+    }
+}
@@ -0,0 +1,611 @@
+//! Reading dates and times back from text, with the format specifiers of [`NaiveDate::format`] or as ISO 8601.
+//!
+//! Positions in a [`ParseError`] count characters from 0, so they line up with what a user typed even when the
+//! input contains non-ASCII text such as `2024年6月30日`.
+
+use super::{DateTime, FixedOffset, Locale, NaiveDate, NaiveDateTime, NaiveTime};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: usize,
+    found: Option<char>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Something else was expected at the position.
+    Expected(Expected),
+    /// The input goes on after everything the pattern asks for.
+    TrailingInput,
+    /// A field out of range on its own, such as month 13; the position is where the field starts.
+    OutOfRange(Field),
+    /// Every field is in range but the date does not exist, such as 2023-02-29; the position is where the day
+    /// starts.
+    NoSuchDate,
+    /// The pattern never fills a field the result needs.
+    MissingField(Field),
+    /// The pattern contains a specifier that cannot be parsed; the position is in the pattern.
+    UnknownSpecifier(char),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expected {
+    Digit,
+    Char(char),
+    MonthName,
+    /// `AM` or `PM`.
+    DayPeriod,
+    /// `T` or a space between an ISO 8601 date and time.
+    DateTimeSeparator,
+    /// `Z` or `±HH:MM` after an ISO 8601 datetime.
+    Offset,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Offset,
+}
+
+#[allow(dead_code)]
+impl ParseError {
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Characters before the offending one, counting from 0.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The offending character; `None` at the end of the input.
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Digit => f.write_str("a digit"),
+            Expected::Char(ch) => write!(f, "{ch:?}"),
+            Expected::MonthName => f.write_str("a month name"),
+            Expected::DayPeriod => f.write_str("'AM' or 'PM'"),
+            Expected::DateTimeSeparator => f.write_str("'T' or a space"),
+            Expected::Offset => f.write_str("'Z' or a UTC offset"),
+        }
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Field::Year => "year",
+            Field::Month => "month",
+            Field::Day => "day",
+            Field::Hour => "hour",
+            Field::Minute => "minute",
+            Field::Second => "second",
+            Field::Offset => "UTC offset",
+        })
+    }
+}
+
+/// Positions are shown counting from 1, e.g. `expected '-' at character 5, found '/'`.
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let at = self.position + 1;
+        let found = match self.found {
+            Some(ch) => format!("{ch:?}"),
+            None => "the end".to_string(),
+        };
+        match self.kind {
+            ParseErrorKind::Expected(expected) => {
+                write!(f, "expected {expected} at character {at}, found {found}")
+            }
+            ParseErrorKind::TrailingInput => write!(f, "unexpected {found} at character {at}"),
+            ParseErrorKind::OutOfRange(field) => {
+                write!(f, "{field} out of range at character {at}")
+            }
+            ParseErrorKind::NoSuchDate => write!(f, "no such date, see the day at character {at}"),
+            ParseErrorKind::MissingField(field) => write!(f, "the {field} is missing"),
+            ParseErrorKind::UnknownSpecifier(spec) => {
+                write!(f, "cannot parse %{spec} at character {at} of the pattern")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+struct Cursor {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Cursor {
+    fn new(input: &str) -> Self {
+        Self {
+            chars: input.chars().collect(),
+            index: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.index, kind)
+    }
+
+    fn error_at(&self, position: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            kind,
+            position,
+            found: self.chars.get(position).copied(),
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> Result<(), ParseError> {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::Expected(Expected::Char(expected))))
+        }
+    }
+
+    fn eat_if(&mut self, matches: impl Fn(char) -> bool) -> Option<char> {
+        let ch = self.peek().filter(|ch| matches(*ch))?;
+        self.index += 1;
+        Some(ch)
+    }
+
+    /// Between `min` and `max` digits, checked against `range`.
+    fn number(
+        &mut self,
+        min: usize,
+        max: usize,
+        field: Field,
+        range: std::ops::RangeInclusive<u32>,
+    ) -> Result<u32, ParseError> {
+        let start = self.index;
+        let mut value = 0;
+        while self.index - start < max {
+            let Some(digit) = self.peek().and_then(|ch| ch.to_digit(10)) else {
+                break;
+            };
+            value = value * 10 + digit;
+            self.index += 1;
+        }
+        if self.index - start < min {
+            return Err(self.error(ParseErrorKind::Expected(Expected::Digit)));
+        }
+        if !range.contains(&value) {
+            return Err(self.error_at(start, ParseErrorKind::OutOfRange(field)));
+        }
+        Ok(value)
+    }
+
+    fn month_name(&mut self, names: fn(u32) -> &'static str) -> Result<u32, ParseError> {
+        for month in 1..=12 {
+            let name: Vec<char> = names(month).chars().collect();
+            let candidate = self.chars.get(self.index..self.index + name.len());
+            let matches = candidate.is_some_and(|candidate| {
+                candidate
+                    .iter()
+                    .zip(&name)
+                    .all(|(a, b)| a.eq_ignore_ascii_case(b))
+            });
+            if matches {
+                self.index += name.len();
+                return Ok(month);
+            }
+        }
+        Err(self.error(ParseErrorKind::Expected(Expected::MonthName)))
+    }
+
+    /// `AM` or `PM` in any case; true for `PM`.
+    fn day_period(&mut self) -> Result<bool, ParseError> {
+        let candidate: String = self.chars.iter().skip(self.index).take(2).collect();
+        let pm = match candidate.to_ascii_uppercase().as_str() {
+            "AM" => false,
+            "PM" => true,
+            _ => return Err(self.error(ParseErrorKind::Expected(Expected::DayPeriod))),
+        };
+        self.index += 2;
+        Ok(pm)
+    }
+
+    fn finish(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(ParseErrorKind::TrailingInput)),
+        }
+    }
+}
+
+/// Fields read from the input, with where the day started for [`ParseErrorKind::NoSuchDate`].
+#[derive(Default)]
+struct Parsed {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<(u32, usize)>,
+    hour: Option<u32>,
+    /// `%I`, combined with `pm` when there is no `%H`.
+    hour12: Option<u32>,
+    pm: Option<bool>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    milli: u32,
+}
+
+impl Parsed {
+    fn from_pattern(input: &str, pattern: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(input);
+        let mut parsed = Parsed::default();
+        let mut specs = pattern.chars().enumerate().peekable();
+        while let Some((percent_at, ch)) = specs.next() {
+            if ch.is_whitespace() {
+                // Any run of whitespace, including none, matches whitespace in the pattern.
+                while cursor.eat_if(char::is_whitespace).is_some() {}
+                continue;
+            }
+            if ch != '%' {
+                cursor.eat(ch)?;
+                continue;
+            }
+            // Padding does not matter when reading, so `%-d` reads like `%d`.
+            specs.next_if(|(_, spec)| *spec == '-');
+            let Some((_, spec)) = specs.next() else {
+                cursor.eat('%')?;
+                break;
+            };
+            match spec {
+                'Y' => {
+                    let negative = cursor.eat_if(|ch| ch == '-').is_some();
+                    let year = cursor.number(1, 4, Field::Year, 0..=9_999)? as i32;
+                    parsed.year = Some(if negative { -year } else { year });
+                }
+                'm' => parsed.month = Some(cursor.number(1, 2, Field::Month, 1..=12)?),
+                'd' => {
+                    let start = cursor.index;
+                    parsed.day = Some((cursor.number(1, 2, Field::Day, 1..=31)?, start));
+                }
+                'B' => {
+                    parsed.month = Some(cursor.month_name(|month| Locale::EnUs.month_name(month))?)
+                }
+                'b' => {
+                    parsed.month =
+                        Some(cursor.month_name(|month| Locale::EnUs.short_month_name(month))?)
+                }
+                'H' => parsed.hour = Some(cursor.number(1, 2, Field::Hour, 0..=23)?),
+                'I' => parsed.hour12 = Some(cursor.number(1, 2, Field::Hour, 1..=12)?),
+                'p' => parsed.pm = Some(cursor.day_period()?),
+                'M' => parsed.minute = Some(cursor.number(1, 2, Field::Minute, 0..=59)?),
+                'S' => parsed.second = Some(cursor.number(1, 2, Field::Second, 0..=59)?),
+                '%' => cursor.eat('%')?,
+                other => {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnknownSpecifier(other),
+                        position: percent_at,
+                        found: Some('%'),
+                    })
+                }
+            }
+        }
+        cursor.finish()?;
+        Ok(parsed)
+    }
+
+    fn date(&self) -> Result<NaiveDate, ParseError> {
+        let missing = |field| ParseError {
+            kind: ParseErrorKind::MissingField(field),
+            position: 0,
+            found: None,
+        };
+        let year = self.year.ok_or_else(|| missing(Field::Year))?;
+        let month = self.month.ok_or_else(|| missing(Field::Month))?;
+        let (day, day_at) = self.day.ok_or_else(|| missing(Field::Day))?;
+        NaiveDate::from_ymd_opt(year, month, day).ok_or(ParseError {
+            kind: ParseErrorKind::NoSuchDate,
+            position: day_at,
+            found: None,
+        })
+    }
+
+    /// Hours and minutes are required; seconds default to 0. A `%I` hour without `%p` is read as AM.
+    fn time(&self) -> Result<NaiveTime, ParseError> {
+        let missing = |field| ParseError {
+            kind: ParseErrorKind::MissingField(field),
+            position: 0,
+            found: None,
+        };
+        let hour12 = self
+            .hour12
+            .map(|hour| hour % 12 + if self.pm == Some(true) { 12 } else { 0 });
+        let hour = self.hour.or(hour12).ok_or_else(|| missing(Field::Hour))?;
+        let minute = self.minute.ok_or_else(|| missing(Field::Minute))?;
+        let second = self.second.unwrap_or(0);
+        Ok(
+            NaiveTime::from_hms_milli_opt(hour, minute, second, self.milli)
+                .expect("fields in range"),
+        )
+    }
+}
+
+/// Reads `YYYY-MM-DD` into `parsed`.
+fn iso_date(cursor: &mut Cursor, parsed: &mut Parsed) -> Result<(), ParseError> {
+    parsed.year = Some(cursor.number(4, 4, Field::Year, 0..=9_999)? as i32);
+    cursor.eat('-')?;
+    parsed.month = Some(cursor.number(2, 2, Field::Month, 1..=12)?);
+    cursor.eat('-')?;
+    let start = cursor.index;
+    parsed.day = Some((cursor.number(2, 2, Field::Day, 1..=31)?, start));
+    Ok(())
+}
+
+/// Reads `HH:MM[:SS[.fff]]` into `parsed`. Fractions finer than a millisecond are dropped.
+fn iso_time(cursor: &mut Cursor, parsed: &mut Parsed) -> Result<(), ParseError> {
+    parsed.hour = Some(cursor.number(2, 2, Field::Hour, 0..=23)?);
+    cursor.eat(':')?;
+    parsed.minute = Some(cursor.number(2, 2, Field::Minute, 0..=59)?);
+    if cursor.eat_if(|ch| ch == ':').is_none() {
+        return Ok(());
+    }
+    parsed.second = Some(cursor.number(2, 2, Field::Second, 0..=59)?);
+    if cursor.eat_if(|ch| ch == '.' || ch == ',').is_none() {
+        return Ok(());
+    }
+    let start = cursor.index;
+    while cursor.eat_if(|ch| ch.is_ascii_digit()).is_some() {}
+    if cursor.index == start {
+        return Err(cursor.error(ParseErrorKind::Expected(Expected::Digit)));
+    }
+    let digits: String = cursor.chars[start..cursor.index].iter().take(3).collect();
+    parsed.milli = format!("{digits:0<3}").parse().expect("three digits");
+    Ok(())
+}
+
+fn iso_datetime(cursor: &mut Cursor) -> Result<Parsed, ParseError> {
+    let mut parsed = Parsed::default();
+    iso_date(cursor, &mut parsed)?;
+    if cursor.eat_if(|ch| matches!(ch, 'T' | 't' | ' ')).is_none() {
+        return Err(cursor.error(ParseErrorKind::Expected(Expected::DateTimeSeparator)));
+    }
+    iso_time(cursor, &mut parsed)?;
+    Ok(parsed)
+}
+
+/// `Z` or `±HH[:]MM`.
+fn iso_offset(cursor: &mut Cursor) -> Result<FixedOffset, ParseError> {
+    if cursor.eat_if(|ch| ch == 'Z' || ch == 'z').is_some() {
+        return Ok(FixedOffset::UTC);
+    }
+    let start = cursor.index;
+    let Some(sign) = cursor.eat_if(|ch| ch == '+' || ch == '-') else {
+        return Err(cursor.error(ParseErrorKind::Expected(Expected::Offset)));
+    };
+    let hours = cursor.number(2, 2, Field::Offset, 0..=23)? as i32;
+    cursor.eat_if(|ch| ch == ':');
+    let minutes = cursor.number(2, 2, Field::Offset, 0..=59)? as i32;
+    let seconds = (hours * 60 + minutes) * 60;
+    FixedOffset::east_opt(if sign == '-' { -seconds } else { seconds })
+        .ok_or_else(|| cursor.error_at(start, ParseErrorKind::OutOfRange(Field::Offset)))
+}
+
+#[allow(dead_code)]
+impl NaiveDate {
+    /// Reads `input` laid out as `pattern`, using the specifiers of [`NaiveDate::format`]. Numbers may leave out
+    /// leading zeros, month names ignore case, and whitespace in the pattern matches any run of whitespace.
+    pub fn parse_from_str(input: &str, pattern: &str) -> Result<Self, ParseError> {
+        Parsed::from_pattern(input, pattern)?.date()
+    }
+}
+
+#[allow(dead_code)]
+impl NaiveTime {
+    /// Reads `input` laid out as `pattern` with `%H %I %p %M %S`; seconds default to 0.
+    pub fn parse_from_str(input: &str, pattern: &str) -> Result<Self, ParseError> {
+        Parsed::from_pattern(input, pattern)?.time()
+    }
+}
+
+#[allow(dead_code)]
+impl NaiveDateTime {
+    /// Reads `input` laid out as `pattern` with the date and time specifiers; seconds default to 0.
+    pub fn parse_from_str(input: &str, pattern: &str) -> Result<Self, ParseError> {
+        let parsed = Parsed::from_pattern(input, pattern)?;
+        Ok(parsed.date()?.and_time(parsed.time()?))
+    }
+}
+
+/// ISO 8601 calendar date, `2024-06-30`.
+impl FromStr for NaiveDate {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(input);
+        let mut parsed = Parsed::default();
+        iso_date(&mut cursor, &mut parsed)?;
+        cursor.finish()?;
+        parsed.date()
+    }
+}
+
+/// ISO 8601 time of day, `09:30`, `09:30:15` or `09:30:15.250`.
+impl FromStr for NaiveTime {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(input);
+        let mut parsed = Parsed::default();
+        iso_time(&mut cursor, &mut parsed)?;
+        cursor.finish()?;
+        parsed.time()
+    }
+}
+
+/// ISO 8601 date and time without an offset, `2024-06-30T09:30:15`; a space may stand in for the `T`.
+impl FromStr for NaiveDateTime {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(input);
+        let parsed = iso_datetime(&mut cursor)?;
+        cursor.finish()?;
+        Ok(parsed.date()?.and_time(parsed.time()?))
+    }
+}
+
+/// ISO 8601 date and time with an offset, `2024-06-30T09:30:15+08:00` or `2024-06-30T01:30:15Z`.
+impl FromStr for DateTime<FixedOffset> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(input);
+        let parsed = iso_datetime(&mut cursor)?;
+        let offset = iso_offset(&mut cursor)?;
+        cursor.finish()?;
+        let local = parsed.date()?.and_time(parsed.time()?);
+        Ok(DateTime::from_local(local, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn patterns_mirror_the_format_specifiers() {
+        assert_eq!(
+            NaiveDate::parse_from_str("2024-06-30", "%Y-%m-%d"),
+            Ok(date(2024, 6, 30))
+        );
+        assert_eq!(
+            NaiveDate::parse_from_str("2024/6/3", "%Y/%m/%d"),
+            Ok(date(2024, 6, 3))
+        );
+        assert_eq!(
+            NaiveDate::parse_from_str("20240630", "%Y%m%d"),
+            Ok(date(2024, 6, 30))
+        );
+        assert_eq!(
+            NaiveDate::parse_from_str("30 jun  2024", "%d %b %Y"),
+            Ok(date(2024, 6, 30))
+        );
+        assert_eq!(
+            NaiveDate::parse_from_str("June 30, 2024", "%B %d, %Y"),
+            Ok(date(2024, 6, 30))
+        );
+        assert_eq!(
+            NaiveDate::parse_from_str("2024年6月30日", "%Y年%m月%d日"),
+            Ok(date(2024, 6, 30))
+        );
+
+        let formatted = date(2024, 2, 29).format("%d %B %Y 100%%").to_string();
+        assert_eq!(
+            NaiveDate::parse_from_str(&formatted, "%d %B %Y 100%%"),
+            Ok(date(2024, 2, 29))
+        );
+        assert_eq!(
+            NaiveDateTime::parse_from_str("2024-06-30 9:05", "%Y-%m-%d %H:%M")
+                .map(|time| time.to_string()),
+            Ok("2024-06-30 09:05:00".to_string())
+        );
+        assert_eq!(
+            NaiveTime::parse_from_str("12:30 am", "%I:%M %p"),
+            Ok(NaiveTime::from_hms_opt(0, 30, 0).unwrap())
+        );
+        let afternoon = NaiveTime::from_hms_opt(15, 4, 5).unwrap();
+        assert_eq!(
+            NaiveTime::parse_from_str(&afternoon.format("%-I:%M:%S %p").to_string(), "%I:%M:%S %p"),
+            Ok(afternoon)
+        );
+        let err = NaiveTime::parse_from_str("3:04 XM", "%I:%M %p").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 'AM' or 'PM' at character 6, found 'X'"
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_character() {
+        let err = NaiveDate::parse_from_str("2024/06/30", "%Y-%m-%d").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::Expected(Expected::Char('-')));
+        assert_eq!((err.position(), err.found()), (4, Some('/')));
+        assert_eq!(err.to_string(), "expected '-' at character 5, found '/'");
+
+        let err = NaiveDate::parse_from_str("2024年13月1日", "%Y年%m月%d日").unwrap_err();
+        assert_eq!(
+            (err.kind(), err.position()),
+            (ParseErrorKind::OutOfRange(Field::Month), 5)
+        );
+
+        let err = NaiveDate::parse_from_str("2023-02-29", "%Y-%m-%d").unwrap_err();
+        assert_eq!(
+            (err.kind(), err.position()),
+            (ParseErrorKind::NoSuchDate, 8)
+        );
+
+        let err = NaiveDate::parse_from_str("2024-06-30x", "%Y-%m-%d").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected 'x' at character 11");
+        let err = NaiveDate::parse_from_str("2024-06", "%Y-%m-%d").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected '-' at character 8, found the end"
+        );
+        let err = NaiveDate::parse_from_str("2024-06", "%Y-%m").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::MissingField(Field::Day));
+        let err = NaiveDate::parse_from_str("2024", "%Y %j").unwrap_err();
+        assert_eq!(
+            (err.kind(), err.position()),
+            (ParseErrorKind::UnknownSpecifier('j'), 3)
+        );
+    }
+
+    #[test]
+    fn iso_8601_dates_and_datetimes() {
+        assert_eq!("2024-06-30".parse(), Ok(date(2024, 6, 30)));
+        assert!("2024-6-30".parse::<NaiveDate>().is_err());
+        assert_eq!(
+            "09:30:15.25".parse::<NaiveTime>(),
+            Ok(NaiveTime::from_hms_milli_opt(9, 30, 15, 250).unwrap())
+        );
+        assert_eq!(
+            "2024-06-30T09:30".parse::<NaiveDateTime>(),
+            Ok(date(2024, 6, 30).and_hms_opt(9, 30, 0).unwrap())
+        );
+        let err = "2024-06-30_09:30".parse::<NaiveDateTime>().unwrap_err();
+        assert_eq!(
+            (err.kind(), err.position()),
+            (ParseErrorKind::Expected(Expected::DateTimeSeparator), 10)
+        );
+
+        let shanghai = "2024-06-30T09:30:15+08:00"
+            .parse::<DateTime<FixedOffset>>()
+            .unwrap();
+        let utc = "2024-06-30T01:30:15Z"
+            .parse::<DateTime<FixedOffset>>()
+            .unwrap();
+        assert_eq!(shanghai, utc);
+        assert_eq!(shanghai.to_string(), "2024-06-30 09:30:15 +08:00");
+        let err = "2024-06-30T09:30:15"
+            .parse::<DateTime<FixedOffset>>()
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::Expected(Expected::Offset));
+    }
+}
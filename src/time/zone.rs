@@ -0,0 +1,626 @@
+//! UTC offsets, IANA time zones and datetimes that know their zone.
+//!
+//! Zones come from an embedded table holding the POSIX TZ rule each zone currently follows (the same rule tzdata
+//! writes at the end of its compiled files), so no network or operating-system lookup is needed. Only current rules
+//! are known: instants before a zone last changed its rules use today's offsets.
+
+use super::{Duration, FormattedDate, Locale, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+const SECONDS_PER_HOUR: i32 = 3_600;
+
+/// `(IANA name, label, POSIX TZ rule)`.
+const ZONES: &[(&str, &str, &str)] = &[
+    ("UTC", "协调世界时", "UTC0"),
+    ("Asia/Shanghai", "上海", "CST-8"),
+    ("Asia/Hong_Kong", "香港", "HKT-8"),
+    ("Asia/Taipei", "台北", "CST-8"),
+    ("Asia/Tokyo", "东京", "JST-9"),
+    ("Asia/Seoul", "首尔", "KST-9"),
+    ("Asia/Singapore", "新加坡", "<+08>-8"),
+    ("Asia/Bangkok", "曼谷", "<+07>-7"),
+    ("Asia/Kolkata", "加尔各答", "IST-5:30"),
+    ("Asia/Dubai", "迪拜", "<+04>-4"),
+    ("Europe/Moscow", "莫斯科", "MSK-3"),
+    ("Europe/Berlin", "柏林", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Paris", "巴黎", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/London", "伦敦", "GMT0BST,M3.5.0/1,M10.5.0"),
+    ("America/Sao_Paulo", "圣保罗", "<-03>3"),
+    ("America/New_York", "纽约", "EST5EDT,M3.2.0,M11.1.0"),
+    ("America/Chicago", "芝加哥", "CST6CDT,M3.2.0,M11.1.0"),
+    ("America/Denver", "丹佛", "MST7MDT,M3.2.0,M11.1.0"),
+    ("America/Phoenix", "凤凰城", "MST7"),
+    ("America/Los_Angeles", "洛杉矶", "PST8PDT,M3.2.0,M11.1.0"),
+    ("Pacific/Honolulu", "檀香山", "HST10"),
+    ("Australia/Sydney", "悉尼", "AEST-10AEDT,M10.1.0,M4.1.0/3"),
+    ("Pacific/Auckland", "奥克兰", "NZST-12NZDT,M9.5.0,M4.1.0/3"),
+    ("Pacific/Kiritimati", "圣诞岛", "<+14>-14"),
+];
+
+/// Something that maps instants to UTC offsets.
+pub trait TimeZone: Copy + Debug {
+    /// The offset in effect at the UTC instant `utc`.
+    fn offset_at(&self, utc: NaiveDateTime) -> FixedOffset;
+
+    /// The offset that turns the wall-clock time `local` into UTC. A time that happens twice when clocks go back
+    /// reads as the earlier instant; a time skipped when clocks go forward reads with the offset from before the
+    /// jump, which lands it after the jump.
+    fn offset_from_local(&self, local: NaiveDateTime) -> FixedOffset {
+        let before = self.offset_at(local - Duration::days(1));
+        let after = self.offset_at(local + Duration::days(1));
+        [before, after]
+            .into_iter()
+            .filter(|offset| self.offset_at(local - offset.duration()) == *offset)
+            .max()
+            .unwrap_or(before)
+    }
+}
+
+/// A constant offset from UTC, positive east of Greenwich.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct FixedOffset {
+    seconds: i32,
+}
+
+#[allow(dead_code)]
+impl FixedOffset {
+    pub const UTC: FixedOffset = FixedOffset { seconds: 0 };
+
+    /// `seconds` east of UTC; `None` unless it is within a day.
+    pub fn east_opt(seconds: i32) -> Option<Self> {
+        (seconds.abs() < 24 * SECONDS_PER_HOUR).then_some(Self { seconds })
+    }
+
+    /// `seconds` west of UTC; `None` unless it is within a day.
+    pub fn west_opt(seconds: i32) -> Option<Self> {
+        Self::east_opt(-seconds)
+    }
+
+    pub fn local_minus_utc(self) -> i32 {
+        self.seconds
+    }
+
+    fn duration(self) -> Duration {
+        Duration::seconds(self.seconds as i64)
+    }
+}
+
+impl TimeZone for FixedOffset {
+    fn offset_at(&self, _utc: NaiveDateTime) -> FixedOffset {
+        *self
+    }
+
+    fn offset_from_local(&self, _local: NaiveDateTime) -> FixedOffset {
+        *self
+    }
+}
+
+/// `+08:00`, `-03:30`, `+00:00`.
+impl Display for FixedOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let sign = if self.seconds < 0 { '-' } else { '+' };
+        let minutes = self.seconds.unsigned_abs() / 60;
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+/// An IANA time zone from the embedded table, stored by its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tz {
+    key: &'static str,
+    label: &'static str,
+    rule: PosixRule,
+}
+
+#[allow(dead_code)]
+impl Tz {
+    pub fn utc() -> Self {
+        Self::from_key("UTC").expect("UTC is in the zone table")
+    }
+
+    /// The IANA name, e.g. `Asia/Shanghai`.
+    pub fn key(self) -> &'static str {
+        self.key
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        ZONES
+            .iter()
+            .find(|(name, _, _)| *name == key)
+            .and_then(|(key, label, rule)| {
+                Some(Self {
+                    key,
+                    label,
+                    rule: PosixRule::parse(rule)?,
+                })
+            })
+    }
+
+    /// A city name for pickers, e.g. `上海`.
+    pub fn label(self) -> &'static str {
+        self.label
+    }
+
+    pub fn all() -> Vec<Tz> {
+        ZONES
+            .iter()
+            .filter_map(|(key, _, _)| Self::from_key(key))
+            .collect()
+    }
+
+    /// The zone abbreviation at `utc`, e.g. `CST`, `EDT` or `+08`.
+    pub fn abbreviation_at(self, utc: NaiveDateTime) -> &'static str {
+        match self.rule.daylight {
+            Some(daylight) if self.rule.in_daylight_time(daylight, utc) => daylight.abbreviation,
+            _ => self.rule.abbreviation,
+        }
+    }
+}
+
+impl TimeZone for Tz {
+    fn offset_at(&self, utc: NaiveDateTime) -> FixedOffset {
+        match self.rule.daylight {
+            Some(daylight) if self.rule.in_daylight_time(daylight, utc) => daylight.offset,
+            _ => self.rule.offset,
+        }
+    }
+}
+
+impl Display for Tz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key)
+    }
+}
+
+impl Serialize for Tz {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.key)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tz {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        Tz::from_key(&key).ok_or_else(|| de::Error::custom(format!("unknown time zone: {key}")))
+    }
+}
+
+/// A POSIX TZ rule such as `EST5EDT,M3.2.0,M11.1.0`. Offsets in the rule count west of UTC; they are stored east.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct PosixRule {
+    abbreviation: &'static str,
+    offset: FixedOffset,
+    daylight: Option<Daylight>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Daylight {
+    abbreviation: &'static str,
+    offset: FixedOffset,
+    /// Wall-clock time in standard time when daylight time starts.
+    start: Transition,
+    /// Wall-clock time in daylight time when it ends.
+    end: Transition,
+}
+
+/// `Mm.w.d[/time]`: weekday `d` (0 is Sunday) of week `w` of month `m`, where week 5 is the last one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Transition {
+    month: u32,
+    week: u32,
+    weekday: u32,
+    seconds: i32,
+}
+
+impl PosixRule {
+    fn parse(spec: &'static str) -> Option<Self> {
+        let mut rest = spec;
+        let abbreviation = take_abbreviation(&mut rest)?;
+        let offset = FixedOffset::west_opt(take_seconds(&mut rest)?)?;
+        if rest.is_empty() {
+            return Some(Self {
+                abbreviation,
+                offset,
+                daylight: None,
+            });
+        }
+
+        let daylight_abbreviation = take_abbreviation(&mut rest)?;
+        let daylight_offset = if rest.starts_with(',') {
+            FixedOffset::east_opt(offset.seconds + SECONDS_PER_HOUR)?
+        } else {
+            FixedOffset::west_opt(take_seconds(&mut rest)?)?
+        };
+        let (start, end) = rest.strip_prefix(',')?.split_once(',')?;
+        Some(Self {
+            abbreviation,
+            offset,
+            daylight: Some(Daylight {
+                abbreviation: daylight_abbreviation,
+                offset: daylight_offset,
+                start: Transition::parse(start)?,
+                end: Transition::parse(end)?,
+            }),
+        })
+    }
+
+    fn in_daylight_time(&self, daylight: Daylight, utc: NaiveDateTime) -> bool {
+        let year = (utc + self.offset.duration()).date().year();
+        let start = daylight.start.local_in(year) - self.offset.duration();
+        let end = daylight.end.local_in(year) - daylight.offset.duration();
+        if start < end {
+            start <= utc && utc < end
+        } else {
+            // Southern hemisphere: daylight time spans the turn of the year.
+            !(end <= utc && utc < start)
+        }
+    }
+}
+
+impl Transition {
+    fn parse(spec: &str) -> Option<Self> {
+        let (date, time) = match spec.split_once('/') {
+            Some((date, time)) => (date, Some(time)),
+            None => (spec, None),
+        };
+        let mut fields = date.strip_prefix('M')?.split('.');
+        let mut field = || fields.next()?.parse::<u32>().ok();
+        let (month, week, weekday) = (field()?, field()?, field()?);
+        if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+            return None;
+        }
+        let seconds = match time {
+            Some(mut time) => {
+                let seconds = take_seconds(&mut time)?;
+                time.is_empty().then_some(seconds)?
+            }
+            None => 2 * SECONDS_PER_HOUR,
+        };
+        Some(Self {
+            month,
+            week,
+            weekday,
+            seconds,
+        })
+    }
+
+    fn local_in(self, year: i32) -> NaiveDateTime {
+        let first = NaiveDate::from_ymd_opt(year, self.month, 1).expect("valid month");
+        let first_weekday = (first.weekday().num_days_from_monday() as u32 + 1) % 7;
+        let mut day = 1 + (self.weekday + 7 - first_weekday) % 7 + (self.week - 1) * 7;
+        while first.with_day(day).is_none() {
+            day -= 7;
+        }
+        first
+            .with_day(day)
+            .expect("day within month")
+            .and_time(NaiveTime::MIDNIGHT)
+            + Duration::seconds(self.seconds as i64)
+    }
+}
+
+/// An abbreviation: letters, or anything between `<` and `>` such as `<+08>`.
+fn take_abbreviation(rest: &mut &'static str) -> Option<&'static str> {
+    let (abbreviation, tail) = match rest.strip_prefix('<') {
+        Some(quoted) => {
+            let (abbreviation, tail) = quoted.split_once('>')?;
+            (abbreviation, tail)
+        }
+        None => {
+            let end = rest
+                .find(|ch: char| !ch.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            rest.split_at(end)
+        }
+    };
+    *rest = tail;
+    (!abbreviation.is_empty()).then_some(abbreviation)
+}
+
+/// `[+-]hh[:mm[:ss]]` as seconds.
+fn take_seconds(rest: &mut &str) -> Option<i32> {
+    let (sign, unsigned) = match rest.as_bytes().first() {
+        Some(b'-') => (-1, &rest[1..]),
+        Some(b'+') => (1, &rest[1..]),
+        _ => (1, *rest),
+    };
+    let end = unsigned
+        .find(|ch: char| !ch.is_ascii_digit() && ch != ':')
+        .unwrap_or(unsigned.len());
+    let (digits, tail) = unsigned.split_at(end);
+    let mut seconds = 0;
+    let mut unit = SECONDS_PER_HOUR;
+    for part in digits.split(':') {
+        if unit == 0 {
+            return None;
+        }
+        seconds += part.parse::<i32>().ok()? * unit;
+        unit /= 60;
+    }
+    *rest = tail;
+    Some(sign * seconds)
+}
+
+/// An instant together with the zone it is shown in. Comparisons, hashing and subtraction look at the instant
+/// only, so the same moment in two zones is equal.
+#[derive(Clone, Copy, Debug)]
+pub struct DateTime<Z: TimeZone> {
+    utc: NaiveDateTime,
+    offset: FixedOffset,
+    zone: Z,
+}
+
+#[allow(dead_code)]
+impl<Z: TimeZone> DateTime<Z> {
+    pub fn from_utc(utc: NaiveDateTime, zone: Z) -> Self {
+        Self {
+            utc,
+            offset: zone.offset_at(utc),
+            zone,
+        }
+    }
+
+    /// The instant the wall-clock time `local` shows in `zone`; see [`TimeZone::offset_from_local`] for times
+    /// around daylight-saving changes.
+    pub fn from_local(local: NaiveDateTime, zone: Z) -> Self {
+        Self::from_utc(local - zone.offset_from_local(local).duration(), zone)
+    }
+
+    pub fn now(zone: Z) -> Self {
+        Self::from_utc(NaiveDateTime::now_utc(), zone)
+    }
+
+    pub fn naive_utc(self) -> NaiveDateTime {
+        self.utc
+    }
+
+    /// The wall-clock time in the zone.
+    pub fn naive_local(self) -> NaiveDateTime {
+        self.utc + self.offset.duration()
+    }
+
+    pub fn date_naive(self) -> NaiveDate {
+        self.naive_local().date()
+    }
+
+    pub fn time(self) -> NaiveTime {
+        self.naive_local().time()
+    }
+
+    pub fn offset(self) -> FixedOffset {
+        self.offset
+    }
+
+    pub fn timezone(self) -> Z {
+        self.zone
+    }
+
+    pub fn timestamp_millis(self) -> i64 {
+        self.utc.timestamp_millis()
+    }
+
+    /// The same instant in another zone.
+    pub fn with_timezone<Other: TimeZone>(self, zone: Other) -> DateTime<Other> {
+        DateTime::from_utc(self.utc, zone)
+    }
+
+    /// Formats the wall-clock time in the zone.
+    pub fn format<'a>(self, pattern: &'a str) -> FormattedDate<'a> {
+        self.naive_local().format(pattern)
+    }
+
+    /// Formats the wall-clock time in the zone with month and weekday names in `locale`.
+    pub fn format_localized<'a>(self, pattern: &'a str, locale: Locale) -> FormattedDate<'a> {
+        self.naive_local().format_localized(pattern, locale)
+    }
+}
+
+impl<Z: TimeZone> PartialEq for DateTime<Z> {
+    fn eq(&self, other: &Self) -> bool {
+        self.utc == other.utc
+    }
+}
+
+impl<Z: TimeZone> Eq for DateTime<Z> {}
+
+impl<Z: TimeZone> PartialOrd for DateTime<Z> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Z: TimeZone> Ord for DateTime<Z> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.utc.cmp(&other.utc)
+    }
+}
+
+impl<Z: TimeZone> Hash for DateTime<Z> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.utc.hash(state);
+    }
+}
+
+impl<Z: TimeZone> Add<Duration> for DateTime<Z> {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self::from_utc(self.utc + rhs, self.zone)
+    }
+}
+
+impl<Z: TimeZone> AddAssign<Duration> for DateTime<Z> {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl<Z: TimeZone> Sub<Duration> for DateTime<Z> {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self::from_utc(self.utc - rhs, self.zone)
+    }
+}
+
+impl<Z: TimeZone> SubAssign<Duration> for DateTime<Z> {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl<Z: TimeZone> Sub for DateTime<Z> {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.utc - rhs.utc
+    }
+}
+
+/// `2024-06-30 20:00:00 +08:00`.
+impl<Z: TimeZone> Display for DateTime<Z> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.naive_local(), self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(
+        (year, month, day): (i32, u32, u32),
+        (hour, minute, second): (u32, u32, u32),
+    ) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .unwrap()
+    }
+
+    fn zone(key: &str) -> Tz {
+        Tz::from_key(key).unwrap()
+    }
+
+    #[test]
+    fn every_zone_in_the_table_parses() {
+        assert_eq!(Tz::all().len(), ZONES.len());
+        assert_eq!(Tz::from_key("Mars/Olympus"), None);
+        assert_eq!(
+            zone("Asia/Kolkata")
+                .offset_at(at((2024, 1, 1), (0, 0, 0)))
+                .to_string(),
+            "+05:30"
+        );
+        assert_eq!(
+            FixedOffset::west_opt(3 * 3_600 + 1_800)
+                .unwrap()
+                .to_string(),
+            "-03:30"
+        );
+        assert_eq!(FixedOffset::east_opt(24 * 3_600), None);
+    }
+
+    #[test]
+    fn daylight_time_follows_the_rules_on_both_hemispheres() {
+        let new_york = zone("America/New_York");
+        // 2024: clocks go forward on 10 March at 07:00 UTC and back on 3 November at 06:00 UTC.
+        assert_eq!(
+            new_york
+                .offset_at(at((2024, 3, 10), (6, 59, 59)))
+                .to_string(),
+            "-05:00"
+        );
+        assert_eq!(
+            new_york.offset_at(at((2024, 3, 10), (7, 0, 0))).to_string(),
+            "-04:00"
+        );
+        assert_eq!(new_york.abbreviation_at(at((2024, 7, 1), (0, 0, 0))), "EDT");
+        assert_eq!(
+            new_york.offset_at(at((2024, 11, 3), (6, 0, 0))).to_string(),
+            "-05:00"
+        );
+
+        let london = zone("Europe/London");
+        assert_eq!(london.abbreviation_at(at((2024, 3, 31), (1, 0, 0))), "BST");
+        assert_eq!(london.abbreviation_at(at((2024, 10, 27), (1, 0, 0))), "GMT");
+
+        let sydney = zone("Australia/Sydney");
+        assert_eq!(
+            sydney.offset_at(at((2024, 1, 15), (0, 0, 0))).to_string(),
+            "+11:00"
+        );
+        assert_eq!(
+            sydney.offset_at(at((2024, 6, 15), (0, 0, 0))).to_string(),
+            "+10:00"
+        );
+        // Daylight time ends on 7 April 2024 at 03:00 local daylight time, 16:00 UTC the day before.
+        assert_eq!(
+            sydney.offset_at(at((2024, 4, 6), (15, 59, 59))).to_string(),
+            "+11:00"
+        );
+        assert_eq!(
+            sydney.offset_at(at((2024, 4, 6), (16, 0, 0))).to_string(),
+            "+10:00"
+        );
+    }
+
+    #[test]
+    fn local_times_resolve_around_clock_changes() {
+        let new_york = zone("America/New_York");
+        // 02:30 does not exist on 10 March 2024 and lands after the jump.
+        let skipped = DateTime::from_local(at((2024, 3, 10), (2, 30, 0)), new_york);
+        assert_eq!(skipped.to_string(), "2024-03-10 03:30:00 -04:00");
+        // 01:30 happens twice on 3 November 2024; the first one is picked.
+        let repeated = DateTime::from_local(at((2024, 11, 3), (1, 30, 0)), new_york);
+        assert_eq!(repeated.to_string(), "2024-11-03 01:30:00 -04:00");
+        assert_eq!(
+            (repeated + Duration::hours(1)).to_string(),
+            "2024-11-03 01:30:00 -05:00"
+        );
+    }
+
+    #[test]
+    fn instants_convert_between_zones() {
+        let placed = DateTime::from_local(at((2024, 6, 30), (23, 30, 0)), zone("Asia/Shanghai"));
+        assert_eq!(placed.naive_utc(), at((2024, 6, 30), (15, 30, 0)));
+
+        let in_london = placed.with_timezone(zone("Europe/London"));
+        assert_eq!(in_london.to_string(), "2024-06-30 16:30:00 +01:00");
+        assert_eq!(in_london, placed);
+
+        let in_los_angeles = placed.with_timezone(zone("America/Los_Angeles"));
+        assert_eq!(
+            in_los_angeles.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()
+        );
+        let in_auckland = placed.with_timezone(zone("Pacific/Auckland"));
+        assert_eq!(
+            in_auckland.format("%Y-%m-%d %H:%M").to_string(),
+            "2024-07-01 03:30"
+        );
+
+        let fixed = placed.with_timezone(FixedOffset::east_opt(9 * 3_600).unwrap());
+        assert_eq!(fixed.naive_utc(), placed.naive_utc());
+        assert_eq!(fixed.time(), NaiveTime::from_hms_opt(0, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn zones_display_as_their_name() {
+        assert_eq!(zone("Europe/Paris").to_string(), "Europe/Paris");
+        assert_eq!(
+            Tz::from_key(&zone("Europe/Paris").to_string()),
+            Some(zone("Europe/Paris"))
+        );
+        assert_eq!(
+            zone("Asia/Singapore").abbreviation_at(at((2024, 1, 1), (0, 0, 0))),
+            "+08"
+        );
+    }
+}
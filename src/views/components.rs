@@ -2,23 +2,77 @@ use crate::components::ui::{
     Accordion, AccordionContent, AccordionItem, AccordionTrigger, Alert, AlertVariant, AspectRatio,
     Avatar, Badge, BadgeVariant, Breadcrumb, Button, ButtonSize, ButtonVariant, Calendar, Card,
     CardContent, CardDescription, CardFooter, CardHeader, CardTitle, Checkbox, Collapsible,
-    CollapsibleContent, CollapsibleTrigger, Combobox, ComboboxOption, CommandItem, CommandPalette,
-    ContextItem, ContextMenu, Crumb, DateRange, DateRangePicker, Dialog, DropdownMenu,
-    DropdownMenuItem, FileDropZone, FileMetadata, FormField, FormMessage, FormMessageVariant,
-    HoverCard, Input, Label, Menubar, MenubarItem, MenubarMenu, NavigationItem, NavigationMenu,
-    Pagination, Popover, Progress, RadioGroup, RadioGroupItem, ScrollArea, Select, SelectOption,
-    Separator, SeparatorOrientation, Sheet, SheetSide, Sidebar, SidebarContent, SidebarFooter,
-    SidebarGroup, SidebarGroupContent, SidebarGroupLabel, SidebarHeader, SidebarInset,
-    SidebarLayout, SidebarMenu, SidebarMenuButton, SidebarMenuItem, SidebarSeparator,
+    CollapsibleContent, CollapsibleTrigger, ColumnSortKind, Combobox, ComboboxOption, CommandItem,
+    CommandPalette, ContextItem, ContextMenu, Crumb, DatePicker, DateRange, DateRangeComparison,
+    DateRangePicker, DateRangePreset, DateTimePicker, Dialog, DropdownMenu, DropdownMenuItem,
+    FileDropZone, FileMetadata, FormField, FormMessage, FormMessageVariant, HoverCard, Input,
+    InteractiveTable, Label, Menubar, MenubarItem, MenubarMenu, NavigationItem, NavigationMenu,
+    Pagination, Popover, Progress, RadioGroup, RadioGroupItem, RowHeight, ScrollArea, Select,
+    SelectOption, Separator, SeparatorOrientation, Sheet, SheetSide, Sidebar, SidebarContent,
+    SidebarFooter, SidebarGroup, SidebarGroupContent, SidebarGroupLabel, SidebarHeader,
+    SidebarInset, SidebarLayout, SidebarMenu, SidebarMenuButton, SidebarMenuItem, SidebarSeparator,
     SidebarTrigger, Skeleton, Slider, StepItem, Steps, Switch, Table, TableBody, TableCaption,
-    TableCell, TableFooter, TableHead, TableHeader, TableRow, Tabs, TabsContent, TabsList,
-    TabsTrigger, Textarea, Toast, ToastViewport, Toggle, ToggleGroup, ToggleGroupItem,
-    ToggleGroupMode, ToggleGroupOrientation, Tooltip,
+    TableCell, TableColumnConfig, TableFooter, TableHead, TableHeader, TableRecord, TableRow,
+    TableRowData, TableSort, TableVirtualization, Tabs, TabsContent, TabsList, TabsTrigger,
+    Textarea, TimePicker, Toast, ToastViewport, Toggle, ToggleGroup, ToggleGroupItem,
+    ToggleGroupMode, ToggleGroupOrientation, Tooltip, VirtualTable,
 };
-use crate::time::NaiveDate;
+use crate::i18n::use_i18n;
+use crate::time::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use dioxus::html::events::FormEvent;
 use dioxus::prelude::*;
 
+/// A generated CI run for the virtualized table demo. Cell text is derived from the run number, so tens of
+/// thousands of rows cost little more than the numbers themselves.
+#[derive(Clone, PartialEq)]
+struct BuildRun {
+    number: u32,
+}
+
+impl TableRecord for BuildRun {
+    fn row_id(&self) -> String {
+        self.number.to_string()
+    }
+
+    fn cell_text(&self, column_id: &str) -> String {
+        match column_id {
+            "run" => format!("#{}", self.number),
+            "branch" => ["main", "release", "feature/billing", "fix/login"]
+                [self.number as usize % 4]
+                .to_string(),
+            "duration" => format!("{}s", 30 + (self.number * 37) % 600),
+            "result" => if self.number.is_multiple_of(11) {
+                "Failed"
+            } else {
+                "Passed"
+            }
+            .to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Position of a deploy status in the pipeline, for sorting by progress rather than by name.
+fn pipeline_stage(status: &str) -> i64 {
+    ["Queued", "Building", "Review", "Shipping", "Paused"]
+        .iter()
+        .position(|stage| *stage == status)
+        .map_or(i64::MAX, |index| index as i64)
+}
+
+/// Age in minutes of labels such as `14 minutes ago` or `2 hours ago`.
+fn minutes_ago(updated: &str) -> i64 {
+    let mut parts = updated.split_whitespace();
+    let amount: i64 = parts
+        .next()
+        .and_then(|amount| amount.parse().ok())
+        .unwrap_or(i64::MAX);
+    match parts.next() {
+        Some(unit) if unit.starts_with("hour") => amount.saturating_mul(60),
+        _ => amount,
+    }
+}
+
 #[component]
 pub fn Components() -> Element {
     rsx! {
@@ -69,6 +123,12 @@ fn UiShowcase() -> Element {
         ))
     });
     let dropzone_files = use_signal(|| Vec::<FileMetadata>::new());
+    let i18n = use_i18n();
+    let compare_periods = use_signal(|| true);
+    let comparison = use_signal(|| None::<DateRangeComparison>);
+    let delivery_date = use_signal(|| NaiveDate::from_ymd_opt(2024, 6, 12));
+    let pickup_time = use_signal(|| NaiveTime::from_hms_opt(9, 30, 0));
+    let shipment_at = use_signal(|| None::<NaiveDateTime>);
     let slider_value_signal = slider_value.clone();
     let slider_value_setter = slider_value.clone();
     let contact_method_signal = contact_method.clone();
@@ -156,6 +216,30 @@ fn UiShowcase() -> Element {
         Some(range) => format!("Single day: {}", range.start.format("%b %d, %Y")),
         None => "Pick a date window to compare analytics.".to_string(),
     };
+    let comparison_preview = move || match comparison() {
+        Some(DateRangeComparison { previous, .. }) => format!(
+            "Compared with {} → {}",
+            previous.start.format("%b %d"),
+            previous.end.format("%b %d %Y")
+        ),
+        None => "Not comparing with a previous period.".to_string(),
+    };
+    let delivery_preview = move || match delivery_date() {
+        Some(date) => format!("Delivery on {}", date.format("%A, %b %d, %Y")),
+        None => "Type a weekday in 2024, or pick one from the calendar.".to_string(),
+    };
+    let shipment_preview = move || match (pickup_time(), shipment_at()) {
+        (Some(pickup), Some(at)) => format!(
+            "Pickup at {}, ships {}",
+            pickup.format("%H:%M"),
+            at.format("%b %d, %Y %H:%M")
+        ),
+        (Some(pickup), None) => format!(
+            "Pickup at {}, shipment not scheduled.",
+            pickup.format("%H:%M")
+        ),
+        (None, _) => "Pick a pickup time.".to_string(),
+    };
     let dropzone_summary = move || {
         let files = dropzone_files_signal();
         if files.is_empty() {
@@ -208,6 +292,11 @@ fn UiShowcase() -> Element {
             Some("Dive into the latest Dioxus 0.7 docs"),
         ),
     ];
+    let build_runs = use_hook(|| {
+        (1..=50_000)
+            .map(|number| BuildRun { number })
+            .collect::<Vec<_>>()
+    });
     let table_rows = vec![
         ("DW-9021", "Realtime dashboard", "Shipping", "2 minutes ago"),
         (
@@ -593,12 +682,59 @@ fn UiShowcase() -> Element {
                                             move |range| setter.set(range)
                                         },
                                         initial_month: Some(NaiveDate::from_ymd_opt(2024, 6, 1).expect("valid month")),
+                                        presets: vec![
+                                            DateRangePreset::last_days(7, i18n),
+                                            DateRangePreset::last_days(30, i18n),
+                                            DateRangePreset::previous_month(i18n),
+                                            DateRangePreset::new("Black Friday week", |today: NaiveDate, _| {
+                                                let november = NaiveDate::from_ymd_opt(today.year(), 11, 22).expect("valid date");
+                                                DateRange::new(november, november + Duration::days(6))
+                                            }),
+                                        ],
+                                        max_span: 31,
+                                        compare: compare_periods,
+                                        on_compare_change: {
+                                            let mut setter = comparison;
+                                            move |value| setter.set(value)
+                                        },
                                     }
                                     FormMessage {
                                         variant: FormMessageVariant::Helper,
                                         class: Some("ui-field-helper".to_string()),
                                         "{range_preview()}"
                                     }
+                                    FormMessage {
+                                        variant: FormMessageVariant::Helper,
+                                        class: Some("ui-field-helper".to_string()),
+                                        "{comparison_preview()}"
+                                    }
+                                    DatePicker {
+                                        value: delivery_date,
+                                        pattern: "%Y/%m/%d",
+                                        min: NaiveDate::from_ymd_opt(2024, 1, 1),
+                                        max: NaiveDate::from_ymd_opt(2024, 12, 31),
+                                        disabled_date: |date: NaiveDate| date.weekday().num_days_from_monday() >= 5,
+                                    }
+                                    FormMessage {
+                                        variant: FormMessageVariant::Helper,
+                                        class: Some("ui-field-helper".to_string()),
+                                        "{delivery_preview()}"
+                                    }
+                                    TimePicker {
+                                        value: pickup_time,
+                                        minute_step: 15,
+                                        min: NaiveTime::from_hms_opt(8, 0, 0),
+                                        max: NaiveTime::from_hms_opt(18, 0, 0),
+                                    }
+                                    DateTimePicker {
+                                        value: shipment_at,
+                                        minute_step: 5,
+                                    }
+                                    FormMessage {
+                                        variant: FormMessageVariant::Helper,
+                                        class: Some("ui-field-helper".to_string()),
+                                        "{shipment_preview()}"
+                                    }
                                 }
                             }
                         }
@@ -743,6 +879,59 @@ fn UiShowcase() -> Element {
                                         "Keep automation quick by streaming the hottest rows into view."
                                     }
                                     Separator { style: "margin: 1rem 0;" }
+                                    SpanHelper { "Sortable pipelines" }
+                                    InteractiveTable {
+                                        columns: vec![
+                                            TableColumnConfig::new("id", "ID").fixed().sortable(ColumnSortKind::Text),
+                                            TableColumnConfig::new("project", "Project").sortable(ColumnSortKind::Text),
+                                            TableColumnConfig::new("status", "Status")
+                                                .sortable(ColumnSortKind::Custom(pipeline_stage)),
+                                            TableColumnConfig::new("updated", "Updated")
+                                                .sortable(ColumnSortKind::Custom(minutes_ago)),
+                                        ],
+                                        rows: table_rows
+                                            .iter()
+                                            .map(|(id, name, status, updated)| {
+                                                TableRowData::from_pairs(
+                                                    *id,
+                                                    [("id", *id), ("project", *name), ("status", *status), ("updated", *updated)],
+                                                )
+                                            })
+                                            .collect::<Vec<_>>(),
+                                        sort: vec![TableSort::asc("status")],
+                                    }
+                                    Separator { style: "margin: 1rem 0;" }
+                                    SpanHelper { "Build history · 50,000 runs" }
+                                    InteractiveTable {
+                                        columns: vec![
+                                            TableColumnConfig::new("run", "Run").fixed(),
+                                            TableColumnConfig::new("branch", "Branch").sortable(ColumnSortKind::Text),
+                                            TableColumnConfig::new("duration", "Duration").sortable(ColumnSortKind::Numeric),
+                                            TableColumnConfig::new("result", "Result"),
+                                        ],
+                                        rows: build_runs.clone(),
+                                        virtualization: TableVirtualization::new("320px", RowHeight::Measured { estimate: 49.0 }),
+                                    }
+                                    Separator { style: "margin: 1rem 0;" }
+                                    SpanHelper { "Event stream · 100,000 events" }
+                                    VirtualTable {
+                                        row_count: 100_000,
+                                        columns: 3,
+                                        virtualization: TableVirtualization::new("240px", RowHeight::Fixed(41.0)).overscan(10),
+                                        render_row: move |index: usize| rsx! {
+                                            TableCell { {format!("{:05}", index + 1)} }
+                                            TableCell { {BuildRun { number: index as u32 }.cell_text("branch")} }
+                                            TableCell { class: Some("ui-field-helper".to_string()), "heartbeat received" }
+                                        },
+                                        TableHeader {
+                                            TableRow {
+                                                TableHead { "Event" }
+                                                TableHead { "Source" }
+                                                TableHead { "Message" }
+                                            }
+                                        }
+                                    }
+                                    Separator { style: "margin: 1rem 0;" }
                                     SpanHelper { "Activity feed" }
                                     ScrollArea {
                                         max_height: Some("140px".to_string()),
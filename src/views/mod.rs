@@ -2,7 +2,8 @@
 //! enum will render one of these components.
 //!
 //!
-//! The [`Home`] and [`Components`] views back the dashboard and component gallery routes.
+//! The [`Home`] and [`Components`] views back the dashboard and component gallery routes, while [`Orders`] and
+//! [`OrderDetail`] cover the order list and a single order.
 //!
 //!
 //! The [`Navbar`] component will be rendered on all pages of our app since every page is under the layout. The layout defines
@@ -10,10 +11,15 @@
 
 mod components;
 mod home;
+mod order_bulk;
+mod order_detail;
+mod order_import;
+mod order_views;
 mod orders;
 
 pub use components::Components;
 pub use home::Home;
+pub use order_detail::OrderDetail;
 pub use orders::Orders;
 
 mod navbar;
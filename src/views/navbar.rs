@@ -1,18 +1,85 @@
 use crate::{
     components::ui::{
-        Button, ButtonSize, ButtonVariant, Sidebar, SidebarContent, SidebarFooter, SidebarGroup,
-        SidebarGroupContent, SidebarGroupLabel, SidebarHeader, SidebarInset, SidebarLayout,
-        SidebarMenu, SidebarMenuButton, SidebarMenuItem, SidebarRail, SidebarSeparator,
+        Breadcrumb, Button, ButtonSize, ButtonVariant, Crumb, Select, SelectOption, Sidebar,
+        SidebarContent, SidebarFooter, SidebarGroup, SidebarGroupContent, SidebarGroupLabel,
+        SidebarHeader, SidebarInset, SidebarLayout, SidebarMenu, SidebarMenuButton,
+        SidebarMenuItem, SidebarRail, SidebarSeparator,
     },
+    i18n::I18n,
+    orders::query::OrderQuery,
+    platform::{load_setting, save_setting},
+    time::{business_zone, set_business_zone, Locale, NaiveDateTime, TimeZone, Tz},
     Route,
 };
 use dioxus::prelude::*;
 
-fn page_title(route: &Route) -> &'static str {
+/// Setting the business time zone picked in the top bar is stored under.
+const BUSINESS_ZONE_SETTING: &str = "business.time_zone";
+
+/// Setting the language picked in the top bar is stored under.
+const LOCALE_SETTING: &str = "app.locale";
+
+/// Every language under its own name.
+fn locale_options() -> Vec<SelectOption> {
+    Locale::all()
+        .iter()
+        .map(|locale| SelectOption::new(locale.label(), locale.key()))
+        .collect()
+}
+
+/// `(UTC+08:00) 上海` for every zone, with the offset in effect right now.
+fn zone_options(i18n: I18n) -> Vec<SelectOption> {
+    let now = NaiveDateTime::now_utc();
+    Tz::all()
+        .into_iter()
+        .map(|zone| {
+            let name = i18n.t(&format!("zone.{}", zone.key()));
+            let label = format!("(UTC{}) {name}", zone.offset_at(now));
+            SelectOption::new(label, zone.key())
+        })
+        .collect()
+}
+
+fn page_title(route: &Route, i18n: I18n) -> String {
+    i18n.t(match route {
+        Route::Home {} => "nav.title.home",
+        Route::Components {} => "nav.title.components",
+        Route::Orders { .. } => "nav.title.orders",
+        Route::OrderDetail { .. } => "nav.title.order_detail",
+    })
+}
+
+/// Trail from the dashboard to the current page. Every crumb except the last links to its route.
+fn breadcrumbs(route: &Route, i18n: I18n) -> Vec<Crumb> {
+    let dashboard = || Crumb::new(i18n.t("nav.dashboard"), Some(Route::Home {}.to_string()));
+    let orders = || {
+        Crumb::new(
+            i18n.t("nav.orders"),
+            Some(
+                Route::Orders {
+                    query: OrderQuery::default(),
+                }
+                .to_string(),
+            ),
+        )
+    };
     match route {
-        Route::Home {} => "Dashboard overview",
-        Route::Components {} => "Component library",
-        Route::Orders {} => "Order management",
+        Route::Home {} => vec![Crumb::new(i18n.t("nav.dashboard"), None::<String>)],
+        Route::Components {} => vec![
+            dashboard(),
+            Crumb::new(i18n.t("nav.components"), None::<String>),
+        ],
+        Route::Orders { .. } => vec![
+            dashboard(),
+            Crumb::new(i18n.t("nav.orders"), None::<String>),
+        ],
+        Route::OrderDetail { number } => {
+            vec![
+                dashboard(),
+                orders(),
+                Crumb::new(number.clone(), None::<String>),
+            ]
+        }
     }
 }
 
@@ -26,18 +93,41 @@ pub fn Navbar() -> Element {
         "ui-shell shadcn"
     };
 
-    let _title = page_title(&current_route);
+    // Components translate through `use_i18n`, which reads this context, so switching the language below
+    // re-renders everything in the new one.
+    let mut locale = use_context_provider(|| Signal::new(Locale::default()));
+    use_future(move || async move {
+        if let Some(stored) = load_setting::<Locale>(LOCALE_SETTING).await {
+            locale.set(stored);
+        }
+    });
+    let i18n = I18n::new(locale());
+
+    let _title = page_title(&current_route, i18n);
     let is_dashboard = matches!(current_route, Route::Home { .. });
     let is_components = matches!(current_route, Route::Components { .. });
-    let is_orders = matches!(current_route, Route::Orders { .. });
+    let is_orders = matches!(
+        current_route,
+        Route::Orders { .. } | Route::OrderDetail { .. }
+    );
+    let crumbs = breadcrumbs(&current_route, i18n);
+
+    // Pages read the business time zone from this context so "today" follows the zone picked below.
+    let mut zone = use_context_provider(|| Signal::new(business_zone()));
+    use_future(move || async move {
+        if let Some(stored) = load_setting::<Tz>(BUSINESS_ZONE_SETTING).await {
+            set_business_zone(stored);
+            zone.set(stored);
+        }
+    });
 
     let theme_label = {
         let is_dark = is_dark.clone();
         move || {
             if is_dark() {
-                "Light mode"
+                i18n.t("nav.light_mode")
             } else {
-                "Dark mode"
+                i18n.t("nav.dark_mode")
             }
         }
     };
@@ -46,6 +136,7 @@ pub fn Navbar() -> Element {
     rsx! {
         section {
             class: shell_class,
+            lang: locale().key(),
             SidebarLayout {
                 class: "admin-shell",
                 SidebarRail {}
@@ -61,13 +152,13 @@ pub fn Navbar() -> Element {
                     }
                     SidebarContent {
                         SidebarGroup {
-                            SidebarGroupLabel { "Overview" }
+                            SidebarGroupLabel { {i18n.t("nav.overview")} }
                             SidebarGroupContent {
                                 SidebarMenu {
                                     SidebarMenuItem {
                                         SidebarMenuButton {
-                                            label: "Dashboard",
-                                            description: Some("KPIs, monitors, and recent activity".to_string()),
+                                            label: i18n.t("nav.dashboard"),
+                                            description: Some(i18n.t("nav.dashboard_description")),
                                             icon: Some("📊".to_string()),
                                             active: is_dashboard,
                                             href: Some(Route::Home {}.to_string()),
@@ -75,17 +166,17 @@ pub fn Navbar() -> Element {
                                     }
                                     SidebarMenuItem {
                                         SidebarMenuButton {
-                                            label: "Orders",
-                                            description: Some("Manage filters and fulfillment queues".to_string()),
+                                            label: i18n.t("nav.orders"),
+                                            description: Some(i18n.t("nav.orders_description")),
                                             icon: Some("🧾".to_string()),
                                             active: is_orders,
-                                            href: Some(Route::Orders {}.to_string()),
+                                            href: Some(Route::Orders { query: OrderQuery::default() }.to_string()),
                                         }
                                     }
                                     SidebarMenuItem {
                                         SidebarMenuButton {
-                                            label: "Components",
-                                            description: Some("Living style guide of primitives".to_string()),
+                                            label: i18n.t("nav.components"),
+                                            description: Some(i18n.t("nav.components_description")),
                                             icon: Some("🧩".to_string()),
                                             active: is_components,
                                             href: Some(Route::Components {}.to_string()),
@@ -96,22 +187,22 @@ pub fn Navbar() -> Element {
                         }
                         SidebarSeparator {}
                         SidebarGroup {
-                            SidebarGroupLabel { "Shortcuts" }
+                            SidebarGroupLabel { {i18n.t("nav.shortcuts")} }
                             SidebarGroupContent {
                                 SidebarMenu {
                                     SidebarMenuItem {
                                         SidebarMenuButton {
-                                            label: "Team",
-                                            description: Some("Invite and manage collaborators".to_string()),
+                                            label: i18n.t("nav.team"),
+                                            description: Some(i18n.t("nav.team_description")),
                                             icon: Some("👥".to_string()),
-                                            badge: Some("4 pending".to_string()),
+                                            badge: Some(i18n.t_count("nav.team_pending", 4, &[])),
                                             href: Some("#team".to_string()),
                                         }
                                     }
                                     SidebarMenuItem {
                                         SidebarMenuButton {
-                                            label: "Settings",
-                                            description: Some("Branding, auth, billing".to_string()),
+                                            label: i18n.t("nav.settings"),
+                                            description: Some(i18n.t("nav.settings_description")),
                                             icon: Some("⚙️".to_string()),
                                             href: Some("#settings".to_string()),
                                         }
@@ -125,7 +216,7 @@ pub fn Navbar() -> Element {
                             class: Some("mt-2 w-full".to_string()),
                             variant: ButtonVariant::Secondary,
                             r#type: "button".to_string(),
-                            "Switch account"
+                            {i18n.t("nav.switch_account")}
                         }
                     }
                 }
@@ -133,8 +224,46 @@ pub fn Navbar() -> Element {
                     class: "admin-shell-inset",
                     header {
                         class: "admin-shell-topbar",
-                        h1 { class: "admin-shell-title", "Admin Template" }
+                        div { class: "admin-shell-heading",
+                            h1 { class: "admin-shell-title", {i18n.t("nav.app_title")} }
+                            Breadcrumb { items: crumbs }
+                        }
                         div { class: "admin-shell-actions",
+                            div { class: "admin-shell-locale", title: i18n.t("nav.language"),
+                                Select {
+                                    placeholder: i18n.t("nav.language"),
+                                    options: locale_options(),
+                                    selected: Some(locale().key().to_string()),
+                                    on_change: move |key: String| {
+                                        let Some(next) = Locale::from_key(&key) else {
+                                            return;
+                                        };
+                                        locale.set(next);
+                                        spawn(async move {
+                                            // Not worth interrupting anyone for: the language still applies until reload.
+                                            let _ = save_setting(LOCALE_SETTING, &next).await;
+                                        });
+                                    },
+                                }
+                            }
+                            div { class: "admin-shell-zone", title: i18n.t("nav.business_zone"),
+                                Select {
+                                    placeholder: i18n.t("nav.business_zone"),
+                                    options: zone_options(i18n),
+                                    selected: Some(zone().key().to_string()),
+                                    on_change: move |key: String| {
+                                        let Some(next) = Tz::from_key(&key) else {
+                                            return;
+                                        };
+                                        set_business_zone(next);
+                                        zone.set(next);
+                                        spawn(async move {
+                                            // Not worth interrupting anyone for: the zone still applies until reload.
+                                            let _ = save_setting(BUSINESS_ZONE_SETTING, &next).await;
+                                        });
+                                    },
+                                }
+                            }
                             Button {
                                 variant: ButtonVariant::Icon,
                                 size: ButtonSize::Icon,
@@ -0,0 +1,297 @@
+use crate::components::ui::{Button, ButtonSize, ButtonVariant, Dialog, Select, SelectOption};
+use crate::i18n::{use_i18n, I18n};
+use crate::orders::{
+    api::{bulk_update_orders, restore_orders},
+    bulk::{BulkAction, BulkFailure, BulkOutcome, UNDO_WINDOW_MS},
+    transition::TransitionError,
+    Order, AVAILABLE_TAGS,
+};
+use crate::time::business_zone;
+use dioxus::prelude::*;
+
+async fn wait(milliseconds: u32) {
+    let _ = document::eval(&format!(
+        "await new Promise((resolve) => setTimeout(resolve, {milliseconds}));"
+    ))
+    .await;
+}
+
+fn describe_failure(failure: &BulkFailure, i18n: I18n) -> String {
+    match failure {
+        BulkFailure::NotFound => i18n.t("orders.bulk.not_found"),
+        BulkFailure::Transition(TransitionError::NotAllowed { action, from }) => i18n.t_with(
+            "orders.bulk.not_allowed",
+            &[
+                ("status", &from.status.label_in(i18n)),
+                ("payment", &from.payment_status.label_in(i18n)),
+                ("fulfillment", &from.fulfillment_status.label_in(i18n)),
+                ("action", &action.label_in(i18n)),
+            ],
+        ),
+        BulkFailure::ChangedSince => i18n.t("orders.bulk.changed_since"),
+        BulkFailure::Storage(message) => {
+            i18n.t_with("orders.bulk.storage_failed", &[("error", message)])
+        }
+    }
+}
+
+/// The results last shown under the toolbar.
+#[derive(Clone, PartialEq)]
+enum BulkReport {
+    Applied(BulkAction, BulkOutcome),
+    /// An undo that skipped orders changed since the action.
+    Undone(BulkOutcome),
+}
+
+/// Toolbar for the orders selected in the list. Every action is confirmed first; afterwards the per-order
+/// results are listed and the change can be undone for a short while. Keep it mounted while nothing is selected
+/// so the results survive the changed orders leaving the current page.
+#[component]
+pub(super) fn OrderBulkActions(orders: Vec<Order>, on_applied: EventHandler<()>) -> Element {
+    let i18n = use_i18n();
+    let mut tag = use_signal(|| None::<String>);
+    let mut pending = use_signal(|| None::<BulkAction>);
+    let mut confirm_open = use_signal(|| false);
+    let mut running = use_signal(|| false);
+    let mut outcome = use_signal(|| None::<BulkReport>);
+    let mut error = use_signal(|| None::<String>);
+    let mut undo_open = use_signal(|| false);
+    let mut undo_generation = use_signal(|| 0u32);
+
+    let numbers: Vec<String> = orders.iter().map(|order| order.number.clone()).collect();
+    let all_flagged = orders.iter().all(|order| order.flagged);
+    let tag_selected = tag();
+    let tag_options = AVAILABLE_TAGS
+        .iter()
+        .map(|tag| SelectOption::new(*tag, *tag))
+        .collect::<Vec<_>>();
+
+    let mut request = move |action: BulkAction| {
+        pending.set(Some(action));
+        confirm_open.set(true);
+    };
+
+    let confirm_numbers = numbers.clone();
+    let confirm = move |_| {
+        let Some(action) = pending() else {
+            return;
+        };
+        let numbers = confirm_numbers.clone();
+        confirm_open.set(false);
+        pending.set(None);
+        running.set(true);
+        spawn(async move {
+            let result = bulk_update_orders(numbers, action.clone(), business_zone()).await;
+            running.set(false);
+            match result {
+                Ok(result) => {
+                    let can_undo = result.undo_token.is_some();
+                    outcome.set(Some(BulkReport::Applied(action, result)));
+                    error.set(None);
+                    on_applied.call(());
+                    if can_undo {
+                        let generation = undo_generation() + 1;
+                        undo_generation.set(generation);
+                        undo_open.set(true);
+                        wait(UNDO_WINDOW_MS).await;
+                        if undo_generation() == generation {
+                            undo_open.set(false);
+                        }
+                    }
+                }
+                Err(err) => error.set(Some(i18n.t_with("orders.bulk.failed", &[("error", &err)]))),
+            }
+        });
+    };
+
+    let undo = move |_| {
+        let Some(BulkReport::Applied(
+            _,
+            BulkOutcome {
+                undo_token: Some(token),
+                ..
+            },
+        )) = outcome()
+        else {
+            return;
+        };
+        undo_open.set(false);
+        spawn(async move {
+            match restore_orders(token).await {
+                Ok(Some(result)) => {
+                    let skipped = result.failures().count() > 0;
+                    outcome.set(skipped.then_some(BulkReport::Undone(result)));
+                    error.set(None);
+                    on_applied.call(());
+                }
+                Ok(None) => {
+                    outcome.set(None);
+                    error.set(Some(i18n.t("orders.bulk.undo_expired")));
+                }
+                Err(err) => error.set(Some(
+                    i18n.t_with("orders.bulk.undo_failed", &[("error", &err)]),
+                )),
+            }
+        });
+    };
+
+    let pending_action = pending();
+    let outcome_value = outcome();
+
+    rsx! {
+        if !numbers.is_empty() {
+            div { class: "orders-bulk-bar",
+                span { class: "orders-bulk-count", {i18n.t_count("orders.bulk.selected", numbers.len(), &[])} }
+                div { class: "orders-bulk-actions",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Sm,
+                        disabled: running(),
+                        on_click: move |_| request(BulkAction::MarkFulfilled),
+                        {BulkAction::MarkFulfilled.label_in(i18n)}
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Sm,
+                        disabled: running(),
+                        on_click: move |_| request(BulkAction::SetFlagged(!all_flagged)),
+                        {BulkAction::SetFlagged(!all_flagged).label_in(i18n)}
+                    }
+                    div { class: "orders-bulk-tag",
+                        Select {
+                            placeholder: i18n.t("orders.bulk.tag_placeholder"),
+                            options: tag_options,
+                            selected: tag_selected.clone(),
+                            on_change: move |value: String| tag.set(Some(value)),
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        size: ButtonSize::Sm,
+                        disabled: running() || tag_selected.is_none(),
+                        on_click: {
+                            let tag_selected = tag_selected.clone();
+                            move |_| {
+                                if let Some(value) = tag_selected.clone() {
+                                    request(BulkAction::AddTag(value));
+                                }
+                            }
+                        },
+                        {i18n.t("orders.bulk.add_tag")}
+                    }
+                    Button {
+                        variant: ButtonVariant::Outline,
+                        size: ButtonSize::Sm,
+                        disabled: running() || tag_selected.is_none(),
+                        on_click: {
+                            let tag_selected = tag_selected.clone();
+                            move |_| {
+                                if let Some(value) = tag_selected.clone() {
+                                    request(BulkAction::RemoveTag(value));
+                                }
+                            }
+                        },
+                        {i18n.t("orders.bulk.remove_tag")}
+                    }
+                    Button {
+                        variant: ButtonVariant::Destructive,
+                        size: ButtonSize::Sm,
+                        disabled: running(),
+                        on_click: move |_| request(BulkAction::Cancel),
+                        {BulkAction::Cancel.label_in(i18n)}
+                    }
+                }
+            }
+        }
+
+        if let Some(message) = error() {
+            span { class: "ui-field-helper", style: "color: hsl(var(--destructive));", "{message}" }
+        }
+
+        if let Some(report) = outcome_value {
+            {
+                let (summary, result) = match report {
+                    BulkReport::Applied(action, result) => (
+                        i18n.t_with(
+                            "orders.bulk.outcome",
+                            &[
+                                ("action", &action.label_in(i18n)),
+                                ("succeeded", &result.succeeded()),
+                                ("failed", &result.failures().count()),
+                            ],
+                        ),
+                        result,
+                    ),
+                    BulkReport::Undone(result) => (
+                        i18n.t_with(
+                            "orders.bulk.undo_outcome",
+                            &[
+                                ("succeeded", &result.succeeded()),
+                                ("failed", &result.failures().count()),
+                            ],
+                        ),
+                        result,
+                    ),
+                };
+                rsx! {
+                    div { class: "orders-bulk-result",
+                        div { class: "orders-bulk-result-header",
+                            span { "{summary}" }
+                            div { class: "orders-bulk-actions",
+                                if undo_open() {
+                                    Button {
+                                        variant: ButtonVariant::Outline,
+                                        size: ButtonSize::Sm,
+                                        on_click: undo,
+                                        {i18n.t("orders.bulk.undo")}
+                                    }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Ghost,
+                                    size: ButtonSize::Sm,
+                                    on_click: move |_| {
+                                        undo_open.set(false);
+                                        outcome.set(None);
+                                    },
+                                    {i18n.t("dialog.close")}
+                                }
+                            }
+                        }
+                        if result.failures().count() > 0 {
+                            ul { class: "orders-bulk-failures",
+                                for (number, failure) in result.failures() {
+                                    li { {i18n.t_with("orders.bulk.failure", &[("number", &number), ("reason", &describe_failure(failure, i18n))])} }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(action) = pending_action {
+            Dialog {
+                open: confirm_open,
+                title: i18n.t_with("orders.confirm_title", &[("action", &action.label_in(i18n))]),
+                description: i18n.t_count("orders.bulk.confirm_description", numbers.len(), &[]),
+                on_close: move |_| pending.set(None),
+                p { class: "ui-field-helper", {i18n.list(&numbers)} }
+                if !action.is_undoable() {
+                    p { class: "ui-field-helper", {i18n.t("orders.bulk.no_undo")} }
+                }
+                div { class: "orders-bulk-actions",
+                    Button {
+                        variant: if action.is_destructive() {
+                            ButtonVariant::Destructive
+                        } else {
+                            ButtonVariant::Default
+                        },
+                        size: ButtonSize::Sm,
+                        on_click: confirm,
+                        {action.label_in(i18n)}
+                    }
+                }
+            }
+        }
+    }
+}
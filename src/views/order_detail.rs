@@ -0,0 +1,460 @@
+use super::orders::{initials, payment_badge, status_badge};
+use crate::components::ui::{
+    Avatar, Badge, BadgeVariant, Button, ButtonSize, ButtonVariant, Card, CardContent,
+    CardDescription, CardFooter, CardHeader, CardTitle, CheckboxChipGroup, CheckboxChipOption,
+    Dialog, StepItem, Steps, Table, TableBody, TableCell, TableFooter, TableHead, TableHeader,
+    TableRow, Textarea,
+};
+use crate::i18n::{use_i18n, I18n};
+use crate::orders::{
+    api::{add_order_note, apply_order_action, get_order, set_order_tags},
+    query::OrderQuery,
+    transition::OrderAction,
+    FulfillmentStatus, Order, OrderStatus, PaymentStatus, StatusChange, AVAILABLE_TAGS,
+};
+use crate::time::business_zone;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// The fulfillment statuses an order normally walks through, used to preview the steps still ahead.
+const FULFILLMENT_PATH: &[FulfillmentStatus] = &[
+    FulfillmentStatus::Unfulfilled,
+    FulfillmentStatus::Picking,
+    FulfillmentStatus::Shipped,
+    FulfillmentStatus::Delivered,
+];
+
+/// Turns a status history into steps: recorded statuses carry their date, the latest one is active and
+/// `upcoming` statuses follow without a date.
+fn timeline_steps<S: Copy>(
+    history: &[StatusChange<S>],
+    current: S,
+    upcoming: &[S],
+    label: impl Fn(&S) -> String,
+    i18n: I18n,
+) -> (Vec<StepItem>, usize) {
+    let mut steps: Vec<StepItem> = history
+        .iter()
+        .map(|change| {
+            StepItem::new(
+                label(&change.status),
+                Some(change.on.format("%Y-%m-%d").to_string()),
+            )
+        })
+        .collect();
+    if steps.is_empty() {
+        steps.push(StepItem::new(
+            label(&current),
+            Some(i18n.t("orders.detail.no_time")),
+        ));
+    }
+    let reached = steps.len();
+    steps.extend(
+        upcoming
+            .iter()
+            .map(|status| StepItem::new(label(status), None::<String>)),
+    );
+    (steps, reached)
+}
+
+fn payment_timeline(order: &Order, i18n: I18n) -> (Vec<StepItem>, usize) {
+    let upcoming: &[PaymentStatus] = match (order.status, order.payment_status) {
+        (OrderStatus::Cancelled, _) => &[],
+        (_, PaymentStatus::Pending | PaymentStatus::Overdue) => &[PaymentStatus::Paid],
+        (_, PaymentStatus::Paid | PaymentStatus::Refunded) => &[],
+    };
+    timeline_steps(
+        &order.payment_history,
+        order.payment_status,
+        upcoming,
+        |status: &PaymentStatus| status.label_in(i18n),
+        i18n,
+    )
+}
+
+fn fulfillment_timeline(order: &Order, i18n: I18n) -> (Vec<StepItem>, usize) {
+    let upcoming = FULFILLMENT_PATH
+        .iter()
+        .position(|status| *status == order.fulfillment_status)
+        .filter(|_| order.status != OrderStatus::Cancelled)
+        .map(|index| &FULFILLMENT_PATH[index + 1..])
+        .unwrap_or(&[]);
+    timeline_steps(
+        &order.fulfillment_history,
+        order.fulfillment_status,
+        upcoming,
+        |status: &FulfillmentStatus| status.label_in(i18n),
+        i18n,
+    )
+}
+
+#[component]
+pub fn OrderDetail(number: String) -> Element {
+    let i18n = use_i18n();
+    let navigator = use_navigator();
+    let mut order = use_resource(use_reactive((&number,), |(number,)| async move {
+        get_order(number).await
+    }));
+
+    let body = match &*order.read() {
+        None => rsx! {
+            div { class: "orders-empty", {i18n.t("orders.list.loading")} }
+        },
+        Some(Err(err)) => rsx! {
+            div { class: "orders-empty", {i18n.t_with("orders.detail.load_failed", &[("error", err)])} }
+        },
+        Some(Ok(None)) => rsx! {
+            div { class: "orders-empty", {i18n.t_with("orders.detail.not_found", &[("number", &number)])} }
+        },
+        Some(Ok(Some(loaded))) => rsx! {
+            OrderDetailContent {
+                key: "{loaded.number}",
+                order: loaded.clone(),
+                on_updated: move |_| order.restart(),
+            }
+        },
+    };
+
+    rsx! {
+        div {
+            class: "ui-stack",
+            style: "gap: 1.5rem;",
+            div {
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    size: ButtonSize::Sm,
+                    // Back to the list as it was left, filters in the URL included; opened from a link, to the
+                    // unfiltered list.
+                    on_click: move |_| {
+                        if navigator.can_go_back() {
+                            navigator.go_back();
+                        } else {
+                            navigator.push(Route::Orders { query: OrderQuery::default() });
+                        }
+                    },
+                    {i18n.t("orders.detail.back")}
+                }
+            }
+            {body}
+        }
+    }
+}
+
+#[component]
+fn OrderDetailContent(order: Order, on_updated: EventHandler<()>) -> Element {
+    let i18n = use_i18n();
+    let tags = use_signal(|| order.tags.clone());
+    let mut tags_error = use_signal(|| None::<String>);
+    let mut note_draft = use_signal(String::new);
+    let mut note_error = use_signal(|| None::<String>);
+    let mut saving_note = use_signal(|| false);
+    let mut action_error = use_signal(|| None::<String>);
+    let mut applying = use_signal(|| false);
+    let mut confirming = use_signal(|| None::<OrderAction>);
+    let mut confirm_open = use_signal(|| false);
+
+    let action_number = order.number.clone();
+    let run_action = use_callback(move |action: OrderAction| {
+        let number = action_number.clone();
+        applying.set(true);
+        spawn(async move {
+            match apply_order_action(number, action, business_zone()).await {
+                Ok(_) => {
+                    action_error.set(None);
+                    on_updated.call(());
+                }
+                Err(err) => action_error.set(Some(i18n.t_with(
+                    "orders.detail.action_failed",
+                    &[("action", &action.label_in(i18n)), ("error", &err)],
+                ))),
+            }
+            applying.set(false);
+        });
+    });
+    let available_actions = order.available_actions();
+
+    let (payment_steps, payment_current) = payment_timeline(&order, i18n);
+    let (fulfillment_steps, fulfillment_current) = fulfillment_timeline(&order, i18n);
+    let items_total: f32 = order.line_items.iter().map(|item| item.subtotal()).sum();
+    let placed_on = order.placed_on.format("%Y-%m-%d").to_string();
+
+    // Tags outside the predefined set stay selectable so they can be removed.
+    let mut tag_chip_options: Vec<CheckboxChipOption> = AVAILABLE_TAGS
+        .iter()
+        .map(|tag| CheckboxChipOption::new(*tag, *tag))
+        .collect();
+    for tag in &order.tags {
+        if !AVAILABLE_TAGS.contains(&tag.as_str()) {
+            tag_chip_options.push(CheckboxChipOption::new(tag.clone(), tag.clone()));
+        }
+    }
+
+    let tags_number = order.number.clone();
+    let notes_number = order.number.clone();
+    let note_value = note_draft();
+    let note_blank = note_value.trim().is_empty();
+
+    rsx! {
+        Card {
+            CardHeader {
+                CardTitle { {i18n.t_with("orders.detail.title", &[("number", &order.number)])} }
+                CardDescription {
+                    {i18n.t_with(
+                        "orders.detail.summary",
+                        &[
+                            ("date", &placed_on),
+                            ("channel", &order.channel.label_in(i18n)),
+                            ("method", &order.payment_method.label_in(i18n)),
+                        ],
+                    )}
+                }
+            }
+            CardContent {
+                div { class: "orders-detail-badges",
+                    Badge { variant: status_badge(order.status), {order.status.label_in(i18n)} }
+                    Badge { variant: payment_badge(order.payment_status), {order.payment_status.label_in(i18n)} }
+                    Badge { variant: BadgeVariant::Outline, {order.fulfillment_status.label_in(i18n)} }
+                    if order.flagged {
+                        Badge { variant: BadgeVariant::Destructive, {i18n.t("orders.detail.flagged")} }
+                    }
+                    span { class: "orders-detail-total", {format!("¥{:.2}", order.total)} }
+                }
+            }
+        }
+
+        div { class: "orders-detail-grid",
+            div { class: "ui-stack", style: "gap: 1.5rem;",
+                Card {
+                    CardHeader {
+                        CardTitle { {i18n.t("orders.detail.items")} }
+                        CardDescription {
+                            {i18n.t_count("orders.detail.item_count", order.line_items.iter().map(|item| item.quantity as usize).sum(), &[])}
+                        }
+                    }
+                    CardContent {
+                        if order.line_items.is_empty() {
+                            div { class: "orders-empty", {i18n.t("orders.detail.no_items")} }
+                        } else {
+                            Table {
+                                TableHeader {
+                                    TableRow {
+                                        TableHead { {i18n.t("orders.detail.item")} }
+                                        TableHead { {i18n.t("orders.detail.quantity")} }
+                                        TableHead { {i18n.t("orders.detail.unit_price")} }
+                                        TableHead { {i18n.t("orders.detail.subtotal")} }
+                                    }
+                                }
+                                TableBody {
+                                    for item in order.line_items.iter().cloned() {
+                                        TableRow {
+                                            TableCell {
+                                                div { style: "display: flex; flex-direction: column; gap: 0.25rem;",
+                                                    span { style: "font-weight: 600;", "{item.name}" }
+                                                    span { class: "ui-field-helper", "{item.sku}" }
+                                                }
+                                            }
+                                            TableCell { "{item.quantity}" }
+                                            TableCell { {format!("¥{:.2}", item.unit_price)} }
+                                            TableCell { {format!("¥{:.2}", item.subtotal())} }
+                                        }
+                                    }
+                                }
+                                TableFooter {
+                                    TableRow {
+                                        TableCell { {i18n.t("orders.detail.items_sum")} }
+                                        TableCell { "" }
+                                        TableCell { "" }
+                                        TableCell { {format!("¥{:.2}", items_total)} }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                Card {
+                    CardHeader {
+                        CardTitle { {i18n.t("orders.detail.timeline")} }
+                        CardDescription { {i18n.t("orders.detail.timeline_description")} }
+                    }
+                    CardContent {
+                        div { class: "ui-stack", style: "gap: 1.25rem;",
+                            div { class: "ui-stack", style: "gap: 0.5rem;",
+                                span { class: "orders-detail-section-label", {i18n.t("orders.column.payment")} }
+                                Steps { steps: payment_steps, current: payment_current }
+                            }
+                            div { class: "ui-stack", style: "gap: 0.5rem;",
+                                span { class: "orders-detail-section-label", {i18n.t("orders.column.fulfillment")} }
+                                Steps { steps: fulfillment_steps, current: fulfillment_current }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "ui-stack", style: "gap: 1.5rem;",
+                Card {
+                    CardHeader {
+                        CardTitle { {i18n.t("orders.detail.actions")} }
+                        CardDescription { {i18n.t("orders.detail.actions_description")} }
+                    }
+                    CardContent {
+                        if available_actions.is_empty() {
+                            span { class: "ui-field-helper", {i18n.t("orders.detail.no_actions")} }
+                        } else {
+                            div { class: "orders-detail-actions",
+                                for action in available_actions {
+                                    Button {
+                                        size: ButtonSize::Sm,
+                                        variant: if action.is_destructive() {
+                                            ButtonVariant::Destructive
+                                        } else {
+                                            ButtonVariant::Secondary
+                                        },
+                                        disabled: applying(),
+                                        on_click: move |_| {
+                                            if action.is_destructive() {
+                                                confirming.set(Some(action));
+                                                confirm_open.set(true);
+                                            } else {
+                                                run_action.call(action);
+                                            }
+                                        },
+                                        {action.label_in(i18n)}
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(message) = action_error() {
+                            span { class: "ui-field-helper", style: "color: hsl(var(--destructive));", "{message}" }
+                        }
+                    }
+                }
+
+                Card {
+                    CardHeader {
+                        CardTitle { {i18n.t("orders.column.customer")} }
+                    }
+                    CardContent {
+                        div { style: "display: flex; align-items: center; gap: 0.75rem;",
+                            Avatar {
+                                fallback: Some(initials(&order.customer_name)),
+                                alt: Some(order.customer_name.clone()),
+                            }
+                            div { style: "display: flex; flex-direction: column; gap: 0.25rem;",
+                                span { style: "font-weight: 600;", "{order.customer_name}" }
+                                span { class: "ui-field-helper", "{order.customer_email}" }
+                            }
+                        }
+                    }
+                }
+
+                Card {
+                    CardHeader {
+                        CardTitle { {i18n.t("orders.column.tags")} }
+                        CardDescription { {i18n.t("orders.detail.tags_description")} }
+                    }
+                    CardContent {
+                        CheckboxChipGroup {
+                            values: tags,
+                            options: tag_chip_options,
+                            on_values_change: move |values: Vec<String>| {
+                                let number = tags_number.clone();
+                                spawn(async move {
+                                    match set_order_tags(number, values).await {
+                                        Ok(_) => {
+                                            tags_error.set(None);
+                                            on_updated.call(());
+                                        }
+                                        Err(err) => tags_error.set(Some(i18n.t_with("orders.detail.tags_failed", &[("error", &err)]))),
+                                    }
+                                });
+                            },
+                        }
+                        if let Some(message) = tags_error() {
+                            span { class: "ui-field-helper", style: "color: hsl(var(--destructive));", "{message}" }
+                        }
+                    }
+                }
+
+                Card {
+                    CardHeader {
+                        CardTitle { {i18n.t("orders.detail.notes")} }
+                        CardDescription { {i18n.t("orders.detail.notes_description")} }
+                    }
+                    CardContent {
+                        div { class: "ui-stack", style: "gap: 0.75rem;",
+                            if order.notes.is_empty() {
+                                span { class: "ui-field-helper", {i18n.t("orders.detail.no_notes")} }
+                            }
+                            for note in order.notes.iter().rev().cloned() {
+                                div { class: "orders-detail-note",
+                                    span { class: "orders-detail-note-meta",
+                                        {format!("{} · {}", note.author, note.created_on.format("%Y-%m-%d"))}
+                                    }
+                                    p { "{note.body}" }
+                                }
+                            }
+                            Textarea {
+                                value: Some(note_value.clone()),
+                                placeholder: Some(i18n.t("orders.detail.note_placeholder")),
+                                rows: Some(3),
+                                on_input: move |event: FormEvent| note_draft.set(event.value()),
+                            }
+                            if let Some(message) = note_error() {
+                                span { class: "ui-field-helper", style: "color: hsl(var(--destructive));", "{message}" }
+                            }
+                        }
+                    }
+                    CardFooter {
+                        Button {
+                            size: ButtonSize::Sm,
+                            disabled: note_blank || saving_note(),
+                            on_click: move |_| {
+                                let number = notes_number.clone();
+                                let body = note_draft();
+                                saving_note.set(true);
+                                spawn(async move {
+                                    match add_order_note(number, body, business_zone(), i18n.locale()).await {
+                                        Ok(_) => {
+                                            note_draft.set(String::new());
+                                            note_error.set(None);
+                                            on_updated.call(());
+                                        }
+                                        Err(err) => note_error.set(Some(i18n.t_with("orders.detail.note_failed", &[("error", &err)]))),
+                                    }
+                                    saving_note.set(false);
+                                });
+                            },
+                            {i18n.t("orders.detail.add_note")}
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(action) = confirming() {
+            Dialog {
+                open: confirm_open,
+                title: i18n.t_with("orders.confirm_title", &[("action", &action.label_in(i18n))]),
+                description: match action {
+                    OrderAction::Return => i18n.t("orders.detail.confirm_return"),
+                    _ => i18n.t("orders.detail.confirm_cancel"),
+                },
+                on_close: move |_| confirming.set(None),
+                div { class: "orders-detail-actions",
+                    Button {
+                        variant: ButtonVariant::Destructive,
+                        size: ButtonSize::Sm,
+                        on_click: move |_| {
+                            confirm_open.set(false);
+                            confirming.set(None);
+                            run_action.call(action);
+                        },
+                        {action.label_in(i18n)}
+                    }
+                }
+            }
+        }
+    }
+}
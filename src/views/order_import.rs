@@ -0,0 +1,317 @@
+use crate::components::ui::{
+    Badge, BadgeVariant, Button, ButtonSize, ButtonVariant, Card, CardContent, CardDescription,
+    CardHeader, CardTitle, FileContents, FileDropZone, Select, SelectOption, StepItem, Steps,
+    Table, TableBody, TableCaption, TableCell, TableHead, TableHeader, TableRow,
+};
+use crate::i18n::{use_i18n, I18n};
+use crate::orders::{
+    api::import_orders,
+    import::{
+        guess_mapping, missing_fields, parse_csv, validate, ColumnMapping, CsvError, CsvTable,
+        ImportField, RowError,
+    },
+    Order,
+};
+use dioxus::prelude::*;
+
+/// Rows shown in the preview table. Every row is still validated and counted.
+const PREVIEW_LIMIT: usize = 50;
+
+/// Select value for columns that are not imported.
+const IGNORE: &str = "ignore";
+
+fn describe_csv_error(error: CsvError, i18n: I18n) -> String {
+    match error {
+        CsvError::UnterminatedQuote { line } => {
+            i18n.t_with("orders.import.unterminated_quote", &[("line", &line)])
+        }
+        CsvError::Empty => i18n.t("orders.import.no_header"),
+    }
+}
+
+fn describe_row_error(error: &RowError, i18n: I18n) -> String {
+    match error {
+        RowError::Missing(field) => {
+            i18n.t_with("orders.import.missing", &[("field", &field.label_in(i18n))])
+        }
+        RowError::Invalid { field, value } => i18n.t_with(
+            "orders.import.invalid",
+            &[("field", &field.label_in(i18n)), ("value", value)],
+        ),
+        RowError::DuplicateNumber(number) => {
+            i18n.t_with("orders.import.duplicate_number", &[("number", number)])
+        }
+        RowError::Inconsistent(statuses) => i18n.t_with(
+            "orders.import.inconsistent",
+            &[
+                ("status", &statuses.status.label_in(i18n)),
+                ("payment", &statuses.payment_status.label_in(i18n)),
+                ("fulfillment", &statuses.fulfillment_status.label_in(i18n)),
+            ],
+        ),
+    }
+}
+
+/// CSV import on the orders page: upload a file, map its columns onto order fields, preview the validated rows
+/// and import the valid ones. Invalid rows stay in the preview with their errors and are never sent.
+#[component]
+pub(super) fn OrderImport(on_imported: EventHandler<()>) -> Element {
+    let i18n = use_i18n();
+    let mut file_name = use_signal(|| None::<String>);
+    let mut table = use_signal(|| None::<CsvTable>);
+    let mut mapping = use_signal(ColumnMapping::new);
+    let mut generation = use_signal(|| 0u32);
+    let mut error = use_signal(|| None::<String>);
+    let mut importing = use_signal(|| false);
+    let mut message = use_signal(|| None::<String>);
+
+    let load = move |files: Vec<FileContents>| {
+        let Some(file) = files.into_iter().next() else {
+            return;
+        };
+        message.set(None);
+        match file
+            .text(i18n)
+            .and_then(|text| parse_csv(&text).map_err(|err| describe_csv_error(err, i18n)))
+        {
+            Ok(parsed) => {
+                mapping.set(guess_mapping(&parsed.headers));
+                table.set(Some(parsed));
+                error.set(None);
+            }
+            Err(err) => {
+                table.set(None);
+                error.set(Some(i18n.t_with(
+                    "orders.import.file_error",
+                    &[("file", &file.metadata.name), ("error", &err)],
+                )));
+            }
+        }
+        file_name.set(Some(file.metadata.name));
+        generation += 1;
+    };
+
+    let mut reset = move || {
+        table.set(None);
+        file_name.set(None);
+        error.set(None);
+        generation += 1;
+    };
+
+    let current_mapping = mapping();
+    let current_table = table();
+    let missing = missing_fields(&current_mapping);
+    let rows = current_table
+        .as_ref()
+        .map(|table| validate(table, &current_mapping))
+        .unwrap_or_default();
+    let valid: Vec<Order> = rows
+        .iter()
+        .filter_map(|row| row.result.clone().ok())
+        .collect();
+    let invalid_count = rows.len() - valid.len();
+    let ready = current_table.is_some() && missing.is_empty();
+    let step = if current_table.is_none() {
+        1
+    } else if !missing.is_empty() {
+        2
+    } else {
+        3
+    };
+
+    let field_options = std::iter::once(SelectOption::new(i18n.t("orders.import.ignore"), IGNORE))
+        .chain(ImportField::all().iter().map(|field| {
+            let label = if field.is_required() {
+                format!("{} *", field.label_in(i18n))
+            } else {
+                field.label_in(i18n)
+            };
+            SelectOption::new(label, field.key())
+        }))
+        .collect::<Vec<_>>();
+
+    let commit = move |_| {
+        let Some(file) = table() else {
+            return;
+        };
+        if valid.is_empty() {
+            return;
+        }
+        let columns = mapping();
+        importing.set(true);
+        spawn(async move {
+            // The server validates the rows again and imports the ones that pass, the same ones as `valid`.
+            let result = import_orders(file, columns, i18n.locale()).await;
+            importing.set(false);
+            match result {
+                Ok(outcome) => {
+                    let mut summary =
+                        i18n.t_count("orders.import.done", outcome.imported.len(), &[]);
+                    if !outcome.skipped.is_empty() {
+                        summary.push_str(&i18n.t_with(
+                            "orders.import.skipped",
+                            &[("numbers", &i18n.list(&outcome.skipped))],
+                        ));
+                    }
+                    message.set(Some(summary));
+                    reset();
+                    on_imported.call(());
+                }
+                Err(err) => error.set(Some(
+                    i18n.t_with("orders.import.failed", &[("error", &err)]),
+                )),
+            }
+        });
+    };
+
+    rsx! {
+        Card {
+            CardHeader {
+                CardTitle { {i18n.t("orders.import.title")} }
+                CardDescription { {i18n.t("orders.import.description")} }
+            }
+            CardContent {
+                div { class: "ui-stack", style: "gap: 1rem;",
+                    Steps {
+                        current: step,
+                        steps: vec![
+                            StepItem::new(i18n.t("orders.import.step_upload"), Some(i18n.t("orders.import.step_upload_hint"))),
+                            StepItem::new(i18n.t("orders.import.step_map"), Some(i18n.t("orders.import.step_map_hint"))),
+                            StepItem::new(i18n.t("orders.import.step_preview"), Some(i18n.t("orders.import.step_preview_hint"))),
+                        ],
+                    }
+                    FileDropZone {
+                        key: "{generation}",
+                        accept: Some(".csv,text/csv".into()),
+                        on_file_contents: load,
+                    }
+                    if let Some(message) = error() {
+                        span { class: "ui-field-helper", style: "color: hsl(var(--destructive));", "{message}" }
+                    }
+                    if let Some(message) = message() {
+                        span { class: "ui-field-helper", "{message}" }
+                    }
+
+                    if let Some(csv) = current_table.clone() {
+                        div { class: "orders-import-header",
+                            span { class: "orders-bulk-count",
+                                {i18n.t_count("orders.import.file_rows", csv.rows.len(), &[("file", &file_name().unwrap_or_default())])}
+                            }
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                size: ButtonSize::Sm,
+                                on_click: move |_| reset(),
+                                {i18n.t("orders.import.choose_again")}
+                            }
+                        }
+                        div { class: "orders-import-mapping",
+                            for (index, header) in csv.headers.iter().enumerate() {
+                                div { key: "{generation}-{index}", class: "orders-import-column",
+                                    span { class: "orders-import-column-name", "{header}" }
+                                    span { class: "orders-metric-sub",
+                                        {csv.rows.first().and_then(|row| row.cells.get(index)).cloned().unwrap_or_default()}
+                                    }
+                                    Select {
+                                        placeholder: i18n.t("orders.import.ignore"),
+                                        options: field_options.clone(),
+                                        selected: Some(
+                                            current_mapping
+                                                .get(index)
+                                                .copied()
+                                                .flatten()
+                                                .map(|field| field.key())
+                                                .unwrap_or(IGNORE)
+                                                .to_string(),
+                                        ),
+                                        on_change: move |value: String| {
+                                            let field = ImportField::from_key(&value);
+                                            let mut columns = mapping.write();
+                                            // A field feeds from one column only; clear it elsewhere and redraw the selects.
+                                            if field.is_some() && columns.contains(&field) {
+                                                for column in columns.iter_mut() {
+                                                    if *column == field {
+                                                        *column = None;
+                                                    }
+                                                }
+                                                generation += 1;
+                                            }
+                                            if let Some(column) = columns.get_mut(index) {
+                                                *column = field;
+                                            }
+                                        },
+                                    }
+                                }
+                            }
+                        }
+
+                        if !missing.is_empty() {
+                            span { class: "ui-field-helper", style: "color: hsl(var(--destructive));",
+                                {i18n.t_with(
+                                    "orders.import.unmapped",
+                                    &[("fields", &i18n.list(&missing.iter().map(|field| field.label_in(i18n)).collect::<Vec<_>>()))],
+                                )}
+                            }
+                        } else {
+                            Table {
+                                if rows.len() > PREVIEW_LIMIT {
+                                    TableCaption { {i18n.t_with("orders.import.preview_limit", &[("limit", &PREVIEW_LIMIT), ("total", &rows.len())])} }
+                                }
+                                TableHeader {
+                                    TableRow {
+                                        TableHead { {i18n.t("orders.import.line")} }
+                                        TableHead { {ImportField::Number.label_in(i18n)} }
+                                        TableHead { {ImportField::CustomerName.label_in(i18n)} }
+                                        TableHead { {ImportField::PlacedOn.label_in(i18n)} }
+                                        TableHead { {ImportField::Total.label_in(i18n)} }
+                                        TableHead { {i18n.t("orders.import.result")} }
+                                    }
+                                }
+                                TableBody {
+                                    for row in rows.iter().take(PREVIEW_LIMIT) {
+                                        TableRow { key: "{row.line}",
+                                            TableCell { "{row.line}" }
+                                            TableCell { {row.value(&current_mapping, ImportField::Number).to_string()} }
+                                            TableCell { {row.value(&current_mapping, ImportField::CustomerName).to_string()} }
+                                            TableCell { {row.value(&current_mapping, ImportField::PlacedOn).to_string()} }
+                                            TableCell { {row.value(&current_mapping, ImportField::Total).to_string()} }
+                                            TableCell {
+                                                match &row.result {
+                                                    Ok(_) => rsx! {
+                                                        Badge { variant: BadgeVariant::Secondary, {i18n.t("orders.import.valid")} }
+                                                    },
+                                                    Err(errors) => rsx! {
+                                                        ul { class: "orders-import-errors",
+                                                            for error in errors.iter() {
+                                                                li { {describe_row_error(error, i18n)} }
+                                                            }
+                                                        }
+                                                    },
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "orders-import-header",
+                            span { class: "orders-metric-sub",
+                                {i18n.t_with("orders.import.counts", &[("valid", &(rows.len() - invalid_count)), ("invalid", &invalid_count)])}
+                            }
+                            Button {
+                                size: ButtonSize::Sm,
+                                disabled: !ready || rows.len() == invalid_count || importing(),
+                                on_click: commit,
+                                if importing() {
+                                    {i18n.t("orders.import.running")}
+                                } else {
+                                    {i18n.t_count("orders.import.submit", rows.len() - invalid_count, &[])}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::components::ui::{TableLayout, TableView, TableViewSwitcher, TableViews};
+use crate::i18n::use_i18n;
+use crate::orders::query::OrderQuery;
+use crate::platform::{load_setting, save_setting};
+use dioxus::prelude::*;
+
+/// Setting the saved views of the orders table are stored under.
+const STORAGE_KEY: &str = "orders.views";
+
+/// Saved views of the orders table. The stored views are loaded once when the page opens and the default view,
+/// if there is one and `apply_default` is set, is applied; every change to the views is stored right away.
+#[component]
+pub(super) fn OrderViews(
+    current_layout: TableLayout,
+    /// The filters and sort on screen. The page number is not part of a view.
+    current_query: OrderQuery,
+    /// Off when the page was opened from a link that already carries filters.
+    apply_default: bool,
+    on_apply: EventHandler<TableView<OrderQuery>>,
+) -> Element {
+    let i18n = use_i18n();
+    let mut views = use_signal(TableViews::<OrderQuery>::default);
+    let mut active = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    use_future(move || async move {
+        let Some(stored) = load_setting::<TableViews<OrderQuery>>(STORAGE_KEY).await else {
+            return;
+        };
+        if let Some(view) = stored.default_view().filter(|_| apply_default).cloned() {
+            active.set(Some(view.id.clone()));
+            on_apply.call(view);
+        }
+        views.set(stored);
+    });
+
+    rsx! {
+        div { class: "orders-views",
+            TableViewSwitcher {
+                views: views(),
+                active: active(),
+                current_layout,
+                current_state: current_query,
+                on_views_change: move |next: TableViews<OrderQuery>| {
+                    views.set(next.clone());
+                    spawn(async move {
+                        let result = save_setting(STORAGE_KEY, &next).await;
+                        error.set(result
+                            .err()
+                            .map(|err| i18n.t_with("orders.views.save_failed", &[("error", &err)])));
+                    });
+                },
+                on_apply: move |view: TableView<OrderQuery>| {
+                    active.set(Some(view.id.clone()));
+                    on_apply.call(view);
+                },
+            }
+            if let Some(message) = error() {
+                span { class: "ui-field-helper", style: "color: hsl(var(--destructive));", "{message}" }
+            }
+        }
+    }
+}
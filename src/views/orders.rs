@@ -1,555 +1,632 @@
 use crate::components::ui::{
-    Avatar, Badge, BadgeVariant, Button, ButtonSize, ButtonVariant, Card, CardContent,
-    CardDescription, CardFooter, CardHeader, CardTitle, CheckboxChipGroup, CheckboxChipOption,
-    DateRange, DateRangePicker, Input, InteractiveTable, Label, Pagination, Popover, Select,
+    Aggregate, Avatar, Badge, BadgeVariant, Button, ButtonSize, ButtonVariant, Card, CardContent,
+    CardDescription, CardFooter, CardHeader, CardTitle, CellEdit, CellEditor, CheckboxChipGroup,
+    CheckboxChipOption, ColumnPin, ColumnSortKind, DateRange, DateRangePicker, DateRangePreset,
+    DropdownMenu, DropdownMenuItem, Input, InteractiveTable, Label, Pagination, Popover, Select,
     SelectOption, Slider, Table, TableBody, TableCaption, TableCell, TableColumnConfig,
-    TableFooter, TableHead, TableHeader, TableRow, TableRowData,
+    TableFooter, TableHead, TableHeader, TableLayout, TableRecord, TableRow, TableSort,
+    TableSortDirection, TableSummary, TableView,
 };
-use crate::time::NaiveDate;
+use crate::i18n::{use_i18n, I18n};
+use crate::orders::{
+    api::{edit_order, export_orders, query_orders},
+    edit::{EditError, OrderEdit},
+    export::{export, ExportColumn, ExportFormat},
+    import::parse_date,
+    query::{
+        OrderGroupField, OrderMetrics, OrderPage, OrderPipeline, OrderQuery, OrderSort,
+        OrderSortField, SortDirection,
+    },
+    FulfillmentStatus, Order, OrderStatus, PaymentMethod, PaymentStatus, SalesChannel,
+    AVAILABLE_TAGS,
+};
+use crate::platform::save_download;
+use crate::time::{business_zone, NaiveDate};
+use crate::Route;
 use dioxus::prelude::*;
+use std::collections::HashMap;
+
+use super::order_bulk::OrderBulkActions;
+use super::order_import::OrderImport;
+use super::order_views::OrderViews;
 
 const PAGE_SIZE: usize = 8;
-const AVAILABLE_TAGS: &[&str] = &["加急", "赠品", "VIP", "缺货", "重复下单", "需回访"];
 
-#[derive(Clone)]
-struct Order {
-    number: String,
-    placed_on: NaiveDate,
-    customer_name: String,
-    customer_email: String,
-    status: OrderStatus,
-    payment_status: PaymentStatus,
-    fulfillment_status: FulfillmentStatus,
-    payment_method: PaymentMethod,
-    channel: SalesChannel,
-    total: f32,
-    tags: Vec<String>,
-    flagged: bool,
+pub(super) fn status_badge(status: OrderStatus) -> BadgeVariant {
+    match status {
+        OrderStatus::Draft => BadgeVariant::Secondary,
+        OrderStatus::PendingPayment => BadgeVariant::Outline,
+        OrderStatus::Processing => BadgeVariant::Default,
+        OrderStatus::Fulfilled => BadgeVariant::Default,
+        OrderStatus::Cancelled => BadgeVariant::Destructive,
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum OrderStatus {
-    Draft,
-    PendingPayment,
-    Processing,
-    Fulfilled,
-    Cancelled,
+pub(super) fn payment_badge(status: PaymentStatus) -> BadgeVariant {
+    match status {
+        PaymentStatus::Paid => BadgeVariant::Secondary,
+        PaymentStatus::Refunded => BadgeVariant::Outline,
+        PaymentStatus::Overdue => BadgeVariant::Destructive,
+        PaymentStatus::Pending => BadgeVariant::Default,
+    }
 }
 
-impl OrderStatus {
-    fn label(&self) -> &'static str {
-        match self {
-            OrderStatus::Draft => "草稿",
-            OrderStatus::PendingPayment => "待支付",
-            OrderStatus::Processing => "处理中",
-            OrderStatus::Fulfilled => "已完成",
-            OrderStatus::Cancelled => "已取消",
-        }
+pub(super) fn initials(input: &str) -> String {
+    let initials: String = input
+        .split_whitespace()
+        .filter_map(|part| part.chars().next())
+        .take(2)
+        .collect();
+    if initials.is_empty() {
+        input.chars().take(2).collect()
+    } else {
+        initials.to_uppercase()
     }
+}
 
-    fn key(&self) -> &'static str {
-        match self {
-            OrderStatus::Draft => "draft",
-            OrderStatus::PendingPayment => "pending",
-            OrderStatus::Processing => "processing",
-            OrderStatus::Fulfilled => "fulfilled",
-            OrderStatus::Cancelled => "cancelled",
-        }
+/// Id of the column holding the row buttons. It has no data, so exports leave it out.
+const ACTIONS_COLUMN: &str = "actions";
+
+impl TableRecord for Order {
+    fn row_id(&self) -> String {
+        self.number.clone()
     }
 
-    fn badge(&self) -> BadgeVariant {
-        match self {
-            OrderStatus::Draft => BadgeVariant::Secondary,
-            OrderStatus::PendingPayment => BadgeVariant::Outline,
-            OrderStatus::Processing => BadgeVariant::Default,
-            OrderStatus::Fulfilled => BadgeVariant::Default,
-            OrderStatus::Cancelled => BadgeVariant::Destructive,
+    fn cell_text(&self, column_id: &str) -> String {
+        match column_id {
+            "number" => self.number.clone(),
+            "customer" => self.customer_name.clone(),
+            "date" => self.placed_on.format("%Y-%m-%d").to_string(),
+            "status" => self.status.label().to_string(),
+            "payment" => self.payment_status.label().to_string(),
+            "fulfillment" => self.fulfillment_status.label().to_string(),
+            "channel" => self.channel.label().to_string(),
+            "method" => self.payment_method.label().to_string(),
+            "total" => format!("¥{:.2}", self.total),
+            "tags" => self.tags.join("、"),
+            "flagged" => self.flagged.to_string(),
+            _ => String::new(),
         }
     }
+}
 
-    fn all() -> &'static [OrderStatus] {
-        &[
-            OrderStatus::Draft,
-            OrderStatus::PendingPayment,
-            OrderStatus::Processing,
-            OrderStatus::Fulfilled,
-            OrderStatus::Cancelled,
-        ]
+/// The change an edited cell of the orders table stands for. `value` is in the form [`CellEditor`] produces for
+/// the column.
+fn order_edit(column_id: &str, value: &str) -> Option<OrderEdit> {
+    match column_id {
+        "customer" => Some(OrderEdit::CustomerName(value.to_string())),
+        "date" => parse_date(value).map(OrderEdit::PlacedOn),
+        "channel" => SalesChannel::all()
+            .iter()
+            .find(|channel| channel.label() == value)
+            .map(|channel| OrderEdit::Channel(*channel)),
+        "flagged" => Some(OrderEdit::Flagged(value == "true")),
+        _ => None,
     }
+}
 
-    fn from_key(value: &str) -> Option<Self> {
-        match value {
-            "draft" => Some(OrderStatus::Draft),
-            "pending" => Some(OrderStatus::PendingPayment),
-            "processing" => Some(OrderStatus::Processing),
-            "fulfilled" => Some(OrderStatus::Fulfilled),
-            "cancelled" => Some(OrderStatus::Cancelled),
-            _ => None,
-        }
+fn describe_edit_error(error: EditError, i18n: I18n) -> String {
+    match error {
+        EditError::BlankCustomerName => i18n.t("orders.edit.blank_customer"),
+        EditError::FutureDate(_) => i18n.t("orders.edit.future_date"),
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum PaymentStatus {
-    Pending,
-    Paid,
-    Refunded,
-    Overdue,
+fn validate_cell(column_id: &str, value: &str, i18n: I18n) -> Result<(), String> {
+    order_edit(column_id, value)
+        .ok_or_else(|| i18n.t("orders.edit.unrecognized"))?
+        .validate(NaiveDate::today())
+        .map_err(|error| describe_edit_error(error, i18n))
 }
 
-impl PaymentStatus {
-    fn label(&self) -> &'static str {
-        match self {
-            PaymentStatus::Pending => "待入账",
-            PaymentStatus::Paid => "已支付",
-            PaymentStatus::Refunded => "已退款",
-            PaymentStatus::Overdue => "逾期",
-        }
-    }
+/// Notes shown in an expanded row; the detail page lists all of them.
+const DETAIL_NOTES: usize = 3;
 
-    fn key(&self) -> &'static str {
-        match self {
-            PaymentStatus::Pending => "pending",
-            PaymentStatus::Paid => "paid",
-            PaymentStatus::Refunded => "refunded",
-            PaymentStatus::Overdue => "overdue",
-        }
-    }
-
-    fn all() -> &'static [PaymentStatus] {
-        &[
-            PaymentStatus::Pending,
-            PaymentStatus::Paid,
-            PaymentStatus::Refunded,
-            PaymentStatus::Overdue,
-        ]
-    }
+/// Panel under an expanded row of the orders table: line items and the latest internal notes.
+#[component]
+fn OrderRowDetail(order: Order) -> Element {
+    let i18n = use_i18n();
+    let items_total: f32 = order.line_items.iter().map(|item| item.subtotal()).sum();
+    let hidden_notes = order.notes.len().saturating_sub(DETAIL_NOTES);
 
-    fn from_key(value: &str) -> Option<Self> {
-        match value {
-            "pending" => Some(PaymentStatus::Pending),
-            "paid" => Some(PaymentStatus::Paid),
-            "refunded" => Some(PaymentStatus::Refunded),
-            "overdue" => Some(PaymentStatus::Overdue),
-            _ => None,
+    rsx! {
+        div { class: "orders-row-detail",
+            div { class: "ui-stack", style: "gap: 0.5rem;",
+                span { class: "orders-metric-label", {i18n.t("orders.detail.items")} }
+                if order.line_items.is_empty() {
+                    span { class: "ui-field-helper", {i18n.t("orders.detail.no_items")} }
+                } else {
+                    ul { class: "orders-row-detail-items",
+                        for item in order.line_items.iter() {
+                            li { key: "{item.sku}",
+                                span { "{item.name}" }
+                                span { class: "ui-field-helper", "{item.sku}" }
+                                span { {format!("{} × ¥{:.2}", item.quantity, item.unit_price)} }
+                                span { {format!("¥{:.2}", item.subtotal())} }
+                            }
+                        }
+                    }
+                    span { class: "orders-metric-sub", {i18n.t_with("orders.detail.items_total", &[("total", &format!("¥{items_total:.2}"))])} }
+                }
+            }
+            div { class: "ui-stack", style: "gap: 0.5rem;",
+                span { class: "orders-metric-label", {i18n.t("orders.detail.notes")} }
+                if order.notes.is_empty() {
+                    span { class: "ui-field-helper", {i18n.t("orders.detail.no_notes")} }
+                }
+                for note in order.notes.iter().rev().take(DETAIL_NOTES) {
+                    div { class: "orders-detail-note",
+                        span { class: "orders-detail-note-meta",
+                            {format!("{} · {}", note.author, note.created_on.format("%Y-%m-%d"))}
+                        }
+                        p { "{note.body}" }
+                    }
+                }
+                if hidden_notes > 0 {
+                    Link {
+                        class: "orders-metric-sub",
+                        to: Route::OrderDetail { number: order.number.clone() },
+                        {i18n.t_count("orders.detail.more_notes", hidden_notes, &[])}
+                    }
+                }
+            }
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum FulfillmentStatus {
-    Unfulfilled,
-    Picking,
-    Shipped,
-    Delivered,
-    Returned,
+/// Columns of the interactive orders table. The ids double as export column ids; the sortable ones map onto
+/// [`OrderSortField`] through [`sort_column`]. Cells hold the canonical Chinese labels, which sorting, grouping
+/// and editing work on; columns with such labels render and group them in the language of `i18n`.
+fn order_columns(i18n: I18n) -> Vec<TableColumnConfig<Order>> {
+    vec![
+        TableColumnConfig::new("number", i18n.t("orders.column.number"))
+            .fixed()
+            .sortable(ColumnSortKind::Text)
+            .width(120.0)
+            .pin(ColumnPin::Left),
+        TableColumnConfig::new("customer", i18n.t("orders.column.customer"))
+            .sortable(ColumnSortKind::Text)
+            .editable(CellEditor::Text)
+            .validate(move |_: &Order, value: &str| validate_cell("customer", value, i18n))
+            .render(|order: &Order| {
+                rsx! {
+                    div { style: "display: flex; align-items: center; gap: 0.75rem;",
+                        Avatar {
+                            fallback: Some(initials(&order.customer_name)),
+                            alt: Some(order.customer_name.clone()),
+                        }
+                        div { style: "display: flex; flex-direction: column; gap: 0.25rem;",
+                            span { style: "font-weight: 600;", "{order.customer_name}" }
+                            span { class: "ui-field-helper", "{order.customer_email}" }
+                        }
+                    }
+                }
+            }),
+        TableColumnConfig::new("date", i18n.t("orders.column.date"))
+            .sortable(ColumnSortKind::Date)
+            .editable(CellEditor::Date)
+            .validate(move |_: &Order, value: &str| validate_cell("date", value, i18n)),
+        TableColumnConfig::new("status", i18n.t("orders.column.status"))
+            .render(move |order: &Order| {
+                rsx! {
+                    Badge { variant: status_badge(order.status), {order.status.label_in(i18n)} }
+                }
+            })
+            .format_group(move |label| {
+                translate_label(OrderStatus::all(), label, |status| {
+                    (status.label(), status.label_in(i18n))
+                })
+            }),
+        TableColumnConfig::new("payment", i18n.t("orders.column.payment")).render(
+            move |order: &Order| {
+                rsx! {
+                    Badge {
+                        variant: payment_badge(order.payment_status),
+                        {order.payment_status.label_in(i18n)}
+                    }
+                }
+            },
+        ),
+        TableColumnConfig::new("fulfillment", i18n.t("orders.column.fulfillment"))
+            .hide_by_default()
+            .render(move |order: &Order| rsx! { {order.fulfillment_status.label_in(i18n)} }),
+        TableColumnConfig::new("channel", i18n.t("orders.column.channel"))
+            .hide_by_default()
+            .editable(CellEditor::Select(
+                SalesChannel::all()
+                    .iter()
+                    .map(|channel| SelectOption::new(channel.label_in(i18n), channel.label()))
+                    .collect(),
+            ))
+            .render(move |order: &Order| rsx! { {order.channel.label_in(i18n)} })
+            .format_group(move |label| {
+                translate_label(SalesChannel::all(), label, |channel| {
+                    (channel.label(), channel.label_in(i18n))
+                })
+            }),
+        TableColumnConfig::new("flagged", i18n.t("orders.column.flagged"))
+            .hide_by_default()
+            .editable(CellEditor::Switch)
+            .render(move |order: &Order| {
+                rsx! {
+                    if order.flagged {
+                        Badge { variant: BadgeVariant::Destructive, {i18n.t("orders.column.flagged")} }
+                    } else {
+                        span { class: "orders-metric-sub", "—" }
+                    }
+                }
+            }),
+        TableColumnConfig::new("method", i18n.t("orders.column.method"))
+            .hide_by_default()
+            .render(move |order: &Order| rsx! { {order.payment_method.label_in(i18n)} })
+            .format_group(move |label| {
+                translate_label(PaymentMethod::all(), label, |method| {
+                    (method.label(), method.label_in(i18n))
+                })
+            }),
+        TableColumnConfig::new("total", i18n.t("orders.column.total"))
+            .sortable(ColumnSortKind::Numeric)
+            .aggregate(Aggregate::Sum)
+            .aggregate(Aggregate::Avg)
+            .format_aggregate(|_, value| format!("¥{value:.2}")),
+        TableColumnConfig::new("tags", i18n.t("orders.column.tags")).render(|order: &Order| {
+            rsx! {
+                div { class: "orders-tag-cloud",
+                    for tag in order.tags.iter() {
+                        Badge { variant: BadgeVariant::Outline, "{tag}" }
+                    }
+                }
+            }
+        }),
+        TableColumnConfig::new(ACTIONS_COLUMN, i18n.t("orders.column.actions"))
+            .fixed()
+            .width(96.0)
+            .pin(ColumnPin::Right)
+            .render(move |order: &Order| {
+                let number = order.number.clone();
+                rsx! {
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Sm,
+                        on_click: move |_| {
+                            navigator().push(Route::OrderDetail {
+                                number: number.clone(),
+                            });
+                        },
+                        {i18n.t("orders.view")}
+                    }
+                }
+            }),
+    ]
 }
 
-impl FulfillmentStatus {
-    fn label(&self) -> &'static str {
-        match self {
-            FulfillmentStatus::Unfulfilled => "待打包",
-            FulfillmentStatus::Picking => "拣货中",
-            FulfillmentStatus::Shipped => "运输中",
-            FulfillmentStatus::Delivered => "已签收",
-            FulfillmentStatus::Returned => "已退回",
-        }
-    }
-
-    fn key(&self) -> &'static str {
-        match self {
-            FulfillmentStatus::Unfulfilled => "unfulfilled",
-            FulfillmentStatus::Picking => "picking",
-            FulfillmentStatus::Shipped => "shipped",
-            FulfillmentStatus::Delivered => "delivered",
-            FulfillmentStatus::Returned => "returned",
-        }
-    }
+/// The translation of a canonical label, found through the value it belongs to; unknown labels are kept.
+fn translate_label<T>(
+    values: &[T],
+    label: &str,
+    labels: impl Fn(&T) -> (&'static str, String),
+) -> String {
+    values
+        .iter()
+        .map(labels)
+        .find(|(canonical, _)| *canonical == label)
+        .map(|(_, translated)| translated)
+        .unwrap_or_else(|| label.to_string())
+}
 
-    fn all() -> &'static [FulfillmentStatus] {
-        &[
-            FulfillmentStatus::Unfulfilled,
-            FulfillmentStatus::Picking,
-            FulfillmentStatus::Shipped,
-            FulfillmentStatus::Delivered,
-            FulfillmentStatus::Returned,
-        ]
+/// The aggregates of the orders table's `total` column for `count` orders worth `gross_revenue` together.
+fn order_summary(count: usize, gross_revenue: f32) -> TableSummary {
+    let summary = TableSummary::new(count).with("total", Aggregate::Sum, gross_revenue as f64);
+    if count > 0 {
+        summary.with(
+            "total",
+            Aggregate::Avg,
+            (gross_revenue / count as f32) as f64,
+        )
+    } else {
+        summary
     }
+}
 
-    fn from_key(value: &str) -> Option<Self> {
-        match value {
-            "unfulfilled" => Some(FulfillmentStatus::Unfulfilled),
-            "picking" => Some(FulfillmentStatus::Picking),
-            "shipped" => Some(FulfillmentStatus::Shipped),
-            "delivered" => Some(FulfillmentStatus::Delivered),
-            "returned" => Some(FulfillmentStatus::Returned),
-            _ => None,
-        }
+fn sort_column(field: OrderSortField) -> &'static str {
+    match field {
+        OrderSortField::PlacedOn => "date",
+        OrderSortField::Number => "number",
+        OrderSortField::Customer => "customer",
+        OrderSortField::Total => "total",
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum SalesChannel {
-    OnlineStore,
-    Marketplace,
-    Wholesale,
-    PopUp,
-    Subscription,
+fn table_sort(sorts: &[OrderSort]) -> Vec<TableSort> {
+    sorts
+        .iter()
+        .map(|sort| match sort.direction {
+            SortDirection::Ascending => TableSort::asc(sort_column(sort.field)),
+            SortDirection::Descending => TableSort::desc(sort_column(sort.field)),
+        })
+        .collect()
 }
 
-impl SalesChannel {
-    fn label(&self) -> &'static str {
-        match self {
-            SalesChannel::OnlineStore => "官网商城",
-            SalesChannel::Marketplace => "第三方平台",
-            SalesChannel::Wholesale => "批发",
-            SalesChannel::PopUp => "快闪店",
-            SalesChannel::Subscription => "订阅",
-        }
-    }
-
-    fn key(&self) -> &'static str {
-        match self {
-            SalesChannel::OnlineStore => "store",
-            SalesChannel::Marketplace => "marketplace",
-            SalesChannel::Wholesale => "wholesale",
-            SalesChannel::PopUp => "popup",
-            SalesChannel::Subscription => "subscription",
-        }
-    }
-
-    fn all() -> &'static [SalesChannel] {
-        &[
-            SalesChannel::OnlineStore,
-            SalesChannel::Marketplace,
-            SalesChannel::Wholesale,
-            SalesChannel::PopUp,
-            SalesChannel::Subscription,
-        ]
-    }
+fn order_sort(sorts: &[TableSort]) -> Vec<OrderSort> {
+    sorts
+        .iter()
+        .filter_map(|sort| {
+            let field = OrderSortField::all()
+                .iter()
+                .copied()
+                .find(|field| sort_column(*field) == sort.column_id)?;
+            let direction = match sort.direction {
+                TableSortDirection::Ascending => SortDirection::Ascending,
+                TableSortDirection::Descending => SortDirection::Descending,
+            };
+            Some(OrderSort::new(field, direction))
+        })
+        .collect()
+}
 
-    fn from_key(value: &str) -> Option<Self> {
-        match value {
-            "store" => Some(SalesChannel::OnlineStore),
-            "marketplace" => Some(SalesChannel::Marketplace),
-            "wholesale" => Some(SalesChannel::Wholesale),
-            "popup" => Some(SalesChannel::PopUp),
-            "subscription" => Some(SalesChannel::Subscription),
-            _ => None,
+/// `query` as the filter widgets can hold it: the date range picker only holds complete ranges, and the page size
+/// is fixed by this page rather than by the link.
+fn on_screen(query: OrderQuery) -> OrderQuery {
+    let (placed_from, placed_to) = match (query.placed_from, query.placed_to) {
+        (Some(from), Some(to)) => {
+            let range = DateRange::new(from, to);
+            (Some(range.start), Some(range.end))
         }
+        _ => (None, None),
+    };
+    OrderQuery {
+        placed_from,
+        placed_to,
+        page_size: PAGE_SIZE,
+        ..query
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum PaymentMethod {
-    CreditCard,
-    BankTransfer,
-    Cash,
-    Paypal,
-    WechatPay,
-    Alipay,
-}
+/// The order list. Filters, sort and page are mirrored into the URL (`query`), so a filtered list can be shared
+/// as a link and the browser's back and forward buttons step through earlier filters.
+#[component]
+pub fn Orders(query: OrderQuery) -> Element {
+    let i18n = use_i18n();
+    let initial = use_hook(|| on_screen(query.clone()));
+    let opened_from_link = use_hook(|| query != OrderQuery::default());
+    let mut search = use_signal(|| initial.search.clone());
+    let mut status_filter = use_signal(|| initial.status);
+    let mut payment_filter = use_signal(|| initial.payment_status);
+    let mut fulfillment_filter = use_signal(|| initial.fulfillment_status);
+    let mut channel_filter = use_signal(|| initial.channel);
+    let mut method_filter = use_signal(|| initial.payment_method);
+    let mut tags_filter = use_signal(|| initial.tags.clone());
+    let mut min_total = use_signal(|| initial.min_total);
+    let mut flagged_only = use_signal(|| initial.flagged_only);
+    let mut date_range = use_signal(|| match (initial.placed_from, initial.placed_to) {
+        (Some(from), Some(to)) => Some(DateRange::new(from, to)),
+        _ => None,
+    });
+    let mut pipeline = use_signal(|| vec![initial.pipeline.key().to_string()]);
+    let mut sort = use_signal(|| initial.sort.clone());
+    // The page picked in the pagination, together with the filters it was picked under. Any other filters start
+    // again from page 1.
+    let mut page = use_signal(|| {
+        (
+            OrderQuery {
+                page: 1,
+                ..initial.clone()
+            },
+            initial.page,
+        )
+    });
+    let mut selected_numbers = use_signal(Vec::<String>::new);
+    // Field the table groups its rows by; `None` lists them flat. Part of the query, so the server aggregates
+    // each group over every page.
+    let mut group_by = use_signal(|| initial.group_by);
 
-impl PaymentMethod {
-    fn label(&self) -> &'static str {
-        match self {
-            PaymentMethod::CreditCard => "信用卡",
-            PaymentMethod::BankTransfer => "银行转账",
-            PaymentMethod::Cash => "现金",
-            PaymentMethod::Paypal => "PayPal",
-            PaymentMethod::WechatPay => "微信支付",
-            PaymentMethod::Alipay => "支付宝",
+    let filters = move || {
+        let range = date_range();
+        OrderQuery {
+            search: search(),
+            status: status_filter(),
+            payment_status: payment_filter(),
+            fulfillment_status: fulfillment_filter(),
+            channel: channel_filter(),
+            payment_method: method_filter(),
+            tags: tags_filter(),
+            placed_from: range.map(|range| range.start),
+            placed_to: range.map(|range| range.end),
+            min_total: min_total(),
+            flagged_only: flagged_only(),
+            pipeline: pipeline()
+                .first()
+                .and_then(|key| OrderPipeline::from_key(key))
+                .unwrap_or_default(),
+            sort: {
+                let sorts = sort();
+                if sorts.is_empty() {
+                    vec![OrderSort::default()]
+                } else {
+                    sorts
+                }
+            },
+            group_by: group_by(),
+            page: 1,
+            page_size: PAGE_SIZE,
         }
-    }
-
-    fn key(&self) -> &'static str {
-        match self {
-            PaymentMethod::CreditCard => "card",
-            PaymentMethod::BankTransfer => "transfer",
-            PaymentMethod::Cash => "cash",
-            PaymentMethod::Paypal => "paypal",
-            PaymentMethod::WechatPay => "wechat",
-            PaymentMethod::Alipay => "alipay",
+    };
+    let current_query = use_memo(move || {
+        let filters = filters();
+        let (picked_under, number) = page();
+        OrderQuery {
+            page: if picked_under == filters { number } else { 1 },
+            ..filters
         }
-    }
+    });
+    let mut orders_page = use_resource(move || {
+        let query = current_query();
+        async move { query_orders(query).await }
+    });
 
-    fn all() -> &'static [PaymentMethod] {
-        &[
-            PaymentMethod::CreditCard,
-            PaymentMethod::BankTransfer,
-            PaymentMethod::Cash,
-            PaymentMethod::Paypal,
-            PaymentMethod::WechatPay,
-            PaymentMethod::Alipay,
-        ]
-    }
+    // Column order, visibility, widths and pins of the orders table; the visible ids are also the export columns.
+    let mut current_layout = use_signal(|| TableLayout::from_columns(&order_columns(i18n)));
 
-    fn from_key(value: &str) -> Option<Self> {
-        match value {
-            "card" => Some(PaymentMethod::CreditCard),
-            "transfer" => Some(PaymentMethod::BankTransfer),
-            "cash" => Some(PaymentMethod::Cash),
-            "paypal" => Some(PaymentMethod::Paypal),
-            "wechat" => Some(PaymentMethod::WechatPay),
-            "alipay" => Some(PaymentMethod::Alipay),
+    // Sets every filter and the sort to what `query` describes, which also takes the list back to page 1.
+    let mut apply_query = move |query: OrderQuery| {
+        search.set(query.search);
+        status_filter.set(query.status);
+        payment_filter.set(query.payment_status);
+        fulfillment_filter.set(query.fulfillment_status);
+        channel_filter.set(query.channel);
+        method_filter.set(query.payment_method);
+        tags_filter.set(query.tags);
+        date_range.set(match (query.placed_from, query.placed_to) {
+            (Some(from), Some(to)) => Some(DateRange::new(from, to)),
             _ => None,
-        }
-    }
-}
-
-fn seeded_orders() -> Vec<Order> {
-    vec![
-        Order {
-            number: "DX-1050".to_string(),
-            placed_on: date(2024, 7, 23),
-            customer_name: "孙若水".to_string(),
-            customer_email: "ruoshui@example.com".to_string(),
-            status: OrderStatus::Processing,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Picking,
-            payment_method: PaymentMethod::CreditCard,
-            channel: SalesChannel::OnlineStore,
-            total: 1288.0,
-            tags: tags(&["VIP", "加急"]),
-            flagged: true,
-        },
-        Order {
-            number: "DX-1049".to_string(),
-            placed_on: date(2024, 7, 22),
-            customer_name: "李倩".to_string(),
-            customer_email: "lian@example.com".to_string(),
-            status: OrderStatus::PendingPayment,
-            payment_status: PaymentStatus::Pending,
-            fulfillment_status: FulfillmentStatus::Unfulfilled,
-            payment_method: PaymentMethod::WechatPay,
-            channel: SalesChannel::Marketplace,
-            total: 342.0,
-            tags: tags(&["需回访"]),
-            flagged: true,
-        },
-        Order {
-            number: "DX-1048".to_string(),
-            placed_on: date(2024, 7, 21),
-            customer_name: "Zoe Chen".to_string(),
-            customer_email: "zoe@example.com".to_string(),
-            status: OrderStatus::Processing,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Shipped,
-            payment_method: PaymentMethod::Paypal,
-            channel: SalesChannel::Marketplace,
-            total: 812.5,
-            tags: tags(&["加急"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1047".to_string(),
-            placed_on: date(2024, 7, 20),
-            customer_name: "王宏".to_string(),
-            customer_email: "hong@example.com".to_string(),
-            status: OrderStatus::Fulfilled,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Delivered,
-            payment_method: PaymentMethod::CreditCard,
-            channel: SalesChannel::OnlineStore,
-            total: 1560.0,
-            tags: tags(&["VIP", "赠品"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1046".to_string(),
-            placed_on: date(2024, 7, 18),
-            customer_name: "刘洋".to_string(),
-            customer_email: "yang@example.com".to_string(),
-            status: OrderStatus::Processing,
-            payment_status: PaymentStatus::Overdue,
-            fulfillment_status: FulfillmentStatus::Unfulfilled,
-            payment_method: PaymentMethod::BankTransfer,
-            channel: SalesChannel::Wholesale,
-            total: 2890.4,
-            tags: tags(&["缺货", "需回访"]),
-            flagged: true,
-        },
-        Order {
-            number: "DX-1045".to_string(),
-            placed_on: date(2024, 7, 17),
-            customer_name: "陈浩".to_string(),
-            customer_email: "hao@example.com".to_string(),
-            status: OrderStatus::Cancelled,
-            payment_status: PaymentStatus::Refunded,
-            fulfillment_status: FulfillmentStatus::Returned,
-            payment_method: PaymentMethod::CreditCard,
-            channel: SalesChannel::OnlineStore,
-            total: 420.0,
-            tags: tags(&["重复下单"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1044".to_string(),
-            placed_on: date(2024, 7, 16),
-            customer_name: "Marvin Zhou".to_string(),
-            customer_email: "marvin@example.com".to_string(),
-            status: OrderStatus::Processing,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Shipped,
-            payment_method: PaymentMethod::CreditCard,
-            channel: SalesChannel::PopUp,
-            total: 980.0,
-            tags: tags(&["赠品"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1043".to_string(),
-            placed_on: date(2024, 7, 15),
-            customer_name: "张伟".to_string(),
-            customer_email: "zhangwei@example.com".to_string(),
-            status: OrderStatus::Fulfilled,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Delivered,
-            payment_method: PaymentMethod::WechatPay,
-            channel: SalesChannel::Subscription,
-            total: 652.0,
-            tags: tags(&["VIP"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1042".to_string(),
-            placed_on: date(2024, 7, 13),
-            customer_name: "李雷".to_string(),
-            customer_email: "lilei@example.com".to_string(),
-            status: OrderStatus::PendingPayment,
-            payment_status: PaymentStatus::Pending,
-            fulfillment_status: FulfillmentStatus::Unfulfilled,
-            payment_method: PaymentMethod::BankTransfer,
-            channel: SalesChannel::Wholesale,
-            total: 3750.0,
-            tags: tags(&["缺货", "加急"]),
-            flagged: true,
-        },
-        Order {
-            number: "DX-1041".to_string(),
-            placed_on: date(2024, 7, 11),
-            customer_name: "丁一".to_string(),
-            customer_email: "dingyi@example.com".to_string(),
-            status: OrderStatus::Processing,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Picking,
-            payment_method: PaymentMethod::Alipay,
-            channel: SalesChannel::OnlineStore,
-            total: 512.8,
-            tags: tags(&["赠品"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1040".to_string(),
-            placed_on: date(2024, 7, 10),
-            customer_name: "Grace Li".to_string(),
-            customer_email: "grace@example.com".to_string(),
-            status: OrderStatus::Fulfilled,
-            payment_status: PaymentStatus::Paid,
-            fulfillment_status: FulfillmentStatus::Delivered,
-            payment_method: PaymentMethod::CreditCard,
-            channel: SalesChannel::Marketplace,
-            total: 786.2,
-            tags: tags(&["VIP", "赠品"]),
-            flagged: false,
-        },
-        Order {
-            number: "DX-1039".to_string(),
-            placed_on: date(2024, 7, 9),
-            customer_name: "赵敏".to_string(),
-            customer_email: "zhaomin@example.com".to_string(),
-            status: OrderStatus::Draft,
-            payment_status: PaymentStatus::Pending,
-            fulfillment_status: FulfillmentStatus::Unfulfilled,
-            payment_method: PaymentMethod::Cash,
-            channel: SalesChannel::PopUp,
-            total: 210.0,
-            tags: tags(&["需回访"]),
-            flagged: false,
-        },
-    ]
-}
-
-fn date(year: i32, month: u32, day: u32) -> NaiveDate {
-    NaiveDate::from_ymd_opt(year, month, day).expect("valid mock date")
-}
-
-fn tags(values: &[&str]) -> Vec<String> {
-    values.iter().map(|value| (*value).to_string()).collect()
-}
-
-fn initials(input: &str) -> String {
-    let initials: String = input
-        .split_whitespace()
-        .filter_map(|part| part.chars().next())
-        .take(2)
-        .collect();
-    if initials.is_empty() {
-        input.chars().take(2).collect()
-    } else {
-        initials.to_uppercase()
-    }
-}
-
-#[component]
-pub fn Orders() -> Element {
-    let search = use_signal(|| String::new());
-    let status_filter = use_signal(|| None::<OrderStatus>);
-    let payment_filter = use_signal(|| None::<PaymentStatus>);
-    let fulfillment_filter = use_signal(|| None::<FulfillmentStatus>);
-    let channel_filter = use_signal(|| None::<SalesChannel>);
-    let method_filter = use_signal(|| None::<PaymentMethod>);
-    let tags_filter = use_signal(|| Vec::<String>::new());
-    let min_total = use_signal(|| 0.0f32);
-    let flagged_only = use_signal(|| false);
-    let date_range = use_signal(|| None::<DateRange>);
-    let pipeline = use_signal(|| vec!["all".to_string()]);
-    let page = use_signal(|| 1usize);
+        });
+        min_total.set(query.min_total);
+        flagged_only.set(query.flagged_only);
+        pipeline.set(vec![query.pipeline.key().to_string()]);
+        sort.set(query.sort);
+        group_by.set(query.group_by);
+    };
 
-    {
-        let search_signal = search.clone();
-        let status_signal = status_filter.clone();
-        let payment_signal = payment_filter.clone();
-        let fulfillment_signal = fulfillment_filter.clone();
-        let channel_signal = channel_filter.clone();
-        let method_signal = method_filter.clone();
-        let tags_signal = tags_filter.clone();
-        let range_signal = date_range.clone();
-        let min_total_signal = min_total.clone();
-        let flagged_signal = flagged_only.clone();
-        let pipeline_signal = pipeline.clone();
-        let mut page_signal = page.clone();
+    // The query string of the current URL, as the filters hold it.
+    let mut url_query = use_signal(|| initial.clone());
+    use_effect(use_reactive((&query,), move |(query,)| {
+        let query = on_screen(query);
+        if *url_query.peek() == query {
+            return;
+        }
+        url_query.set(query.clone());
+        if *current_query.peek() != query {
+            page.set((
+                OrderQuery {
+                    page: 1,
+                    ..query.clone()
+                },
+                query.page,
+            ));
+            apply_query(query);
+        }
+    }));
+    use_effect(move || {
+        let query = current_query();
+        let previous = url_query.peek().clone();
+        if query == previous {
+            return;
+        }
+        url_query.set(query.clone());
+        let route = Route::Orders {
+            query: query.clone(),
+        };
+        // Typing in the search box rewrites the current history entry instead of adding one per keystroke.
+        let search_only = OrderQuery {
+            search: previous.search.clone(),
+            ..query
+        } == previous;
+        if search_only {
+            navigator().replace(route);
+        } else {
+            navigator().push(route);
+        }
+    });
 
-        use_effect(move || {
-            search_signal();
-            status_signal();
-            payment_signal();
-            fulfillment_signal();
-            channel_signal();
-            method_signal();
-            tags_signal();
-            range_signal();
-            min_total_signal();
-            flagged_signal();
-            pipeline_signal();
-            page_signal.set(1);
+    let mut exporting = use_signal(|| false);
+    let mut export_message = use_signal(|| None::<String>);
+    let export_report = move |key: String| {
+        let Some(format) = ExportFormat::from_key(&key) else {
+            return;
+        };
+        if exporting() {
+            return;
+        }
+        let query = current_query();
+        let definitions = order_columns(i18n);
+        let columns: Vec<ExportColumn> = current_layout
+            .read()
+            .visible
+            .iter()
+            .filter(|id| *id != ACTIONS_COLUMN)
+            .filter_map(|id| definitions.iter().find(|column| &column.id == id))
+            .flat_map(|column| {
+                let exported = ExportColumn::new(column.id.clone(), column.label.clone());
+                // The table shows the email under the name; the file keeps it in a column of its own so the
+                // import reads both back.
+                let email = (column.id == "customer")
+                    .then(|| ExportColumn::new("email", i18n.t("order.field.email")));
+                std::iter::once(exported).chain(email)
+            })
+            .collect();
+        exporting.set(true);
+        spawn(async move {
+            let result = match export_orders(query).await {
+                Ok(orders) => {
+                    let file = export(&orders, &columns, format, NaiveDate::today(), i18n);
+                    save_download(&file.name, file.mime_type, file.bytes)
+                        .await
+                        .map(|location| {
+                            i18n.t_count(
+                                "orders.export.done",
+                                orders.len(),
+                                &[("location", &location)],
+                            )
+                        })
+                }
+                Err(err) => Err(err.to_string()),
+            };
+            export_message.set(Some(result.unwrap_or_else(|err| {
+                i18n.t_with("orders.export.failed", &[("error", &err)])
+            })));
+            exporting.set(false);
         });
-    }
-
-    let all_orders = seeded_orders();
-    let total_orders_count = all_orders.len();
+    };
+    let export_items = ExportFormat::all()
+        .iter()
+        .map(|format| DropdownMenuItem::new(format.label(), format.key()))
+        .collect::<Vec<_>>();
+    let (order_page, load_error) = match &*orders_page.read() {
+        Some(Ok(result)) => (result.clone(), None),
+        Some(Err(err)) => (OrderPage::default(), Some(err.to_string())),
+        None => (OrderPage::default(), None),
+    };
+    let is_loading = orders_page.read().is_none();
+    let total_orders_count = order_page.unfiltered_total;
+    let filtered_total = order_page.total;
+    let OrderMetrics {
+        gross_revenue,
+        average_order_value,
+        outstanding_payments,
+        fulfillment_queue: awaiting_fulfillment,
+        flagged: flagged_orders,
+    } = order_page.metrics;
+    let page_count = order_page.page_count;
+    // The footer and the group headers show the server's aggregates over every matching order, not the page's.
+    let total_summary = order_summary(filtered_total, gross_revenue);
+    let group_summaries: HashMap<String, TableSummary> = order_page
+        .groups
+        .iter()
+        .map(|group| {
+            (
+                group.key.clone(),
+                order_summary(group.count, group.gross_revenue),
+            )
+        })
+        .collect();
+    let grouped_by = group_by();
+    let effective_page = order_page.page.max(1);
+    let paginated_orders: Vec<Order> = order_page.orders;
+    let selected_orders: Vec<Order> = paginated_orders
+        .iter()
+        .filter(|order| selected_numbers.read().contains(&order.number))
+        .cloned()
+        .collect();
 
     let search_value = search();
-    let search_term = search_value.to_lowercase();
     let status_selected = status_filter();
     let payment_selected = payment_filter();
     let fulfillment_selected = fulfillment_filter();
     let channel_selected = channel_filter();
     let method_selected = method_filter();
-    let selected_tags = tags_filter();
-    let active_tag_count = selected_tags.len();
+    let active_tag_count = tags_filter.read().len();
     let min_total_value = min_total();
-    let flagged_only_value = flagged_only();
+    let sort_selected = sort();
     let date_range_selected = date_range();
     let date_range_label = date_range_selected
         .map(|range| {
@@ -561,170 +638,50 @@ pub fn Orders() -> Element {
                 format!("{} → {}", start, end)
             }
         })
-        .unwrap_or_else(|| "选择日期范围".to_string());
+        .unwrap_or_else(|| i18n.t("orders.filter.date_placeholder"));
     let date_range_helper = date_range_selected
         .map(|range| {
             let span = (range.end - range.start).num_days().abs() + 1;
-            format!("覆盖 {} 天，点击可修改", span)
+            i18n.t_count("orders.filter.date_span", span as usize, &[])
         })
-        .unwrap_or_else(|| "未限制下单日期".to_string());
-    let pipeline_values = pipeline();
-    let pipeline_value = pipeline_values
-        .first()
-        .cloned()
-        .unwrap_or_else(|| "all".to_string());
+        .unwrap_or_else(|| i18n.t("orders.filter.date_unlimited"));
     let tag_chip_options: Vec<CheckboxChipOption> = AVAILABLE_TAGS
         .iter()
         .map(|tag| CheckboxChipOption::new(*tag, *tag))
         .collect();
 
-    let filtered: Vec<Order> = all_orders
-        .iter()
-        .cloned()
-        .filter(|order| {
-            let matches_search = if !search_term.is_empty() {
-                let composite = format!(
-                    "{} {} {} {} {}",
-                    order.number,
-                    order.customer_name,
-                    order.customer_email,
-                    order.channel.label(),
-                    order.tags.join(" ")
-                )
-                .to_lowercase();
-                composite.contains(&search_term)
-            } else {
-                true
-            };
-
-            let matches_status = status_selected
-                .map(|expected| order.status == expected)
-                .unwrap_or(true);
-            let matches_payment = payment_selected
-                .map(|expected| order.payment_status == expected)
-                .unwrap_or(true);
-            let matches_fulfillment = fulfillment_selected
-                .map(|expected| order.fulfillment_status == expected)
-                .unwrap_or(true);
-            let matches_channel = channel_selected
-                .map(|expected| order.channel == expected)
-                .unwrap_or(true);
-            let matches_method = method_selected
-                .map(|expected| order.payment_method == expected)
-                .unwrap_or(true);
-
-            let matches_tags = if selected_tags.is_empty() {
-                true
-            } else {
-                selected_tags
-                    .iter()
-                    .all(|tag| order.tags.iter().any(|candidate| candidate == tag))
-            };
-
-            let matches_range = if let Some(range) = date_range_selected {
-                let date = order.placed_on;
-                date >= range.start && date <= range.end
-            } else {
-                true
-            };
-
-            let matches_total = if min_total_value > 0.0 {
-                order.total >= min_total_value
-            } else {
-                true
-            };
-
-            let matches_flagged = if flagged_only_value {
-                order.flagged
-            } else {
-                true
-            };
-
-            let matches_pipeline = match pipeline_value.as_str() {
-                "awaiting_fulfillment" => !matches!(
-                    order.fulfillment_status,
-                    FulfillmentStatus::Delivered | FulfillmentStatus::Returned
-                ),
-                "overdue" => matches!(order.payment_status, PaymentStatus::Overdue),
-                "vip" => order.tags.iter().any(|tag| tag == "VIP"),
-                _ => true,
-            };
-
-            matches_search
-                && matches_status
-                && matches_payment
-                && matches_fulfillment
-                && matches_channel
-                && matches_method
-                && matches_tags
-                && matches_range
-                && matches_total
-                && matches_flagged
-                && matches_pipeline
-        })
-        .collect();
-
-    let filtered_total = filtered.len();
-    let gross_revenue = filtered.iter().map(|order| order.total).sum::<f32>();
-    let average_order_value = if filtered_total > 0 {
-        gross_revenue / filtered_total as f32
-    } else {
-        0.0
-    };
-    let outstanding_payments = filtered
-        .iter()
-        .filter(|order| {
-            matches!(
-                order.payment_status,
-                PaymentStatus::Pending | PaymentStatus::Overdue
-            )
-        })
-        .count();
-    let awaiting_fulfillment = filtered
-        .iter()
-        .filter(|order| {
-            !matches!(
-                order.fulfillment_status,
-                FulfillmentStatus::Delivered | FulfillmentStatus::Returned
-            )
-        })
-        .count();
-    let flagged_orders = filtered.iter().filter(|order| order.flagged).count();
-
-    let page_count = ((filtered_total + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
-    let active_page = page();
-    let effective_page = active_page.max(1).min(page_count);
-    if active_page != effective_page {
-        let mut page_signal = page.clone();
-        page_signal.set(effective_page);
-    }
-    let offset = effective_page.saturating_sub(1) * PAGE_SIZE;
-    let paginated_orders: Vec<Order> = filtered
-        .iter()
-        .skip(offset)
-        .take(PAGE_SIZE)
-        .cloned()
-        .collect();
-
     let status_options = OrderStatus::all()
         .iter()
-        .map(|status| SelectOption::new(status.label(), status.key()))
+        .map(|status| SelectOption::new(status.label_in(i18n), status.key()))
         .collect::<Vec<_>>();
     let payment_options = PaymentStatus::all()
         .iter()
-        .map(|status| SelectOption::new(status.label(), status.key()))
+        .map(|status| SelectOption::new(status.label_in(i18n), status.key()))
         .collect::<Vec<_>>();
     let fulfillment_options = FulfillmentStatus::all()
         .iter()
-        .map(|status| SelectOption::new(status.label(), status.key()))
+        .map(|status| SelectOption::new(status.label_in(i18n), status.key()))
         .collect::<Vec<_>>();
     let channel_options = SalesChannel::all()
         .iter()
-        .map(|channel| SelectOption::new(channel.label(), channel.key()))
+        .map(|channel| SelectOption::new(channel.label_in(i18n), channel.key()))
         .collect::<Vec<_>>();
     let method_options = PaymentMethod::all()
         .iter()
-        .map(|method| SelectOption::new(method.label(), method.key()))
+        .map(|method| SelectOption::new(method.label_in(i18n), method.key()))
+        .collect::<Vec<_>>();
+    let sort_options = OrderSortField::all()
+        .iter()
+        .flat_map(|field| {
+            [SortDirection::Descending, SortDirection::Ascending].map(|direction| {
+                let arrow = match direction {
+                    SortDirection::Ascending => "↑",
+                    SortDirection::Descending => "↓",
+                };
+                let sort = OrderSort::new(*field, direction);
+                SelectOption::new(format!("{} {arrow}", field.label_in(i18n)), sort.key())
+            })
+        })
         .collect::<Vec<_>>();
 
     rsx! {
@@ -733,30 +690,30 @@ pub fn Orders() -> Element {
             style: "gap: 1.5rem;",
             Card {
                 CardHeader {
-                    CardTitle { "订单管理" }
-                    CardDescription { "综合筛选订单、监控支付与履约状态，获取健康度指标。" }
+                    CardTitle { {i18n.t("orders.title")} }
+                    CardDescription { {i18n.t("orders.description")} }
                 }
                 CardContent {
                     div { class: "orders-metrics",
                         div { class: "orders-metric-card",
-                            span { class: "orders-metric-label", "筛选后订单" }
+                            span { class: "orders-metric-label", {i18n.t("orders.metric.filtered")} }
                             span { class: "orders-metric-value", "{filtered_total}" }
-                            span { class: "orders-metric-sub", {format!("共 {} 条记录", total_orders_count)} }
+                            span { class: "orders-metric-sub", {i18n.t_count("orders.metric.total_records", total_orders_count, &[])} }
                         }
                         div { class: "orders-metric-card",
-                            span { class: "orders-metric-label", "筛选总收入" }
+                            span { class: "orders-metric-label", {i18n.t("orders.metric.revenue")} }
                             span { class: "orders-metric-value", {format!("¥{:.0}", gross_revenue)} }
-                            span { class: "orders-metric-sub", {format!("平均客单价 ¥{:.0}", average_order_value)} }
+                            span { class: "orders-metric-sub", {i18n.t_with("orders.metric.average", &[("value", &format!("¥{average_order_value:.0}"))])} }
                         }
                         div { class: "orders-metric-card",
-                            span { class: "orders-metric-label", "待处理支付" }
+                            span { class: "orders-metric-label", {i18n.t("orders.metric.outstanding")} }
                             span { class: "orders-metric-value", "{outstanding_payments}" }
-                            span { class: "orders-metric-sub", "包含逾期与待入账订单" }
+                            span { class: "orders-metric-sub", {i18n.t("orders.metric.outstanding_hint")} }
                         }
                         div { class: "orders-metric-card",
-                            span { class: "orders-metric-label", "履约队列" }
+                            span { class: "orders-metric-label", {i18n.t("orders.metric.fulfillment")} }
                             span { class: "orders-metric-value", "{awaiting_fulfillment}" }
-                            span { class: "orders-metric-sub", {format!("标记关注 {} 单", flagged_orders)} }
+                            span { class: "orders-metric-sub", {i18n.t_count("orders.metric.flagged", flagged_orders, &[])} }
                         }
                     }
                 }
@@ -764,16 +721,16 @@ pub fn Orders() -> Element {
 
             Card {
                 CardHeader {
-                    CardTitle { "筛选器" }
-                    CardDescription { "组合多个维度快速圈定目标订单，可随时重置。" }
+                    CardTitle { {i18n.t("orders.filter.title")} }
+                    CardDescription { {i18n.t("orders.filter.description")} }
                 }
                 CardContent {
                     div { class: "orders-filter-grid",
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { html_for: "order-search", "关键词" }
+                            Label { html_for: "order-search", {i18n.t("orders.filter.search")} }
                             Input {
                                 id: Some("order-search".to_string()),
-                                placeholder: Some("订单号 / 客户 / 邮箱".to_string()),
+                                placeholder: Some(i18n.t("orders.filter.search_placeholder")),
                                 value: Some(search_value.clone()),
                                 on_input: {
                                     let mut setter = search.clone();
@@ -782,9 +739,9 @@ pub fn Orders() -> Element {
                             }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "订单状态" }
+                            Label { {i18n.t("orders.filter.status")} }
                             Select {
-                                placeholder: "全部状态",
+                                placeholder: i18n.t("orders.filter.all_statuses"),
                                 options: status_options.clone(),
                                 selected: status_selected.map(|status| status.key().to_string()),
                                 on_change: {
@@ -794,9 +751,9 @@ pub fn Orders() -> Element {
                             }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "支付状态" }
+                            Label { {i18n.t("orders.filter.payment")} }
                             Select {
-                                placeholder: "全部支付".to_string(),
+                                placeholder: i18n.t("orders.filter.all_payments"),
                                 options: payment_options.clone(),
                                 selected: payment_selected.map(|status| status.key().to_string()),
                                 on_change: {
@@ -806,9 +763,9 @@ pub fn Orders() -> Element {
                             }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "履约状态" }
+                            Label { {i18n.t("orders.filter.fulfillment")} }
                             Select {
-                                placeholder: "全部履约".to_string(),
+                                placeholder: i18n.t("orders.filter.all_fulfillments"),
                                 options: fulfillment_options.clone(),
                                 selected: fulfillment_selected.map(|status| status.key().to_string()),
                                 on_change: {
@@ -818,9 +775,9 @@ pub fn Orders() -> Element {
                             }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "销售渠道" }
+                            Label { {i18n.t("orders.filter.channel")} }
                             Select {
-                                placeholder: "全部渠道".to_string(),
+                                placeholder: i18n.t("orders.filter.all_channels"),
                                 options: channel_options.clone(),
                                 selected: channel_selected.map(|channel| channel.key().to_string()),
                                 on_change: {
@@ -830,9 +787,9 @@ pub fn Orders() -> Element {
                             }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "支付方式" }
+                            Label { {i18n.t("orders.filter.method")} }
                             Select {
-                                placeholder: "全部方式".to_string(),
+                                placeholder: i18n.t("orders.filter.all_methods"),
                                 options: method_options.clone(),
                                 selected: method_selected.map(|method| method.key().to_string()),
                                 on_change: {
@@ -842,7 +799,21 @@ pub fn Orders() -> Element {
                             }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "下单日期" }
+                            Label { {i18n.t("orders.filter.sort")} }
+                            Select {
+                                placeholder: i18n.t("orders.filter.default_sort"),
+                                options: sort_options,
+                                selected: sort_selected.first().map(OrderSort::key),
+                                on_change: {
+                                    let mut setter = sort;
+                                    move |value: String| {
+                                        setter.set(vec![OrderSort::from_key(&value).unwrap_or_default()])
+                                    }
+                                },
+                            }
+                        }
+                        div { class: "ui-stack", style: "gap: 0.5rem;",
+                            Label { {i18n.t("orders.filter.placed_on")} }
                             Popover {
                                 placement: "bottom".to_string(),
                                 trigger: rsx! {
@@ -861,6 +832,15 @@ pub fn Orders() -> Element {
                                                 let mut setter = date_range.clone();
                                                 move |range: Option<DateRange>| setter.set(range)
                                             },
+                                            presets: vec![
+                                                DateRangePreset::last_days(7, i18n),
+                                                DateRangePreset::last_days(30, i18n),
+                                                DateRangePreset::last_days(90, i18n),
+                                                DateRangePreset::this_month(i18n),
+                                                DateRangePreset::previous_month(i18n),
+                                                DateRangePreset::quarter_to_date(i18n),
+                                                DateRangePreset::year_to_date(i18n),
+                                            ],
                                         }
                                     }
                                 },
@@ -868,7 +848,7 @@ pub fn Orders() -> Element {
                             span { class: "ui-field-helper", "{date_range_helper}" }
                         }
                         div { class: "ui-stack", style: "gap: 0.5rem;",
-                            Label { "订单金额 (¥)" }
+                            Label { {i18n.t("orders.filter.total")} }
                             Slider {
                                 value: min_total_value,
                                 min: 0.0,
@@ -879,15 +859,15 @@ pub fn Orders() -> Element {
                                     move |value: f32| setter.set(value)
                                 },
                             }
-                            span { class: "ui-field-helper", {format!("最低金额：¥{}", min_total_value as i32)} }
+                            span { class: "ui-field-helper", {i18n.t_with("orders.filter.min_total", &[("value", &format!("¥{}", min_total_value as i32))])} }
                         }
                         div { class: "ui-stack orders-filter-wide", style: "gap: 0.5rem;",
                             CheckboxChipGroup {
-                                label: "标签",
+                                label: i18n.t("orders.filter.tags"),
                                 values: tags_filter.clone(),
                                 options: tag_chip_options.clone(),
                             }
-                            span { class: "ui-field-helper", {format!("已选标签：{}", active_tag_count)} }
+                            span { class: "ui-field-helper", {i18n.t_with("orders.filter.selected_tags", &[("count", &active_tag_count)])} }
                         }
                     }
                 }
@@ -896,68 +876,68 @@ pub fn Orders() -> Element {
                         Button {
                             variant: ButtonVariant::Secondary,
                             size: ButtonSize::Sm,
-                            on_click: {
-                                let mut setter_search = search.clone();
-                                let mut setter_status = status_filter.clone();
-                                let mut setter_payment = payment_filter.clone();
-                                let mut setter_fulfillment = fulfillment_filter.clone();
-                                let mut setter_channel = channel_filter.clone();
-                                let mut setter_method = method_filter.clone();
-                                let mut setter_tags = tags_filter.clone();
-                                let mut setter_range = date_range.clone();
-                                let mut setter_min_total = min_total.clone();
-                                let mut setter_flagged = flagged_only.clone();
-                                let mut setter_pipeline = pipeline.clone();
-                                let mut setter_page = page.clone();
-                                move |_| {
-                                    setter_search.set(String::new());
-                                    setter_status.set(None);
-                                    setter_payment.set(None);
-                                    setter_fulfillment.set(None);
-                                    setter_channel.set(None);
-                                    setter_method.set(None);
-                                    setter_tags.set(Vec::new());
-                                    setter_range.set(None);
-                                    setter_min_total.set(0.0);
-                                    setter_flagged.set(false);
-                                    setter_pipeline.set(vec!["all".to_string()]);
-                                    setter_page.set(1);
-                                }
-                            },
-                            "重置筛选"
+                            on_click: move |_| apply_query(OrderQuery::default()),
+                            {i18n.t("orders.filter.reset")}
                         }
-                        Button {
-                            variant: ButtonVariant::Outline,
-                            size: ButtonSize::Sm,
-                            "导出报表"
+                        DropdownMenu {
+                            label: if exporting() { i18n.t("orders.export.running") } else { i18n.t("orders.export.label") },
+                            items: export_items,
+                            on_select: export_report,
+                        }
+                        if let Some(message) = export_message() {
+                            span { class: "ui-field-helper", "{message}" }
                         }
                     }
                 }
             }
 
+            OrderImport { on_imported: move |_| orders_page.restart() }
+
             Card {
                 CardHeader {
-                    CardTitle { "订单列表" }
-                    CardDescription { "结果会实时反映筛选条件，可分页浏览。" }
+                    CardTitle { {i18n.t("orders.list.title")} }
+                    CardDescription { {i18n.t("orders.list.description")} }
                 }
                 CardContent {
                     div { class: "ui-stack", style: "gap: 1rem;",
-                        if paginated_orders.is_empty() {
+                        OrderViews {
+                            current_layout: current_layout(),
+                            current_query: OrderQuery { page: 1, ..current_query() },
+                            apply_default: !opened_from_link,
+                            on_apply: move |view: TableView<OrderQuery>| {
+                                apply_query(view.state);
+                                current_layout.set(view.layout);
+                            },
+                        }
+                        OrderBulkActions {
+                            orders: selected_orders,
+                            on_applied: move |_| orders_page.restart(),
+                        }
+                        if let Some(message) = load_error.clone() {
+                            div { class: "orders-empty",
+                                span { class: "orders-metric-label", {i18n.t("orders.list.load_failed")} }
+                                span { class: "orders-metric-sub", "{message}" }
+                            }
+                        } else if is_loading {
                             div { class: "orders-empty",
-                                span { class: "orders-metric-label", "没有匹配的订单" }
-                                span { class: "orders-metric-sub", "调整筛选条件或清除限制重新查看。" }
+                                span { class: "orders-metric-label", {i18n.t("orders.list.loading")} }
+                            }
+                        } else if paginated_orders.is_empty() {
+                            div { class: "orders-empty",
+                                span { class: "orders-metric-label", {i18n.t("orders.list.empty")} }
+                                span { class: "orders-metric-sub", {i18n.t("orders.list.empty_hint")} }
                             }
                         } else {
                             // 示例1：基础表格
-                            h3 { style: "margin-top: 1rem;", "1. 基础表格 (Table)" }
+                            h3 { style: "margin-top: 1rem;", {i18n.t("orders.list.basic_table")} }
                             Table {
-                                TableCaption { "订单数据表格" }
+                                TableCaption { {i18n.t("orders.list.basic_caption")} }
                                 TableHeader {
                                     TableRow {
-                                        TableHead { "订单号" }
-                                        TableHead { "客户" }
-                                        TableHead { "状态" }
-                                        TableHead { "金额" }
+                                        TableHead { {i18n.t("orders.column.number")} }
+                                        TableHead { {i18n.t("orders.column.customer")} }
+                                        TableHead { {i18n.t("orders.column.status")} }
+                                        TableHead { {i18n.t("orders.column.total")} }
                                     }
                                 }
                                 TableBody {
@@ -966,7 +946,7 @@ pub fn Orders() -> Element {
                                             TableCell { "{order.number}" }
                                             TableCell { "{order.customer_name}" }
                                             TableCell {
-                                                Badge { variant: order.status.badge(), "{order.status.label()}" }
+                                                Badge { variant: status_badge(order.status), {order.status.label_in(i18n)} }
                                             }
                                             TableCell { {format!("¥{:.2}", order.total)} }
                                         }
@@ -974,7 +954,7 @@ pub fn Orders() -> Element {
                                 }
                                 TableFooter {
                                     TableRow {
-                                        TableCell { "总计 (前3项)" }
+                                        TableCell { {i18n.t("orders.list.basic_total")} }
                                         TableCell { }
                                         TableCell { }
                                         TableCell {
@@ -987,168 +967,56 @@ pub fn Orders() -> Element {
                                 }
                             }
 
-                            // 示例2：InteractiveTable with 行选择 + 列可见性控制
-                            h3 { style: "margin-top: 2rem;", "2. 高级数据表格 (InteractiveTable) - 行选择 + 列切换" }
-                            {
-                                let selected_rows = use_signal(|| std::collections::HashSet::<String>::new());
-                                let selected_count = selected_rows().len();
-
-                                let columns = vec![
-                                    TableColumnConfig {
-                                        id: "number".to_string(),
-                                        label: "订单号".to_string(),
-                                        toggleable: false,
-                                        visible_by_default: true,
-                                    },
-                                    TableColumnConfig {
-                                        id: "customer".to_string(),
-                                        label: "客户".to_string(),
-                                        toggleable: true,
-                                        visible_by_default: true,
-                                    },
-                                    TableColumnConfig {
-                                        id: "date".to_string(),
-                                        label: "日期".to_string(),
-                                        toggleable: true,
-                                        visible_by_default: true,
-                                    },
-                                    TableColumnConfig {
-                                        id: "status".to_string(),
-                                        label: "状态".to_string(),
-                                        toggleable: true,
-                                        visible_by_default: true,
-                                    },
-                                    TableColumnConfig {
-                                        id: "payment".to_string(),
-                                        label: "支付".to_string(),
-                                        toggleable: true,
-                                        visible_by_default: true,
-                                    },
-                                    TableColumnConfig {
-                                        id: "channel".to_string(),
-                                        label: "渠道".to_string(),
-                                        toggleable: true,
-                                        visible_by_default: false,
-                                    },
-                                    TableColumnConfig {
-                                        id: "total".to_string(),
-                                        label: "金额".to_string(),
-                                        toggleable: true,
-                                        visible_by_default: true,
-                                    },
-                                ];
-
-                                let rows: Vec<TableRowData> = paginated_orders
-                                    .iter()
-                                    .map(|order| {
-                                        let mut cells = std::collections::HashMap::new();
-                                        cells.insert("number".to_string(), order.number.clone());
-                                        cells.insert("customer".to_string(), format!("{} ({})", order.customer_name, order.customer_email));
-                                        cells.insert("date".to_string(), order.placed_on.format("%Y-%m-%d").to_string());
-                                        cells.insert("status".to_string(), order.status.label().to_string());
-                                        cells.insert("payment".to_string(), order.payment_status.label().to_string());
-                                        cells.insert("channel".to_string(), order.channel.label().to_string());
-                                        cells.insert("total".to_string(), format!("¥{:.2}", order.total));
-                                        TableRowData {
-                                            id: order.number.clone(),
-                                            cells,
-                                        }
-                                    })
-                                    .collect();
-
-                                rsx! {
-                                    div { style: "margin-bottom: 0.5rem;",
-                                        span { style: "font-size: 0.875rem; color: var(--muted-foreground);",
-                                            "已选择 {selected_count} 行"
-                                        }
-                                    }
-                                    InteractiveTable {
-                                        columns: columns,
-                                        rows: rows,
-                                        default_selected: Some(vec![]),
-                                        empty_state: Some("没有数据".to_string()),
-                                        on_selection_change: move |_selected: Vec<String>| {
-                                            // 选中的订单回调
-                                        }
-                                    }
+                            // 示例2：InteractiveTable + 类型化行数据，单元格可渲染任意组件
+                            h3 { style: "margin-top: 2rem;", {i18n.t("orders.list.interactive_table")} }
+                            div { class: "orders-views",
+                                Label { {i18n.t("orders.group.label")} }
+                                Select {
+                                    placeholder: i18n.t("orders.group.none"),
+                                    options: std::iter::once(SelectOption::new(i18n.t("orders.group.none"), "none"))
+                                        .chain(OrderGroupField::all().iter().map(|field| {
+                                            SelectOption::new(i18n.t(&format!("orders.column.{}", field.key())), field.key())
+                                        }))
+                                        .collect::<Vec<_>>(),
+                                    selected: Some(grouped_by.map_or("none", |field| field.key()).to_string()),
+                                    on_change: move |value: String| group_by.set(OrderGroupField::from_key(&value)),
                                 }
-                            }
-
-                            // 示例3：完整功能表格 (原始设计)
-                            h3 { style: "margin-top: 2rem;", "3. 完整功能表格" }
-                            Table {
-                                TableHeader {
-                                    TableRow {
-                                        TableHead { "订单信息" }
-                                        TableHead { "日期" }
-                                        TableHead { "状态" }
-                                        TableHead { "支付" }
-                                        TableHead { "履约" }
-                                        TableHead { "渠道" }
-                                        TableHead { "金额" }
-                                        TableHead { "标签" }
-                                        TableHead { "操作" }
-                                    }
+                                span { class: "ui-field-helper",
+                                    {i18n.t_count("orders.group.hint", filtered_total, &[])}
                                 }
-                                TableBody {
-                                    for order in paginated_orders.iter().cloned() {
-                                        {
-                                            let badge_variant = order.status.badge();
-                                            let initials = initials(&order.customer_name);
-                                            let date_display = order.placed_on.format("%Y-%m-%d").to_string();
-                                            rsx! {
-                                                TableRow {
-                                                    TableCell {
-                                                        div { style: "display: flex; align-items: center; gap: 0.75rem;",
-                                                            Avatar {
-                                                                fallback: Some(initials.clone()),
-                                                                alt: Some(order.customer_name.clone()),
-                                                            }
-                                                            div { style: "display: flex; flex-direction: column; gap: 0.25rem;",
-                                                                span { style: "font-weight: 600;", "{order.customer_name}" }
-                                                                span { class: "ui-field-helper", "{order.customer_email}" }
-                                                            }
-                                                        }
-                                                    }
-                                                    TableCell { "{date_display}" }
-                                                    TableCell {
-                                                        Badge { variant: badge_variant, "{order.status.label()}" }
-                                                    }
-                                                    TableCell {
-                                                        Badge {
-                                                            variant: match order.payment_status {
-                                                                PaymentStatus::Paid => BadgeVariant::Secondary,
-                                                                PaymentStatus::Refunded => BadgeVariant::Outline,
-                                                                PaymentStatus::Overdue => BadgeVariant::Destructive,
-                                                                PaymentStatus::Pending => BadgeVariant::Default,
-                                                            },
-                                                            "{order.payment_status.label()}"
-                                                        }
-                                                    }
-                                                    TableCell { "{order.fulfillment_status.label()}" }
-                                                    TableCell { "{order.channel.label()}" }
-                                                    TableCell { {format!("¥{:.2}", order.total)} }
-                                                    TableCell {
-                                                        div { class: "orders-tag-cloud",
-                                                            for tag in order.tags.iter().cloned() {
-                                                                {
-                                                                    rsx! { Badge { variant: BadgeVariant::Outline, "{tag}" } }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                    TableCell {
-                                                        Button {
-                                                            variant: ButtonVariant::Ghost,
-                                                            size: ButtonSize::Sm,
-                                                            "查看"
-                                                        }
-                                                    }
-                                                }
-                                            }
+                            }
+                            InteractiveTable {
+                                columns: order_columns(i18n),
+                                group_by: grouped_by.map(|field| field.key().to_string()),
+                                summary: total_summary,
+                                group_summaries,
+                                render_detail: move |order: Order| rsx! { OrderRowDetail { order } },
+                                rows: paginated_orders.clone(),
+                                default_selected: Some(vec![]),
+                                empty_state: Some(i18n.t("orders.list.no_data")),
+                                on_selection_change: move |selected: Vec<String>| {
+                                    selected_numbers.set(selected)
+                                },
+                                layout: Some(current_layout()),
+                                on_layout_change: move |layout: TableLayout| current_layout.set(layout),
+                                sort: table_sort(&sort_selected),
+                                manual_sort: true,
+                                on_sort_change: {
+                                    let mut setter = sort;
+                                    move |sorts: Vec<TableSort>| setter.set(order_sort(&sorts))
+                                },
+                                on_cell_edit: move |edit: CellEdit| {
+                                    let Some(change) = order_edit(&edit.column_id, &edit.new_value) else {
+                                        edit.reject(i18n.t("orders.edit.unrecognized"));
+                                        return;
+                                    };
+                                    spawn(async move {
+                                        match edit_order(edit.row_id.clone(), change, business_zone()).await {
+                                            Ok(_) => orders_page.restart(),
+                                            Err(err) => edit.reject(i18n.t_with("orders.edit.failed", &[("error", &err)])),
                                         }
-                                    }
-                                }
+                                    });
+                                },
                             }
                         }
                     }
@@ -1158,9 +1026,9 @@ pub fn Orders() -> Element {
                         Pagination {
                             total_pages: page_count,
                             current_page: effective_page,
-                            on_page_change: {
-                                let mut setter = page.clone();
-                                move |page_index: usize| setter.set(page_index)
+                            on_page_change: move |page_index: usize| {
+                                let filters = OrderQuery { page: 1, ..current_query.peek().clone() };
+                                page.set((filters, page_index))
                             },
                         }
                     }